@@ -66,7 +66,7 @@ impl GraphConfig {
             }
         }).collect();
         
-        Ok(GraphDef { nodes, edges })
+        Ok(GraphDef::new(nodes, edges))
     }
     
     /// Get the list of node IDs in topological order (useful for initialization)
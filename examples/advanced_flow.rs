@@ -13,7 +13,7 @@ async fn main() -> anyhow::Result<()> {
     println!("Scenario: User Analytics Report Generation with Permission Checks\n");
 
     // Load graph definition from YAML
-    let def = GraphIO::load_from_file("examples/advanced_flow.yaml")?;
+    let def = GraphIO::load_from_yaml_file("examples/advanced_flow.yaml")?;
 
     // Create custom executor with specific node configurations
     let mut executor = Executor::new();
@@ -1,52 +1,105 @@
-use rust_logic_graph::node::{CircuitBreakerNode, Node};
 /// Example: Circuit Breaker pattern for preventing cascading failures
 ///
 /// This example demonstrates CircuitBreakerNode for protecting services
-/// from overload and preventing cascading failures.
-use rust_logic_graph::Context;
+/// from overload and preventing cascading failures. The breaker state is
+/// kept in the executor's context store, so it carries over between runs.
+use async_trait::async_trait;
+use rust_logic_graph::distributed::{ContextStore, InMemoryStore};
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleError,
+    RuleResult,
+};
 use serde_json::json;
+use std::sync::Arc;
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    println!("=== Circuit Breaker Example ===\n");
+/// External API that fails while `api_down` is set in the context
+struct ExternalApiNode {
+    id: String,
+}
 
-    // Example 1: Circuit closed (normal operation)
-    println!("Example 1: Circuit closed - request goes through");
+#[async_trait]
+impl Node for ExternalApiNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("ExternalApi".to_string())
+    }
 
-    let circuit_breaker: Box<dyn Node> = Box::new(CircuitBreakerNode::new(
-        "protected_service",
-        "external_api_call",
-        5, // Open circuit after 5 failures
-    ));
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        if ctx
+            .get("api_down")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            return Err(RuleError::Eval("503 Service Unavailable".to_string()));
+        }
 
-    let mut ctx = Context::new();
-    ctx.set("_circuit_open", json!(false));
+        let result = json!({ "status": 200 });
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
 
-    let result = circuit_breaker.run(&mut ctx).await?;
-    println!("Result: {}", serde_json::to_string_pretty(&result)?);
-    println!("Circuit state: {}\n", result.get("circuit_state").unwrap());
+const GRAPH_YAML: &str = r#"
+nodes:
+  external_api_call:
+    node_type: ExternalApi
+  protected_service:
+    node_type: CircuitBreakerNode
+    target_node: external_api_call
+    failure_threshold: 3
+    recovery_timeout_ms: 60000
+edges: []
+"#;
 
-    // Example 2: Circuit open (fast-fail)
-    println!("Example 2: Circuit open - request rejected immediately");
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("=== Circuit Breaker Example ===\n");
 
-    let circuit_breaker: Box<dyn Node> = Box::new(CircuitBreakerNode::new(
-        "failing_service",
-        "unreliable_endpoint",
-        3,
-    ));
+    let mut registry = NodeRegistry::default();
+    registry.register("ExternalApi", |id: &str, _config: &NodeConfig| {
+        Ok(Box::new(ExternalApiNode { id: id.to_string() }) as Box<dyn Node>)
+    });
+    let registry = Arc::new(registry);
+    let store: Arc<dyn ContextStore> = Arc::new(InMemoryStore::new());
 
-    let mut ctx = Context::new();
-    ctx.set("_circuit_open", json!(true));
+    let def = GraphIO::from_yaml(GRAPH_YAML)?;
+    let mut executor = Executor::from_graph_def_with_registry(&def, registry.clone())?;
+    executor.set_context_store(store.clone());
+
+    // Example 1: Circuit closed (normal operation)
+    println!("Example 1: Circuit closed - request goes through");
+    let mut graph = Graph::new(def.clone());
+    executor.execute(&mut graph).await?;
+    let result = graph.context.get("protected_service_result").unwrap();
+    println!("Result: {}", serde_json::to_string_pretty(result)?);
+    println!("Circuit state: {}\n", result["circuit_state"]);
 
-    let result = circuit_breaker.run(&mut ctx).await?;
-    println!("Result: {}", serde_json::to_string_pretty(&result)?);
-    println!("Status: {}", result.get("status").unwrap());
-    println!("Message: {}", result.get("message").unwrap());
+    // Example 2: Repeated failures open the circuit
+    println!("Example 2: Service goes down - circuit opens after 3 failures");
+    for request in 1..=5 {
+        let mut graph = Graph::new(def.clone());
+        graph.context.set("api_down", json!(true));
+        executor.execute(&mut graph).await?;
+        println!(
+            "  Request {}: failed (nodes failed: {})",
+            request,
+            executor.metrics().nodes_failed
+        );
+    }
 
-    println!("\n💡 Note: This is a simplified example.");
-    println!("   In production, circuit breaker state should be shared");
-    println!("   across requests and automatically transition between");
-    println!("   Closed → Open → HalfOpen → Closed states.");
+    // Example 3: The open circuit is restored from the store by a new executor
+    println!("\nExample 3: New executor sharing the store - request rejected immediately");
+    let mut executor = Executor::from_graph_def_with_registry(&def, registry)?;
+    executor.set_context_store(store);
+    let mut graph = Graph::new(def);
+    executor.execute(&mut graph).await?;
+    println!(
+        "Request rejected without calling the API: {}",
+        executor.metrics().nodes_failed == 1
+    );
 
     Ok(())
 }
@@ -38,8 +38,10 @@ async fn main() -> anyhow::Result<()> {
     executor.register_node(Box::new(notify_supplier));
 
     // Create graph definition
-    let graph_def = GraphDef {
-        nodes: vec![
+    // The branch edges only fire for the branch the router selected; the
+    // other branch is skipped
+    let graph_def = GraphDef::new(
+        vec![
             ("check_inventory".to_string(), Default::default()),
             ("route_based_on_stock".to_string(), Default::default()),
             ("process_order".to_string(), Default::default()),
@@ -47,12 +49,14 @@ async fn main() -> anyhow::Result<()> {
         ]
         .into_iter()
         .collect(),
-        edges: vec![
+        vec![
             Edge::new("check_inventory", "route_based_on_stock"),
-            Edge::new("route_based_on_stock", "process_order"),
-            Edge::new("route_based_on_stock", "notify_supplier"),
+            Edge::new("route_based_on_stock", "process_order")
+                .with_rule("_branch_taken == \"process_order\""),
+            Edge::new("route_based_on_stock", "notify_supplier")
+                .with_rule("_branch_taken == \"notify_supplier\""),
         ],
-    };
+    );
 
     // Test Case 1: High inventory (should take process_order branch)
     println!("Test 1: High inventory (available = 150)");
@@ -63,6 +67,7 @@ async fn main() -> anyhow::Result<()> {
 
     let branch_taken = graph.context.get("_branch_taken");
     println!("Branch taken: {:?}", branch_taken);
+    println!("Nodes skipped: {}", executor.metrics().nodes_skipped);
     println!(
         "Result: {:?}\n",
         graph.context.data.get("route_based_on_stock_result")
@@ -77,6 +82,7 @@ async fn main() -> anyhow::Result<()> {
 
     let branch_taken = graph.context.get("_branch_taken");
     println!("Branch taken: {:?}", branch_taken);
+    println!("Nodes skipped: {}", executor.metrics().nodes_skipped);
     println!(
         "Result: {:?}",
         graph.context.data.get("route_based_on_stock_result")
@@ -49,7 +49,7 @@ async fn main() -> anyhow::Result<()> {
         },
     ];
 
-    let def = GraphDef { nodes, edges };
+    let def = GraphDef::new(nodes, edges);
     let mut graph = Graph::new(def);
 
     // Initialize context with parameter values
@@ -66,7 +66,7 @@ async fn main() -> anyhow::Result<()> {
     println!("   status: pending\n");
 
     // Create executor and run
-    let mut executor = Executor::from_graph_def(&graph.def)?;
+    let executor = Executor::from_graph_def(&graph.def)?;
     executor.execute(&mut graph).await?;
 
     println!("\n✅ Graph execution completed!");
@@ -1,48 +1,101 @@
-use rust_logic_graph::node::{Node, TryCatchNode};
 /// Example: Error handling with Try/Catch pattern
 ///
 /// This example demonstrates TryCatchNode for graceful error handling
-/// and recovery strategies.
-use rust_logic_graph::Context;
+/// and recovery strategies. The try, catch and finally nodes are regular
+/// graph nodes that the TryCatchNode runs through the executor.
+use async_trait::async_trait;
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleError,
+    RuleResult,
+};
 use serde_json::json;
+use std::sync::Arc;
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    println!("=== Try/Catch Flow Example ===\n");
+/// Database call that fails when `db_down` is set in the context
+struct DatabaseCallNode {
+    id: String,
+}
 
-    // Example 1: Successful operation (no error)
-    println!("Example 1: Normal execution (no errors)");
+#[async_trait]
+impl Node for DatabaseCallNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
 
-    let try_catch: Box<dyn Node> = Box::new(
-        TryCatchNode::new("safe_operation", "risky_database_call")
-            .with_catch("fallback_handler")
-            .with_finally("cleanup_resources"),
-    );
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("DatabaseCall".to_string())
+    }
 
-    let mut ctx = Context::new();
-    ctx.set("_simulate_error", json!(false));
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        if ctx
+            .get("db_down")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            return Err(RuleError::Eval("database unavailable".to_string()));
+        }
 
-    let result = try_catch.run(&mut ctx).await?;
-    println!("Result: {}", serde_json::to_string_pretty(&result)?);
-    println!("Status: {}\n", result.get("status").unwrap());
+        let result = json!({ "rows": 3 });
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
 
-    // Example 2: Error occurs, catch handler executes
-    println!("Example 2: Error occurs and is caught");
+const GRAPH_YAML: &str = r#"
+nodes:
+  risky_database_call:
+    node_type: DatabaseCall
+  fallback_handler:
+    node_type: RuleNode
+    condition: "true"
+  cleanup_resources:
+    node_type: RuleNode
+    condition: "true"
+  safe_operation:
+    node_type: TryCatchNode
+    try_node: risky_database_call
+    catch_node: fallback_handler
+    finally_node: cleanup_resources
+edges: []
+"#;
 
-    let try_catch: Box<dyn Node> = Box::new(
-        TryCatchNode::new("error_prone_task", "might_fail_operation").with_catch("error_recovery"),
-    );
+async fn run_example(db_down: bool) -> anyhow::Result<()> {
+    let mut registry = NodeRegistry::default();
+    registry.register("DatabaseCall", |id: &str, _config: &NodeConfig| {
+        Ok(Box::new(DatabaseCallNode { id: id.to_string() }) as Box<dyn Node>)
+    });
 
-    let mut ctx = Context::new();
-    ctx.set("_simulate_error", json!(true));
+    let mut graph = Graph::new(GraphIO::from_yaml(GRAPH_YAML)?);
+    graph.context.set("db_down", json!(db_down));
 
-    let result = try_catch.run(&mut ctx).await?;
-    println!("Result: {}", serde_json::to_string_pretty(&result)?);
-    println!("Status: {}", result.get("status").unwrap());
+    let executor = Executor::from_graph_def_with_registry(&graph.def, Arc::new(registry))?;
+    executor.execute(&mut graph).await?;
 
-    if let Some(error) = ctx.data.get("_error") {
+    let result = graph.context.get("safe_operation_result").unwrap();
+    println!("Result: {}", serde_json::to_string_pretty(result)?);
+    println!("Status: {}", result["status"]);
+
+    if let Some(error) = graph.context.get("_error") {
         println!("Error message: {}", error);
     }
+    println!(
+        "Finally block ran: {}\n",
+        graph.context.get("cleanup_resources_result").is_some()
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("=== Try/Catch Flow Example ===\n");
+
+    // Example 1: Successful operation (no error)
+    println!("Example 1: Normal execution (no errors)");
+    run_example(false).await?;
+
+    // Example 2: Error occurs, catch handler executes
+    println!("Example 2: Error occurs and is caught");
+    run_example(true).await?;
 
     Ok(())
 }
@@ -13,7 +13,7 @@ async fn main() -> anyhow::Result<()> {
     println!("Scenario: Loan Application with Advanced GRL Rules\n");
 
     // Load graph definition from YAML
-    let def = GraphIO::load_from_file("examples/grl_graph_flow.yaml")?;
+    let def = GraphIO::load_from_yaml_file("examples/grl_graph_flow.yaml")?;
 
     // Create custom executor with GRL-powered nodes
     let mut executor = Executor::new();
@@ -75,15 +75,27 @@ async fn main() -> anyhow::Result<()> {
         .data
         .insert("customer_id".to_string(), json!(12345));
 
+    // Scores from the upstream scoring service, read by approval_decision
+    graph
+        .context
+        .data
+        .insert("risk_score".to_string(), json!(35));
+    graph
+        .context
+        .data
+        .insert("fraud_score".to_string(), json!(12));
+
     println!("Application Data:");
     println!("  Loan Amount: $50,000");
     println!("  Credit Score: 720");
     println!("  Annual Income: $180,000");
-    println!("  Customer ID: 12345\n");
+    println!("  Customer ID: 12345");
+    println!("  Risk Score: 35");
+    println!("  Fraud Score: 12\n");
 
     // Execute the graph
     println!("Processing loan application through GRL-powered workflow...\n");
-    executor.execute(&mut graph).await?;
+    let result = executor.execute(&mut graph).await?;
 
     // Display results
     println!("\n=== Application Results ===\n");
@@ -108,6 +120,13 @@ async fn main() -> anyhow::Result<()> {
         println!("✓ Approval Decision: {}", decision);
     }
 
+    if !result.is_success() {
+        for (node_id, error) in result.failures() {
+            eprintln!("✗ Node '{}' failed: {}", node_id, error);
+        }
+        std::process::exit(1);
+    }
+
     println!("\n=== GRL-Powered Workflow Complete ===");
 
     // Demonstrate standalone GRL engine
@@ -1,61 +1,128 @@
-use rust_logic_graph::node::{Node, RetryNode};
 /// Example: Retry logic with exponential backoff
 ///
 /// This example demonstrates RetryNode for handling transient failures
-/// with automatic retry and exponential backoff.
-use rust_logic_graph::Context;
+/// with automatic retry and exponential backoff. The RetryNode re-runs its
+/// target node through the executor until it succeeds or retries run out.
+use async_trait::async_trait;
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleError,
+    RuleResult,
+};
 use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    println!("=== Retry Flow Example ===\n");
+/// Node that fails its first `failures` calls, then succeeds
+struct FlakyApiNode {
+    id: String,
+    failures: usize,
+    calls: AtomicUsize,
+}
 
-    // Example 1: Successful retry after failures
-    println!("Example 1: API call with retry (simulated success on 3rd attempt)");
+#[async_trait]
+impl Node for FlakyApiNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("FlakyApi".to_string())
+    }
 
-    let retry_node: Box<dyn Node> = Box::new(
-        RetryNode::new(
-            "api_call_with_retry",
-            "call_external_api",
-            3, // Max 3 retries
-        )
-        .with_backoff(100, 2.0),
-    ); // Start with 100ms, double each time
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if call <= self.failures {
+            println!("  {} call #{}: connection reset", self.id, call);
+            return Err(RuleError::Eval("connection reset".to_string()));
+        }
 
-    let mut ctx = Context::new();
-    ctx.set("_simulate_failure", json!(false)); // Will succeed
+        println!("  {} call #{}: ok", self.id, call);
+        let result = json!({ "status": 200 });
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
+
+fn registry() -> Arc<NodeRegistry> {
+    let mut registry = NodeRegistry::default();
+    registry.register("FlakyApi", |id: &str, config: &NodeConfig| {
+        let failures = config
+            .settings
+            .as_ref()
+            .and_then(|s| s.get("failures"))
+            .and_then(|f| f.as_u64())
+            .unwrap_or(0) as usize;
+        Ok(Box::new(FlakyApiNode {
+            id: id.to_string(),
+            failures,
+            calls: AtomicUsize::new(0),
+        }) as Box<dyn Node>)
+    });
+    Arc::new(registry)
+}
+
+async fn run_example(yaml: &str) -> anyhow::Result<()> {
+    let def = GraphIO::from_yaml(yaml)?;
+    let mut graph = Graph::new(def);
+    let executor = Executor::from_graph_def_with_registry(&graph.def, registry())?;
 
-    println!("Starting retry logic...");
     let start = std::time::Instant::now();
-    let result = retry_node.run(&mut ctx).await?;
+    executor.execute(&mut graph).await?;
     let elapsed = start.elapsed();
 
-    println!("Result: {}", serde_json::to_string_pretty(&result)?);
+    if executor.metrics().nodes_failed > 0 {
+        println!("Result: retries exhausted, node failed");
+    } else {
+        let result = graph.context.get("api_call_with_retry_result").unwrap();
+        println!("Result: {}", serde_json::to_string_pretty(result)?);
+    }
     println!("Total time: {:?}\n", elapsed);
+    Ok(())
+}
 
-    // Example 2: All retries exhausted
-    println!("Example 2: All retries fail");
-
-    let retry_node: Box<dyn Node> = Box::new(
-        RetryNode::new(
-            "failing_operation",
-            "call_flaky_service",
-            2, // Max 2 retries
-        )
-        .with_backoff(50, 1.5),
-    );
-
-    let mut ctx = Context::new();
-    ctx.set("_simulate_failure", json!(true)); // Will always fail
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("=== Retry Flow Example ===\n");
 
-    println!("Starting retry logic...");
-    let start = std::time::Instant::now();
-    let result = retry_node.run(&mut ctx).await?;
-    let elapsed = start.elapsed();
+    // Example 1: Successful retry after failures
+    println!("Example 1: API call with retry (succeeds on 3rd attempt)");
+    run_example(
+        r#"
+nodes:
+  call_external_api:
+    node_type: FlakyApi
+    settings:
+      failures: 2
+  api_call_with_retry:
+    node_type: RetryNode
+    target_node: call_external_api
+    max_retries: 3
+    initial_delay_ms: 100
+    backoff_multiplier: 2.0
+edges: []
+"#,
+    )
+    .await?;
 
-    println!("Result: {}", serde_json::to_string_pretty(&result)?);
-    println!("Total time: {:?}", elapsed);
-    println!("Status: {}", result.get("status").unwrap());
+    // Example 2: All retries exhausted
+    println!("Example 2: All retries fail");
+    run_example(
+        r#"
+nodes:
+  call_external_api:
+    node_type: FlakyApi
+    settings:
+      failures: 10
+  api_call_with_retry:
+    node_type: RetryNode
+    target_node: call_external_api
+    max_retries: 2
+    initial_delay_ms: 50
+    backoff_multiplier: 1.5
+edges: []
+"#,
+    )
+    .await?;
 
     Ok(())
 }
@@ -3,9 +3,12 @@
 
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use rust_logic_graph::rule::lint::{written_facts, RuleLinter};
+use rust_logic_graph::rule::testing::RuleTestSuite;
+use rust_logic_graph::rule::GRLParser;
 use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Helper function to read and parse YAML graph file
@@ -32,6 +35,10 @@ enum Commands {
         #[arg(short, long)]
         file: PathBuf,
 
+        /// Input context file; its keys count as facts provided to GRL rules
+        #[arg(short, long)]
+        context: Option<PathBuf>,
+
         /// Show detailed validation information
         #[arg(short, long)]
         verbose: bool,
@@ -69,14 +76,33 @@ enum Commands {
         #[arg(short, long)]
         verbose: bool,
     },
+
+    /// Run GRL rule tests from a YAML/JSON fixture file
+    TestRules {
+        /// Path to the rule test fixture file
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// GRL file to test, overriding the fixture's `rules`
+        #[arg(short, long)]
+        rules: Option<PathBuf>,
+
+        /// Show the rule trace of every test
+        #[arg(short, long)]
+        verbose: bool,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Validate { file, verbose } => {
-            validate_graph(file, verbose);
+        Commands::Validate {
+            file,
+            context,
+            verbose,
+        } => {
+            validate_graph(file, context, verbose);
         }
         Commands::Visualize { file, details } => {
             visualize_graph(file, details);
@@ -87,10 +113,17 @@ fn main() {
         Commands::DryRun { file, verbose } => {
             dry_run_graph(file, verbose);
         }
+        Commands::TestRules {
+            file,
+            rules,
+            verbose,
+        } => {
+            test_rules(file, rules, verbose);
+        }
     }
 }
 
-fn validate_graph(file: PathBuf, verbose: bool) {
+fn validate_graph(file: PathBuf, context: Option<PathBuf>, verbose: bool) {
     println!("{}", "🔍 Validating graph...".cyan().bold());
     println!("File: {}\n", file.display());
 
@@ -158,6 +191,16 @@ fn validate_graph(file: PathBuf, verbose: bool) {
         }
     }
 
+    // Lint GRL rules used by RuleNodes and edge rules
+    lint_graph_rules(
+        &graph_def,
+        &file,
+        context.as_ref(),
+        verbose,
+        &mut errors,
+        &mut warnings,
+    );
+
     // Display results
     println!();
     if errors.is_empty() {
@@ -186,6 +229,116 @@ fn validate_graph(file: PathBuf, verbose: bool) {
     }
 }
 
+/// Run the rule linter over every GRL rule set the graph references.
+///
+/// Facts count as provided when they are node results, node input ports,
+/// subgraph outputs, keys of the context file, or set by any rule set in the
+/// graph.
+fn lint_graph_rules(
+    graph_def: &Value,
+    file: &Path,
+    context: Option<&PathBuf>,
+    verbose: bool,
+    errors: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    let mut sources: Vec<(String, String)> = Vec::new();
+    let mut provided: Vec<String> = Vec::new();
+
+    if let Some(nodes) = graph_def.get("nodes").and_then(|n| n.as_object()) {
+        for (node_id, node) in nodes {
+            provided.push(format!("{}_result", node_id));
+            if let Some(inputs) = node.get("inputs").and_then(|i| i.as_object()) {
+                provided.extend(inputs.keys().cloned());
+            }
+            if let Some(mapping) = node.get("output_mapping").and_then(|m| m.as_object()) {
+                provided.extend(
+                    mapping
+                        .values()
+                        .filter_map(|v| v.as_str())
+                        .map(String::from),
+                );
+            }
+
+            if let Some(grl) = node.get("grl").and_then(|g| g.as_str()) {
+                sources.push((format!("Node '{}'", node_id), grl.to_string()));
+            }
+            if let Some(grl_file) = node.get("grl_file").and_then(|g| g.as_str()) {
+                // Relative to the working directory, like the executor, or
+                // else to the graph file
+                let path = Path::new(grl_file);
+                let path = if path.exists() {
+                    path.to_path_buf()
+                } else {
+                    file.parent().unwrap_or(Path::new("")).join(path)
+                };
+                match fs::read_to_string(&path) {
+                    Ok(grl) => {
+                        sources.push((format!("Node '{}' ({})", node_id, path.display()), grl))
+                    }
+                    Err(e) => errors.push(format!(
+                        "Node '{}': failed to read GRL file '{}': {}",
+                        node_id, grl_file, e
+                    )),
+                }
+            }
+        }
+    }
+    if let Some(rules) = graph_def.get("rules").and_then(|r| r.as_object()) {
+        for (name, rule) in rules {
+            if let Some(grl) = rule.get("grl").and_then(|g| g.as_str()) {
+                sources.push((format!("Rule '{}'", name), grl.to_string()));
+            }
+        }
+    }
+    if sources.is_empty() {
+        return;
+    }
+
+    if let Some(context) = context {
+        match read_graph_file(context) {
+            Ok(Value::Object(map)) => provided.extend(map.keys().cloned()),
+            Ok(_) => errors.push(format!(
+                "Context file '{}' is not a mapping",
+                context.display()
+            )),
+            Err(e) => errors.push(format!("Context file '{}': {}", context.display(), e)),
+        }
+    }
+
+    let mut parsed = Vec::new();
+    for (label, grl) in sources {
+        match GRLParser::parse_rules(&grl) {
+            Ok(rules) => {
+                provided.extend(written_facts(&rules));
+                parsed.push((label, rules));
+            }
+            Err(e) => errors.push(format!("{}: failed to parse GRL: {}", label, e)),
+        }
+    }
+
+    let linter = RuleLinter::new().provided_facts(provided);
+    for (label, rules) in parsed {
+        let lints = linter.lint_rules(&rules);
+        if verbose {
+            println!(
+                "\n  Rules: {} ({} rules, {} issues)",
+                label.green(),
+                rules.len(),
+                lints.len()
+            );
+        }
+        for lint in lints {
+            let message = format!("{}: {}", label, lint);
+            if lint.is_error() {
+                errors.push(message);
+            } else {
+                warnings.push(message);
+            }
+        }
+    }
+}
+
 fn visualize_graph(file: PathBuf, details: bool) {
     println!("{}", "🎨 Graph Visualization".cyan().bold());
     println!("{}\n", "═".repeat(80).cyan());
@@ -546,3 +699,64 @@ fn dry_run_graph(file: PathBuf, verbose: bool) {
         "✓".green().bold()
     );
 }
+
+fn test_rules(file: PathBuf, rules: Option<PathBuf>, verbose: bool) {
+    println!("{}", "🧪 Running rule tests...".cyan().bold());
+    println!("File: {}", file.display());
+
+    let mut suite = match RuleTestSuite::from_file(&file) {
+        Ok(suite) => suite,
+        Err(e) => {
+            eprintln!("{} {}", "✗".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+    if rules.is_some() {
+        suite.rules = rules;
+    }
+    if let Some(rules) = &suite.rules {
+        println!("Rules: {}\n", rules.display());
+    }
+
+    let report = match suite.run() {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{} {}", "✗".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+
+    for result in &report.results {
+        if result.passed() {
+            println!("  {} {}", "✓".green().bold(), result.name);
+        } else {
+            println!("  {} {}", "✗".red().bold(), result.name.red());
+            for failure in &result.failures {
+                println!("      {} {}", "•".red(), failure);
+            }
+        }
+        if verbose {
+            for line in result.trace.to_string().lines() {
+                println!("      {}", line.dimmed());
+            }
+        }
+    }
+
+    let failed = report.failures().count();
+    println!();
+    if failed == 0 {
+        println!(
+            "{} All {} rule tests passed!",
+            "✓".green().bold(),
+            report.results.len()
+        );
+    } else {
+        println!(
+            "{} {} of {} rule tests failed",
+            "✗".red().bold(),
+            failed,
+            report.results.len()
+        );
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,156 @@
+//! Checkpoints of graph executions, for resuming after a crash.
+//!
+//! [`Executor::execute_checkpointed`] saves a [`Checkpoint`] to the
+//! executor's [`ContextStore`] every time a node finishes. Calling it again
+//! with the same execution id, from any process sharing the store, restores
+//! the checkpointed context and skips every node that already ran.
+//!
+//! [`Executor::execute_checkpointed`]: crate::core::Executor::execute_checkpointed
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use crate::distributed::{ContextStore, DistributedContext};
+use crate::error::{Result, RustLogicGraphError};
+
+/// State of an execution after its most recently finished node
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub execution_id: String,
+    /// Context with the changes of every completed node merged in
+    pub context: HashMap<String, Value>,
+    /// Nodes that ran, successfully or not, in completion order
+    pub completed: Vec<String>,
+    /// Nodes skipped because none of their incoming edges were taken
+    pub skipped: Vec<String>,
+    /// Nodes that were still running; they run again on resume
+    pub pending: Vec<String>,
+    /// Whether the execution ran to the end
+    pub finished: bool,
+    /// Last update (Unix timestamp in milliseconds)
+    pub updated_at: u64,
+}
+
+impl Checkpoint {
+    /// Checkpoint of an execution that has not run any node yet
+    pub fn new(execution_id: impl Into<String>, context: HashMap<String, Value>) -> Self {
+        Self {
+            execution_id: execution_id.into(),
+            context,
+            ..Default::default()
+        }
+    }
+
+    /// Key the checkpoint of `execution_id` is stored under
+    pub fn store_key(execution_id: &str) -> String {
+        format!("checkpoint:{}", execution_id)
+    }
+
+    /// Ids of the nodes that ran before the checkpoint was taken
+    pub fn completed_nodes(&self) -> HashSet<&str> {
+        self.completed.iter().map(String::as_str).collect()
+    }
+
+    /// Ids of the nodes skipped before the checkpoint was taken
+    pub fn skipped_nodes(&self) -> HashSet<&str> {
+        self.skipped.iter().map(String::as_str).collect()
+    }
+
+    /// Load the checkpoint of `execution_id`, if one was saved
+    pub async fn load(store: &dyn ContextStore, execution_id: &str) -> Result<Option<Self>> {
+        let stored = store
+            .load(&Self::store_key(execution_id))
+            .await
+            .map_err(|e| {
+                store_error(format!(
+                    "Failed to load checkpoint for execution '{}': {:#}",
+                    execution_id, e
+                ))
+            })?;
+        let Some(stored) = stored else {
+            return Ok(None);
+        };
+        let checkpoint = serde_json::from_value(Value::Object(stored.data.into_iter().collect()))
+            .map_err(|e| {
+            RustLogicGraphError::serialization_error(format!(
+                "Invalid checkpoint for execution '{}': {}",
+                execution_id, e
+            ))
+            .with_source(e)
+        })?;
+        Ok(Some(checkpoint))
+    }
+
+    /// Save the checkpoint, replacing the previous one of this execution
+    pub async fn save(&mut self, store: &dyn ContextStore) -> Result<()> {
+        self.updated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+        let mut stored = DistributedContext::new(Self::store_key(&self.execution_id));
+        let fields = serde_json::to_value(&*self).map_err(|e| {
+            RustLogicGraphError::serialization_error(format!(
+                "Failed to serialize checkpoint for execution '{}': {}",
+                self.execution_id, e
+            ))
+            .with_source(e)
+        })?;
+        if let Value::Object(fields) = fields {
+            stored.data = fields.into_iter().collect();
+        }
+        store.save(&stored, None).await.map_err(|e| {
+            store_error(format!(
+                "Failed to save checkpoint for execution '{}': {:#}",
+                self.execution_id, e
+            ))
+        })
+    }
+
+    /// Delete the checkpoint of `execution_id`
+    pub async fn delete(store: &dyn ContextStore, execution_id: &str) -> Result<()> {
+        store
+            .delete(&Self::store_key(execution_id))
+            .await
+            .map_err(|e| {
+                store_error(format!(
+                    "Failed to delete checkpoint for execution '{}': {:#}",
+                    execution_id, e
+                ))
+            })
+    }
+}
+
+fn store_error(message: String) -> RustLogicGraphError {
+    RustLogicGraphError::distributed_error(message, "context_store")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::InMemoryStore;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let store = InMemoryStore::new();
+        assert!(Checkpoint::load(&store, "order-1").await.unwrap().is_none());
+
+        let mut checkpoint = Checkpoint::new(
+            "order-1",
+            HashMap::from([("order_id".to_string(), json!(1))]),
+        );
+        checkpoint.completed.push("fetch".to_string());
+        checkpoint.skipped.push("review".to_string());
+        checkpoint.save(&store).await.unwrap();
+        assert!(checkpoint.updated_at > 0);
+
+        let loaded = Checkpoint::load(&store, "order-1").await.unwrap().unwrap();
+        assert_eq!(loaded, checkpoint);
+        assert_eq!(loaded.completed_nodes(), HashSet::from(["fetch"]));
+        assert_eq!(loaded.skipped_nodes(), HashSet::from(["review"]));
+
+        Checkpoint::delete(&store, "order-1").await.unwrap();
+        assert!(Checkpoint::load(&store, "order-1").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,239 @@
+//! Edge conditions compiled from a [`GraphDef`].
+//!
+//! An edge's `rule` either names a rule in [`GraphDef::rules`] or is itself an
+//! inline condition expression. A bare name that is not defined under `rules`
+//! is rejected rather than read as a context variable.
+//! [`EdgeConditions::compile`] resolves every edge once (building GRL engines
+//! up front) so the executor only evaluates them.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::{GraphDef, RuleDef};
+use crate::error::RustLogicGraphError;
+use crate::rule::{KnowledgeBase, Rule, RuleEngine, RuleError};
+
+/// A compiled condition guarding one or more edges
+pub enum EdgeCondition {
+    /// Expression evaluated with [`Rule`]
+    Expression(Rule),
+    /// GRL rule set; the condition holds when the rules set `outcome` to `true`.
+    /// Each evaluation runs the rules in an engine of its own, so concurrent
+    /// runs sharing a plan do not wait for each other.
+    Grl {
+        rules: Box<KnowledgeBase>,
+        outcome: String,
+    },
+}
+
+impl EdgeCondition {
+    /// Compile a named rule definition
+    pub fn from_rule_def(name: &str, def: &RuleDef) -> Result<Self, RuleError> {
+        match def {
+            RuleDef::Condition(condition) => {
+                Ok(EdgeCondition::Expression(Rule::compile(name, condition)?))
+            }
+            RuleDef::Grl { grl, outcome } => {
+                let engine = RuleEngine::from_grl(grl)?;
+                if engine.inner().knowledge_base().rule_count() == 0 {
+                    return Err(RuleError::InvalidExpression(format!(
+                        "GRL for rule '{}' does not define any rules",
+                        name
+                    )));
+                }
+                Ok(EdgeCondition::Grl {
+                    rules: Box::new(engine.inner().knowledge_base().clone()),
+                    outcome: outcome.clone(),
+                })
+            }
+        }
+    }
+
+    /// Evaluate the condition against the context data
+    pub fn evaluate(&self, data: &HashMap<String, Value>) -> Result<bool, RuleError> {
+        match self {
+            EdgeCondition::Expression(rule) => rule.evaluate_bool(data),
+            EdgeCondition::Grl { rules, outcome } => {
+                let mut engine = RuleEngine::from_knowledge_base(rules.as_ref().clone());
+                let facts = engine.evaluate(data)?;
+                Ok(facts.get(outcome).and_then(Value::as_bool).unwrap_or(false))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for EdgeCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdgeCondition::Expression(rule) => f.debug_tuple("Expression").field(rule).finish(),
+            EdgeCondition::Grl { outcome, .. } => {
+                f.debug_struct("Grl").field("outcome", outcome).finish()
+            }
+        }
+    }
+}
+
+/// Conditions of all edges of a graph, indexed like `GraphDef::edges`
+#[derive(Debug, Default)]
+pub struct EdgeConditions {
+    by_edge: Vec<Option<Arc<EdgeCondition>>>,
+}
+
+impl EdgeConditions {
+    /// Resolve and compile the condition of every edge in `def`
+    pub fn compile(def: &GraphDef) -> crate::error::Result<Self> {
+        let mut named: HashMap<&str, Arc<EdgeCondition>> = HashMap::new();
+        for (name, rule_def) in &def.rules {
+            let condition = EdgeCondition::from_rule_def(name, rule_def).map_err(|e| {
+                RustLogicGraphError::rule_evaluation_error(format!(
+                    "Invalid rule '{}': {}",
+                    name, e
+                ))
+            })?;
+            named.insert(name, Arc::new(condition));
+        }
+
+        let by_edge = def
+            .edges
+            .iter()
+            .map(|edge| {
+                let Some(rule) = edge.rule.as_deref() else {
+                    return Ok(None);
+                };
+                if let Some(condition) = named.get(rule) {
+                    return Ok(Some(condition.clone()));
+                }
+                // A bare name is a reference to a rule, not a variable lookup
+                if is_rule_name(rule) {
+                    return Err(RustLogicGraphError::graph_validation_error(format!(
+                        "Edge {} -> {} references rule '{}', which is not defined under `rules`; \
+                        define it there, or write the condition as an expression (e.g. `{} == true`)",
+                        edge.from, edge.to, rule, rule
+                    )));
+                }
+                let rule =
+                    Rule::compile(format!("{}->{}", edge.from, edge.to), rule).map_err(|e| {
+                        RustLogicGraphError::rule_evaluation_error(format!(
+                            "Invalid condition on edge {} -> {}: {}",
+                            edge.from, edge.to, e
+                        ))
+                    })?;
+                Ok(Some(Arc::new(EdgeCondition::Expression(rule))))
+            })
+            .collect::<crate::error::Result<_>>()?;
+
+        Ok(Self { by_edge })
+    }
+
+    /// Condition of the edge at `edge_index`, if it has one
+    pub fn get(&self, edge_index: usize) -> Option<&EdgeCondition> {
+        self.by_edge.get(edge_index).and_then(|c| c.as_deref())
+    }
+
+    /// Whether the edge at `edge_index` is taken; unconditional edges always are
+    pub fn is_satisfied(
+        &self,
+        edge_index: usize,
+        data: &HashMap<String, Value>,
+    ) -> Result<bool, RuleError> {
+        match self.get(edge_index) {
+            Some(condition) => condition.evaluate(data),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Whether an edge's `rule` is a plain name such as `r1` or `has_access`
+fn is_rule_name(rule: &str) -> bool {
+    let rule = rule.trim();
+    let mut chars = rule.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && rule != "true"
+        && rule != "false"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Edge, NodeConfig};
+    use serde_json::json;
+
+    fn def_with_edges(edges: Vec<Edge>) -> GraphDef {
+        let nodes = ["a", "b", "c"]
+            .iter()
+            .map(|id| (id.to_string(), NodeConfig::rule_node("true")))
+            .collect();
+        GraphDef::new(nodes, edges)
+    }
+
+    #[test]
+    fn test_inline_and_named_conditions() {
+        let def = def_with_edges(vec![
+            Edge::new("a", "b").with_rule("score > 600"),
+            Edge::new("a", "c").with_rule("approved"),
+            Edge::new("b", "c"),
+        ])
+        .with_rule("approved", RuleDef::Condition("score >= 700".to_string()));
+        let conditions = EdgeConditions::compile(&def).unwrap();
+
+        let data = HashMap::from([("score".to_string(), json!(650))]);
+        assert!(conditions.is_satisfied(0, &data).unwrap());
+        assert!(!conditions.is_satisfied(1, &data).unwrap());
+        assert!(conditions.is_satisfied(2, &data).unwrap());
+    }
+
+    #[test]
+    fn test_non_boolean_condition_is_an_error() {
+        let def = def_with_edges(vec![Edge::new("a", "b").with_rule("score + 1")]);
+        let conditions = EdgeConditions::compile(&def).unwrap();
+
+        let data = HashMap::from([("score".to_string(), json!(650))]);
+        assert!(conditions.is_satisfied(0, &data).is_err());
+    }
+
+    #[test]
+    fn test_undefined_rule_name_rejected() {
+        let def = def_with_edges(vec![Edge::new("a", "b").with_rule("r1")]);
+        let err = EdgeConditions::compile(&def).unwrap_err();
+
+        assert!(err.message.contains("references rule 'r1'"));
+        assert!(EdgeConditions::compile(&def_with_edges(vec![
+            Edge::new("a", "b").with_rule("true")
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_grl_condition() {
+        let def = def_with_edges(vec![Edge::new("a", "b").with_rule("big_order")]).with_rule(
+            "big_order",
+            RuleDef::grl(
+                r#"
+                rule "BigOrder" {
+                    when
+                        amount > 1000
+                    then
+                        passed = true;
+                }
+                "#,
+            ),
+        );
+        let conditions = EdgeConditions::compile(&def).unwrap();
+
+        let big = HashMap::from([("amount".to_string(), json!(5000))]);
+        let small = HashMap::from([("amount".to_string(), json!(10))]);
+        assert!(conditions.is_satisfied(0, &big).unwrap());
+        assert!(!conditions.is_satisfied(0, &small).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_grl_rejected() {
+        let def = def_with_edges(vec![]).with_rule("broken", RuleDef::grl("rule {"));
+        assert!(EdgeConditions::compile(&def).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Lifecycle events of graph executions.
+//!
+//! An [`Executor`](crate::core::Executor) reports what it does while running
+//! a graph as [`ExecutionEvent`]s, so audit logs, progress UIs and custom
+//! metrics can follow a run without changing the executor. Events reach:
+//!
+//! - [`ExecutionListener`]s, called synchronously as each event happens
+//!   (see [`Executor::add_listener`](crate::core::Executor::add_listener))
+//! - [`AsyncExecutionListener`]s, awaited as each event happens
+//!   (see [`Executor::add_async_listener`](crate::core::Executor::add_async_listener))
+//! - broadcast receivers, which get every event without holding up the run
+//!   (see [`Executor::subscribe`](crate::core::Executor::subscribe))
+//!
+//! Every scheduled node gets a `NodeScheduled`, a `NodeStarted` and a
+//! `NodeFinished` event, in that order; `CacheHit` and `FallbackApplied` come
+//! between its start and finish. Nodes of one layer run concurrently, so
+//! their events interleave.
+//!
+//! ```
+//! use rust_logic_graph::core::ExecutionListener;
+//! use rust_logic_graph::RuleResult;
+//! use std::time::Duration;
+//!
+//! struct Progress;
+//!
+//! impl ExecutionListener for Progress {
+//!     fn on_node_finished(&self, node_id: &str, result: &RuleResult, duration: Duration) {
+//!         println!("{} finished in {:?} (ok: {})", node_id, duration, result.is_ok());
+//!     }
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::core::ExecutionStatus;
+use crate::rule::{RuleError, RuleResult};
+
+/// Something that happened during a graph execution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecutionEvent {
+    /// The run started
+    GraphStarted {
+        graph: Option<String>,
+        node_count: usize,
+    },
+    /// The node's incoming edges were taken; it will run in the current layer
+    NodeScheduled { node_id: String },
+    /// The executor started working on the node
+    NodeStarted { node_id: String },
+    /// The node finished, successfully or not
+    NodeFinished {
+        node_id: String,
+        result: RuleResult,
+        duration: Duration,
+    },
+    /// The node will not run
+    NodeSkipped { node_id: String, reason: String },
+    /// The node's result was taken from the cache instead of running it
+    CacheHit { node_id: String },
+    /// The node failed and the fallback handler supplied its result
+    FallbackApplied { node_id: String, error: RuleError },
+    /// The run finished: with a status if it ran to completion, or with the
+    /// code and message of the error it failed with
+    GraphFinished {
+        graph: Option<String>,
+        status: Option<ExecutionStatus>,
+        error: Option<String>,
+        duration: Duration,
+    },
+}
+
+/// Receives the events of the executions of the executor it is added to.
+///
+/// Implement the callbacks of the events of interest, or [`on_event`] to
+/// handle every event in one place. Callbacks run on the executor's task while
+/// the graph runs, so they should return quickly; hand slow work off to an
+/// [`AsyncExecutionListener`] or a broadcast receiver.
+///
+/// [`on_event`]: ExecutionListener::on_event
+pub trait ExecutionListener: Send + Sync {
+    /// Called for every event; calls the matching callback below by default
+    fn on_event(&self, event: &ExecutionEvent) {
+        match event {
+            ExecutionEvent::GraphStarted { graph, node_count } => {
+                self.on_graph_started(graph.as_deref(), *node_count)
+            }
+            ExecutionEvent::NodeScheduled { node_id } => self.on_node_scheduled(node_id),
+            ExecutionEvent::NodeStarted { node_id } => self.on_node_started(node_id),
+            ExecutionEvent::NodeFinished {
+                node_id,
+                result,
+                duration,
+            } => self.on_node_finished(node_id, result, *duration),
+            ExecutionEvent::NodeSkipped { node_id, reason } => {
+                self.on_node_skipped(node_id, reason)
+            }
+            ExecutionEvent::CacheHit { node_id } => self.on_cache_hit(node_id),
+            ExecutionEvent::FallbackApplied { node_id, error } => {
+                self.on_fallback_applied(node_id, error)
+            }
+            ExecutionEvent::GraphFinished {
+                graph,
+                status,
+                error,
+                duration,
+            } => self.on_graph_finished(graph.as_deref(), *status, error.as_deref(), *duration),
+        }
+    }
+
+    fn on_graph_started(&self, _graph: Option<&str>, _node_count: usize) {}
+
+    fn on_node_scheduled(&self, _node_id: &str) {}
+
+    fn on_node_started(&self, _node_id: &str) {}
+
+    fn on_node_finished(&self, _node_id: &str, _result: &RuleResult, _duration: Duration) {}
+
+    fn on_node_skipped(&self, _node_id: &str, _reason: &str) {}
+
+    fn on_cache_hit(&self, _node_id: &str) {}
+
+    fn on_fallback_applied(&self, _node_id: &str, _error: &RuleError) {}
+
+    /// `status` is set when the run completed, `error` when it failed
+    fn on_graph_finished(
+        &self,
+        _graph: Option<&str>,
+        _status: Option<ExecutionStatus>,
+        _error: Option<&str>,
+        _duration: Duration,
+    ) {
+    }
+}
+
+/// Receives the events of the executions of the executor it is added to,
+/// asynchronously.
+///
+/// The executor awaits `on_event` before going on, so a listener writing an
+/// audit log has recorded every event by the time the run returns.
+#[async_trait]
+pub trait AsyncExecutionListener: Send + Sync {
+    async fn on_event(&self, event: ExecutionEvent);
+}
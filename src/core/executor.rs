@@ -1,77 +1,449 @@
-use anyhow::Result;
-use std::collections::{HashMap, HashSet, VecDeque};
+use futures::stream::{FuturesUnordered, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tracing::{debug, info, warn};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::field::Empty;
+use tracing::{debug, info, info_span, warn, Instrument};
 
 use crate::cache::{CacheKey, CacheManager};
-use crate::core::{Graph, GraphDef};
-use crate::node::{AINode, DBNode, Node, RuleNode as ConcreteRuleNode};
-use crate::rule::Rule;
+use crate::core::checkpoint::Checkpoint;
+use crate::core::events::{AsyncExecutionListener, ExecutionEvent, ExecutionListener};
+use crate::core::recording::{EdgeDecision, ExecutionRecording, NodeRecord, Replay};
+use crate::core::{
+    Context, ExecutionPlan, ExecutionResult, ExecutionStatus, Graph, GraphDef, NodeStatus,
+    TraceContext,
+};
+use crate::distributed::ContextStore;
+use crate::error::{ErrorContext, Result, RustLogicGraphError};
+use crate::node::{Node, NodeRegistry};
+use crate::rule::{RuleError, RuleResult, RuleSet, RuleSetManager, RuleTrace};
 
 /// Execution statistics for a single node
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeExecutionStats {
     pub node_id: String,
     pub duration: Duration,
     pub cache_hit: bool,
     pub success: bool,
+    /// Whether the node was cancelled for running past its timeout or the
+    /// execution deadline
+    pub timed_out: bool,
+    /// Whether the node was dropped because the execution was cancelled
+    pub cancelled: bool,
+    /// Error the node failed with
+    pub error: Option<RuleError>,
+    /// Traces of the GRL rules the node ran, when rule tracing is enabled
+    pub rule_traces: Vec<RuleTrace>,
 }
 
 /// Overall execution metrics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExecutionMetrics {
     pub total_duration: Duration,
     pub nodes_executed: usize,
     pub nodes_skipped: usize,
     pub nodes_failed: usize,
+    /// Failed nodes that were cancelled by a timeout
+    pub nodes_timed_out: usize,
+    /// Nodes dropped while running because the execution was cancelled
+    pub nodes_cancelled: usize,
+    /// Nodes not run because the checkpoint a run resumed from had them
+    /// completed
+    pub nodes_restored: usize,
     pub cache_hits: usize,
     pub node_stats: Vec<NodeExecutionStats>,
+    /// Version of every rule set used during the run, by rule set name
+    pub rule_set_versions: HashMap<String, u64>,
 }
 
-/// Executor for running graph nodes in topological order.
-///
-/// # Thread Safety
-///
-/// The Executor is **NOT thread-safe** for concurrent executions on the same instance.
-/// While `execute()` is async and takes `&self` (shared reference), the underlying
-/// implementation assumes single-threaded access patterns:
-///
-/// - `self.nodes` is a regular `HashMap` without synchronization
-/// - Multiple concurrent calls to `execute()` would have data races when accessing nodes
+impl ExecutionMetrics {
+    /// Rule traces recorded during the run, with the id of the node that
+    /// produced each one
+    pub fn rule_traces(&self) -> impl Iterator<Item = (&str, &RuleTrace)> {
+        self.node_stats.iter().flat_map(|stats| {
+            stats
+                .rule_traces
+                .iter()
+                .map(move |trace| (stats.node_id.as_str(), trace))
+        })
+    }
+
+    /// Nodes that failed during the run, with the error each one failed with
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &RuleError)> {
+        self.node_stats
+            .iter()
+            .filter(|stats| !stats.cancelled)
+            .filter_map(|stats| {
+                stats
+                    .error
+                    .as_ref()
+                    .map(|error| (stats.node_id.as_str(), error))
+            })
+    }
+
+    /// Fold the metrics of a nested execution (e.g. a subgraph) into these,
+    /// qualifying the nested node ids with `prefix`
+    pub fn merge_nested(&mut self, prefix: &str, nested: &ExecutionMetrics) {
+        self.nodes_executed += nested.nodes_executed;
+        self.nodes_skipped += nested.nodes_skipped;
+        self.nodes_failed += nested.nodes_failed;
+        self.nodes_timed_out += nested.nodes_timed_out;
+        self.nodes_cancelled += nested.nodes_cancelled;
+        self.nodes_restored += nested.nodes_restored;
+        self.cache_hits += nested.cache_hits;
+        self.node_stats
+            .extend(nested.node_stats.iter().map(|stats| NodeExecutionStats {
+                node_id: format!("{}/{}", prefix, stats.node_id),
+                ..stats.clone()
+            }));
+        self.rule_set_versions.extend(
+            nested
+                .rule_set_versions
+                .iter()
+                .map(|(name, version)| (name.clone(), *version)),
+        );
+    }
+}
+
+/// Runtime services available to a node while an [`Executor`] runs it.
 ///
-/// ## Safe Usage Patterns
+/// Passed to [`Node::run_scoped`]; composite nodes use it to build nested
+/// executors with the same node registry and cache, and to report the metrics
+/// of those nested runs back to the parent execution.
+pub struct ExecutionScope<'a> {
+    executor: &'a Executor,
+    nested_metrics: Mutex<Vec<(String, ExecutionMetrics)>>,
+    run: Arc<RunState>,
+    rule_traces: Mutex<Vec<RuleTrace>>,
+    records: Mutex<Vec<NodeRecord>>,
+}
+
+/// State shared by every node of one run
+#[derive(Default)]
+struct RunState {
+    /// Rule set versions resolved so far
+    rule_sets: Mutex<HashMap<String, Arc<RuleSet>>>,
+    /// Number of invocations of each node answered from the replay so far
+    replayed: Mutex<HashMap<String, usize>>,
+    /// `timeout_ms` of the nodes that have one, from the plan
+    timeouts: Arc<HashMap<String, Duration>>,
+    /// When the whole run has to be finished
+    deadline: Option<Instant>,
+    /// Whether the deadline cancelled a node or kept one from starting
+    deadline_exceeded: AtomicBool,
+    /// Token the caller cancels the run with, if it passed one
+    cancel: Option<CancellationToken>,
+    /// Token stopping the nodes of the run: a child of `cancel`, also
+    /// cancelled when a required node fails. `None` when nothing can stop
+    /// the run early.
+    stop: Option<CancellationToken>,
+}
+
+impl RunState {
+    fn new(
+        plan: &ExecutionPlan,
+        deadline: Option<Instant>,
+        cancel: Option<CancellationToken>,
+    ) -> Self {
+        let stop = match &cancel {
+            Some(cancel) => Some(cancel.child_token()),
+            None => plan.has_required_nodes().then(CancellationToken::new),
+        };
+        Self {
+            timeouts: plan.timeouts().clone(),
+            deadline,
+            cancel,
+            stop,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the caller cancelled the run
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Whether the run was cancelled or stopped by a failed required node
+    fn is_stopped(&self) -> bool {
+        self.stop
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// How long `node_id` may run, and whether that limit is the deadline
+    /// rather than the node's own timeout
+    fn time_limit(&self, node_id: &str) -> Option<(Duration, bool)> {
+        let timeout = self.timeouts.get(node_id).copied();
+        let remaining = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (timeout, remaining) {
+            (Some(timeout), Some(remaining)) if remaining < timeout => Some((remaining, true)),
+            (Some(timeout), _) => Some((timeout, false)),
+            (None, Some(remaining)) => Some((remaining, true)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl<'a> ExecutionScope<'a> {
+    pub fn new(executor: &'a Executor) -> Self {
+        Self::with_run(executor, Arc::default())
+    }
+
+    fn with_run(executor: &'a Executor, run: Arc<RunState>) -> Self {
+        Self {
+            executor,
+            nested_metrics: Mutex::new(Vec::new()),
+            run,
+            rule_traces: Mutex::new(Vec::new()),
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Node registry of the running executor
+    pub fn registry(&self) -> &Arc<NodeRegistry> {
+        &self.executor.registry
+    }
+
+    /// Cache of the running executor (if enabled)
+    pub fn cache(&self) -> Option<&CacheManager> {
+        self.executor.cache.as_ref()
+    }
+
+    /// Context store of the running executor (if configured)
+    pub fn context_store(&self) -> Option<&Arc<dyn ContextStore>> {
+        self.executor.context_store.as_ref()
+    }
+
+    /// Rule set manager of the running executor (if configured)
+    pub fn rule_set_manager(&self) -> Option<&Arc<RuleSetManager>> {
+        self.executor.rule_sets.as_ref()
+    }
+
+    /// Version of the rule set `name` to use in this run.
+    ///
+    /// The first call in a run resolves the active version; later calls in
+    /// the same run return that same version, even if a newer one has been
+    /// loaded meanwhile.
+    pub fn rule_set(&self, name: &str) -> std::result::Result<Arc<RuleSet>, RuleError> {
+        self.executor.pinned_rule_set(&self.run, name)
+    }
+
+    /// Whether nodes running GRL rules should trace them
+    pub fn rule_tracing(&self) -> bool {
+        self.executor.rule_tracing
+    }
+
+    /// Attach a rule trace to the running node's execution stats
+    pub fn record_rule_trace(&self, trace: RuleTrace) {
+        self.rule_traces.lock().push(trace);
+    }
+
+    /// Token cancelled when the running execution is cancelled (see
+    /// [`Executor::execute_with_cancellation`]) or stopped by a failed
+    /// required node; `None` when nothing can stop it early
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.run.stop.as_ref()
+    }
+
+    /// Whether the running execution has been cancelled or stopped
+    pub fn is_cancelled(&self) -> bool {
+        self.run.is_stopped()
+    }
+
+    /// Wait until the running execution is cancelled or stopped; never
+    /// completes for executions that cannot be stopped early
+    pub async fn cancelled(&self) {
+        match &self.run.stop {
+            Some(cancel) => cancel.cancelled().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Run another node registered with the executor against `ctx`.
+    ///
+    /// Used by wrapper nodes (try/catch, retry, circuit breaker, loops) to
+    /// invoke the nodes they reference. The node runs with this same scope,
+    /// so wrappers can be nested. If the execution is cancelled, the node is
+    /// dropped and fails with [`RuleError::Cancelled`].
+    pub async fn run_node(&self, node_id: &str, ctx: &mut Context) -> RuleResult {
+        self.invoke(node_id, ctx, true).await
+    }
+
+    /// Run a cleanup node (e.g. a finally block) registered with the
+    /// executor against `ctx`.
+    ///
+    /// Like [`ExecutionScope::run_node`], except that the node runs to
+    /// completion even when the execution has been cancelled. Its time
+    /// limits still apply.
+    pub async fn run_cleanup_node(&self, node_id: &str, ctx: &mut Context) -> RuleResult {
+        self.invoke(node_id, ctx, false).await
+    }
+
+    async fn invoke(&self, node_id: &str, ctx: &mut Context, cancellable: bool) -> RuleResult {
+        let node = self.executor.nodes.get(node_id).ok_or_else(|| {
+            RuleError::Configuration(format!(
+                "Node '{}' is not registered with the executor",
+                node_id
+            ))
+        })?;
+
+        let before = self.executor.recording.then(|| ctx.clone());
+        let start = Instant::now();
+        let result = match self.executor.replay_node(&self.run, node.as_ref(), ctx) {
+            Some(result) => result,
+            None => {
+                self.executor
+                    .run_limited(&self.run, node.as_ref(), ctx, self, cancellable)
+                    .await
+            }
+        };
+
+        if let Some(before) = before {
+            let mut record = self
+                .executor
+                .node_record(node.as_ref(), &before, ctx, &result);
+            record.duration = start.elapsed();
+            self.records.lock().push(record);
+        }
+        result
+    }
+
+    /// Cache namespace for a nested executor started by `node_id`
+    pub fn child_namespace(&self, node_id: &str) -> String {
+        format!("{}{}/", self.executor.cache_namespace, node_id)
+    }
+
+    /// Build an executor for the graph `def` nested in node `node_id` (e.g.
+    /// a subgraph), configured like the running executor.
+    ///
+    /// The child creates its nodes with the same registry and shares the
+    /// cache (namespaced under `node_id`), context store, rule sets, fallback
+    /// handler, listeners, subscribers and limits. Its execution timeout is
+    /// what is left of the running execution's deadline.
+    pub fn child_executor(&self, node_id: &str, def: &GraphDef) -> Result<Executor> {
+        let parent = self.executor;
+        let mut child = Executor::from_graph_def_with_registry(def, parent.registry.clone())?;
+        child.cache = parent.cache.clone();
+        child.cache_namespace = self.child_namespace(node_id);
+        child.max_concurrency = parent.max_concurrency;
+        child.fallback_handler = parent.fallback_handler;
+        child.context_store = parent.context_store.clone();
+        child.rule_sets = parent.rule_sets.clone();
+        child.rule_tracing = parent.rule_tracing;
+        child.execution_timeout = self
+            .run
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        child.listeners = parent.listeners.clone();
+        child.events = parent.events.clone();
+        Ok(child)
+    }
+
+    /// Report the metrics of a nested execution started by `node_id`
+    pub fn record_nested_metrics(&self, node_id: &str, metrics: &ExecutionMetrics) {
+        self.nested_metrics
+            .lock()
+            .push((node_id.to_string(), metrics.clone()));
+    }
+
+    fn take_nested_metrics(&self) -> Vec<(String, ExecutionMetrics)> {
+        std::mem::take(&mut *self.nested_metrics.lock())
+    }
+
+    fn take_rule_traces(&self) -> Vec<RuleTrace> {
+        std::mem::take(&mut *self.rule_traces.lock())
+    }
+
+    fn take_records(&self) -> Vec<NodeRecord> {
+        std::mem::take(&mut *self.records.lock())
+    }
+}
+
+/// Default number of nodes an [`Executor`] runs at the same time
+pub const DEFAULT_MAX_CONCURRENCY: usize = 10;
+
+/// Executor for running graph nodes in topological order.
 ///
-/// 1. **Single execution at a time**: Only call `execute()` once at a time per executor instance
-/// 2. **Clone for parallelism**: Create separate executor instances for parallel graph executions
-/// 3. **Sequential async**: Use `.await` to ensure executions don't overlap
+/// Each node starts as soon as every node it has an incoming edge from has
+/// finished, up to [`Executor::set_max_concurrency`] nodes at a time. A node
+/// works on its own copy of the context, taken when it starts, and its changes
+/// are merged back when it finishes. When nodes not connected by edges write
+/// the same entry, the node with the larger id wins, so results do not
+/// depend on timing.
 ///
-/// ## Future Work
+/// # Thread Safety
 ///
-/// For true concurrent execution support, wrap `nodes` in `Arc<RwLock<HashMap>>` or similar.
+/// `execute()` takes `&self` and keeps no per-run state on the executor, so
+/// one executor can be built once, wrapped in an `Arc` and used to run many
+/// graph instances concurrently (e.g. one per request in a web service).
+/// [`Executor::metrics`] and [`Executor::last_recording`] report the most
+/// recently completed run.
 pub struct Executor {
     nodes: HashMap<String, Box<dyn Node>>,
     cache: Option<CacheManager>,
-    metrics: ExecutionMetrics,
+    metrics: Mutex<ExecutionMetrics>,
+    max_concurrency: usize,
     fallback_handler: Option<crate::fault_tolerance::degradation::FallbackHandler>,
+    registry: Arc<NodeRegistry>,
+    cache_namespace: String,
+    context_store: Option<Arc<dyn ContextStore>>,
+    rule_sets: Option<Arc<RuleSetManager>>,
+    rule_tracing: bool,
+    execution_timeout: Option<Duration>,
+    recording: bool,
+    last_recording: Mutex<Option<ExecutionRecording>>,
+    replay: Option<Arc<Replay>>,
+    listeners: Vec<Listener>,
+    /// Shared with nested executors, so subscribers see their events too
+    events: Arc<Mutex<Option<broadcast::Sender<ExecutionEvent>>>>,
+}
+
+/// A listener added to an [`Executor`]
+#[derive(Clone)]
+enum Listener {
+    Sync(Arc<dyn ExecutionListener>),
+    Async(Arc<dyn AsyncExecutionListener>),
 }
 
+/// Events a broadcast receiver can fall behind by before it misses some
+const EVENT_CAPACITY: usize = 1024;
+
 impl Executor {
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
             cache: None,
-            metrics: ExecutionMetrics::default(),
+            metrics: Mutex::new(ExecutionMetrics::default()),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
             fallback_handler: None,
+            registry: Arc::new(NodeRegistry::default()),
+            cache_namespace: String::new(),
+            context_store: None,
+            rule_sets: None,
+            rule_tracing: false,
+            execution_timeout: None,
+            recording: false,
+            last_recording: Mutex::new(None),
+            replay: None,
+            listeners: Vec::new(),
+            events: Arc::default(),
         }
     }
 
     /// Create a new executor with caching enabled
     pub fn with_cache(cache: CacheManager) -> Self {
         Self {
-            nodes: HashMap::new(),
             cache: Some(cache),
-            metrics: ExecutionMetrics::default(),
-            fallback_handler: None,
+            ..Self::new()
         }
     }
 
@@ -80,6 +452,113 @@ impl Executor {
         self.cache = Some(cache);
     }
 
+    /// Prefix node ids with `namespace` in cache keys.
+    ///
+    /// Nested executors (e.g. subgraphs) share the parent's cache; the
+    /// namespace keeps their nodes from colliding with parent nodes that
+    /// happen to use the same id.
+    pub fn set_cache_namespace(&mut self, namespace: impl Into<String>) {
+        self.cache_namespace = namespace.into();
+    }
+
+    /// Set the context store used by nodes that persist state across runs
+    /// (e.g. circuit breaker state) and by [`Executor::execute_checkpointed`]
+    pub fn set_context_store(&mut self, store: Arc<dyn ContextStore>) {
+        self.context_store = Some(store);
+    }
+
+    /// Set the manager that resolves the rule sets referenced by RuleNodes
+    /// (`rule_set` in their configuration)
+    pub fn set_rule_sets(&mut self, rule_sets: Arc<RuleSetManager>) {
+        self.rule_sets = Some(rule_sets);
+    }
+
+    /// Record a [`RuleTrace`] for every GRL evaluation done by RuleNodes.
+    ///
+    /// Traces are attached to the node's result (as `_trace`) and to its
+    /// [`NodeExecutionStats`]. Off by default, since tracing snapshots the
+    /// facts after every rule that fires.
+    pub fn set_rule_tracing(&mut self, enabled: bool) {
+        self.rule_tracing = enabled;
+    }
+
+    /// Whether rule tracing is enabled
+    pub fn rule_tracing(&self) -> bool {
+        self.rule_tracing
+    }
+
+    /// Record an [`ExecutionRecording`] of every run, available from
+    /// [`Executor::last_recording`].
+    ///
+    /// Off by default, since every node invocation snapshots the context.
+    pub fn set_recording(&mut self, enabled: bool) {
+        self.recording = enabled;
+    }
+
+    /// Recording of the most recently completed run, if recording is enabled
+    pub fn last_recording(&self) -> Option<ExecutionRecording> {
+        self.last_recording.lock().clone()
+    }
+
+    /// Answer external nodes from a recorded run instead of running them.
+    ///
+    /// The n-th invocation of a replayed node in a run gets the context
+    /// changes and outcome of its n-th recorded invocation; see [`Replay`]
+    /// for which nodes are replayed.
+    pub fn set_replay(&mut self, replay: impl Into<Replay>) {
+        self.replay = Some(Arc::new(replay.into()));
+    }
+
+    /// Run every node for real again
+    pub fn clear_replay(&mut self) {
+        self.replay = None;
+    }
+
+    /// Add a listener called as each event of every run happens
+    pub fn add_listener(&mut self, listener: Arc<dyn ExecutionListener>) {
+        self.listeners.push(Listener::Sync(listener));
+    }
+
+    /// Add a listener awaited as each event of every run happens
+    pub fn add_async_listener(&mut self, listener: Arc<dyn AsyncExecutionListener>) {
+        self.listeners.push(Listener::Async(listener));
+    }
+
+    /// Receive the events of every run started from now on.
+    ///
+    /// Runs do not wait for receivers; one that falls more than 1024 events
+    /// behind misses the oldest ones (`RecvError::Lagged`).
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.events
+            .lock()
+            .get_or_insert_with(|| broadcast::channel(EVENT_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Report an event to the listeners and subscribers; `event` is only
+    /// built if there are any
+    async fn emit(&self, event: impl FnOnce() -> ExecutionEvent) {
+        let subscribers = self
+            .events
+            .lock()
+            .clone()
+            .filter(|events| events.receiver_count() > 0);
+        if self.listeners.is_empty() && subscribers.is_none() {
+            return;
+        }
+
+        let event = event();
+        for listener in &self.listeners {
+            match listener {
+                Listener::Sync(listener) => listener.on_event(&event),
+                Listener::Async(listener) => listener.on_event(event.clone()).await,
+            }
+        }
+        if let Some(subscribers) = subscribers {
+            let _ = subscribers.send(event);
+        }
+    }
+
     /// Set a global fallback handler used when node execution fails
     pub fn set_fallback_handler(
         &mut self,
@@ -93,432 +572,1370 @@ impl Executor {
         self.cache.as_ref()
     }
 
+    /// Limit how many nodes run at the same time (at least 1)
+    pub fn set_max_concurrency(&mut self, max_concurrency: usize) {
+        self.max_concurrency = max_concurrency.max(1);
+    }
+
+    /// Maximum number of nodes run at the same time
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Fail every run that takes longer than `timeout`.
+    ///
+    /// Nodes still running at the deadline are cancelled like nodes that
+    /// exceed their own `timeout_ms`; nodes not started yet are not run, and
+    /// the run returns a timeout error (`E005`).
+    pub fn set_execution_timeout(&mut self, timeout: Duration) {
+        self.execution_timeout = Some(timeout);
+    }
+
+    /// Time limit on whole runs, if any
+    pub fn execution_timeout(&self) -> Option<Duration> {
+        self.execution_timeout
+    }
+
     /// Get execution metrics from last run
-    pub fn metrics(&self) -> &ExecutionMetrics {
-        &self.metrics
+    pub fn metrics(&self) -> ExecutionMetrics {
+        self.metrics.lock().clone()
     }
 
     /// Reset execution metrics
-    pub fn reset_metrics(&mut self) {
-        self.metrics = ExecutionMetrics::default();
+    pub fn reset_metrics(&self) {
+        *self.metrics.lock() = ExecutionMetrics::default();
     }
 
-    /// Build executor from graph definition
+    /// Build executor from graph definition using the built-in node factories
     pub fn from_graph_def(def: &GraphDef) -> Result<Self> {
+        Self::from_graph_def_with_registry(def, Arc::new(NodeRegistry::default()))
+    }
+
+    /// Build executor from graph definition, creating every node through `registry`
+    ///
+    /// Register factories for custom node types on the registry to let a
+    /// YAML/JSON `GraphDef` reference them by name.
+    pub fn from_graph_def_with_registry(
+        def: &GraphDef,
+        registry: Arc<NodeRegistry>,
+    ) -> Result<Self> {
         let mut executor = Self::new();
 
-        // Create concrete node instances based on NodeConfig
         for (node_id, config) in &def.nodes {
-            let node: Box<dyn Node> = match config.node_type {
-                crate::node::NodeType::RuleNode => {
-                    let condition = config.condition.as_deref().unwrap_or("true");
-                    Box::new(ConcreteRuleNode::new(node_id, condition))
-                }
-                crate::node::NodeType::DBNode => {
-                    let query = config
-                        .query
-                        .clone()
-                        .unwrap_or_else(|| format!("SELECT * FROM {}", node_id));
-
-                    // Create DBNode with params if specified
-                    if let Some(params) = config.params.clone() {
-                        Box::new(DBNode::with_params(node_id, query, params))
-                    } else {
-                        Box::new(DBNode::new(node_id, query))
-                    }
-                }
-                crate::node::NodeType::AINode => {
-                    let prompt = config
-                        .prompt
-                        .clone()
-                        .unwrap_or_else(|| format!("Process data for {}", node_id));
-                    Box::new(AINode::new(node_id, prompt))
-                }
-                crate::node::NodeType::GrpcNode => {
-                    // Parse query field as "service_url#method"
-                    let query = config
-                        .query
-                        .clone()
-                        .unwrap_or_else(|| format!("http://localhost:50051#{}_method", node_id));
-                    let parts: Vec<&str> = query.split('#').collect();
-                    let service_url = parts
-                        .get(0)
-                        .unwrap_or(&"http://localhost:50051")
-                        .to_string();
-                    let method = parts.get(1).unwrap_or(&"UnknownMethod").to_string();
-                    Box::new(crate::node::GrpcNode::new(node_id, service_url, method))
-                }
-                // Advanced nodes - not yet fully supported in from_graph_def
-                // Use Node trait directly if needed
-                crate::node::NodeType::SubgraphNode
-                | crate::node::NodeType::ConditionalNode
-                | crate::node::NodeType::LoopNode
-                | crate::node::NodeType::TryCatchNode
-                | crate::node::NodeType::RetryNode
-                | crate::node::NodeType::CircuitBreakerNode => {
-                    // Placeholder: create a simple rule node
-                    // TODO: Implement proper constructors for advanced nodes
-                    Box::new(ConcreteRuleNode::new(node_id, "true"))
-                }
-            };
-
+            let node = registry.create(node_id, config)?;
             executor.register_node(node);
         }
 
+        executor.registry = registry;
         Ok(executor)
     }
 
+    /// Get the node registry used to build this executor
+    pub fn registry(&self) -> &Arc<NodeRegistry> {
+        &self.registry
+    }
+
     /// Register a node with the executor
     pub fn register_node(&mut self, node: Box<dyn Node>) {
         let id = node.id().to_string();
         self.nodes.insert(id, node);
     }
 
-    /// Detect cycles in the graph using DFS
-    fn detect_cycles(&self, graph: &Graph) -> Result<()> {
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-
-        // Build adjacency list for cycle detection
-        let mut adj_list: HashMap<String, Vec<String>> = HashMap::new();
-        for edge in &graph.def.edges {
-            adj_list
-                .entry(edge.from.clone())
-                .or_insert_with(Vec::new)
-                .push(edge.to.clone());
-        }
-
-        // DFS to detect cycles
-        fn dfs_cycle_check(
-            node: &str,
-            adj_list: &HashMap<String, Vec<String>>,
-            visited: &mut HashSet<String>,
-            rec_stack: &mut HashSet<String>,
-            path: &mut Vec<String>,
-        ) -> Option<Vec<String>> {
-            visited.insert(node.to_string());
-            rec_stack.insert(node.to_string());
-            path.push(node.to_string());
-
-            if let Some(neighbors) = adj_list.get(node) {
-                for neighbor in neighbors {
-                    if !visited.contains(neighbor) {
-                        if let Some(cycle) =
-                            dfs_cycle_check(neighbor, adj_list, visited, rec_stack, path)
-                        {
-                            return Some(cycle);
-                        }
-                    } else if rec_stack.contains(neighbor) {
-                        // Found a cycle - return the cycle path
-                        let cycle_start = path.iter().position(|n| n == neighbor).unwrap();
-                        return Some(path[cycle_start..].to_vec());
-                    }
+    /// Collect the ids of nodes invoked by wrapper nodes, checking that each
+    /// one is registered
+    fn wrapped_node_ids(&self) -> Result<HashSet<String>> {
+        let mut wrapped = HashSet::new();
+
+        for node in self.nodes.values() {
+            for target in node.wrapped_nodes() {
+                if !self.nodes.contains_key(&target) {
+                    return Err(RustLogicGraphError::graph_validation_error(format!(
+                        "Node '{}' wraps unknown node '{}'",
+                        node.id(),
+                        target
+                    ))
+                    .with_node(node.id()));
                 }
+                wrapped.insert(target);
             }
+        }
 
-            path.pop();
-            rec_stack.remove(node);
-            None
-        }
-
-        // Check all nodes
-        for node_id in graph.def.nodes.keys() {
-            if !visited.contains(node_id) {
-                let mut path = Vec::new();
-                if let Some(cycle) =
-                    dfs_cycle_check(node_id, &adj_list, &mut visited, &mut rec_stack, &mut path)
-                {
-                    return Err(anyhow::anyhow!(
-                        "Cycle detected in graph: {} -> {}",
-                        cycle.join(" -> "),
-                        cycle.first().unwrap()
-                    ));
-                }
+        Ok(wrapped)
+    }
+
+    /// Compile `def` into an [`ExecutionPlan`] for the nodes registered here.
+    ///
+    /// Validation, cycle detection and rule parsing all happen here, once;
+    /// the plan can then be run any number of times with
+    /// [`Executor::execute_plan`].
+    pub fn compile(&self, def: &GraphDef) -> Result<ExecutionPlan> {
+        // Nodes wrapped by another node only run when their wrapper invokes them
+        let wrapped = self
+            .wrapped_node_ids()
+            .map_err(|error| def.named_error(error))?;
+        ExecutionPlan::new(def, &wrapped)
+    }
+
+    /// Execute the graph in topological order, running independent nodes
+    /// concurrently.
+    ///
+    /// Returns an [`ExecutionResult`] with the graph's declared outputs and
+    /// the outcome of every node; the full context is left in
+    /// `graph.context`.
+    ///
+    /// This compiles the graph on every call; use [`Executor::compile`] and
+    /// [`Executor::execute_plan`] to run the same graph repeatedly.
+    pub async fn execute(&self, graph: &mut Graph) -> Result<ExecutionResult> {
+        let plan = self.compile(&graph.def)?;
+        self.execute_plan(&plan, &mut graph.context).await
+    }
+
+    /// Execute a compiled plan against `context`
+    pub async fn execute_plan(
+        &self,
+        plan: &ExecutionPlan,
+        context: &mut Context,
+    ) -> Result<ExecutionResult> {
+        self.run_plan(plan, context, None, None).await
+    }
+
+    /// Execute the graph until it finishes or `cancel` is cancelled.
+    ///
+    /// On cancellation no further nodes are started, and running nodes are
+    /// dropped and their changes to the context discarded. Wrapper nodes
+    /// still run their finally blocks. The run then returns a cancelled
+    /// error (`E013`, see [`RustLogicGraphError::is_cancelled`]) carrying
+    /// the metrics of the nodes that did finish (see
+    /// [`RustLogicGraphError::metrics`]).
+    pub async fn execute_with_cancellation(
+        &self,
+        graph: &mut Graph,
+        cancel: CancellationToken,
+    ) -> Result<ExecutionResult> {
+        let plan = self.compile(&graph.def)?;
+        self.execute_plan_with_cancellation(&plan, &mut graph.context, cancel)
+            .await
+    }
+
+    /// Execute a compiled plan until it finishes or `cancel` is cancelled;
+    /// see [`Executor::execute_with_cancellation`]
+    pub async fn execute_plan_with_cancellation(
+        &self,
+        plan: &ExecutionPlan,
+        context: &mut Context,
+        cancel: CancellationToken,
+    ) -> Result<ExecutionResult> {
+        self.run_plan(plan, context, None, Some(cancel)).await
+    }
+
+    /// Execute the graph, saving a [`Checkpoint`] to the context store every
+    /// time a node finishes.
+    ///
+    /// If the store already holds a checkpoint for `execution_id` (e.g. the
+    /// process running it crashed), the graph resumes from it: the
+    /// checkpointed context replaces `graph.context` and nodes that already
+    /// ran or were skipped are not run again. Nodes that were still running
+    /// when the checkpoint was taken run again.
+    pub async fn execute_checkpointed(
+        &self,
+        graph: &mut Graph,
+        execution_id: &str,
+    ) -> Result<ExecutionResult> {
+        let plan = self.compile(&graph.def)?;
+        self.execute_plan_checkpointed(&plan, &mut graph.context, execution_id)
+            .await
+    }
+
+    /// Execute a compiled plan with checkpoints; see
+    /// [`Executor::execute_checkpointed`]
+    pub async fn execute_plan_checkpointed(
+        &self,
+        plan: &ExecutionPlan,
+        context: &mut Context,
+        execution_id: &str,
+    ) -> Result<ExecutionResult> {
+        let store = self.context_store.clone().ok_or_else(|| {
+            plan.def().named_error(RustLogicGraphError::configuration_error(format!(
+                "Checkpointing execution '{}' requires a context store (see Executor::set_context_store)",
+                execution_id
+            )))
+        })?;
+
+        let checkpoint = match self.checkpoint(execution_id).await? {
+            Some(checkpoint) => {
+                info!(
+                    "Resuming execution '{}' from checkpoint: {} nodes completed",
+                    execution_id,
+                    checkpoint.completed.len()
+                );
+                context.data = checkpoint.context.clone();
+                checkpoint
             }
-        }
+            None => Checkpoint::new(execution_id, context.data.clone()),
+        };
+
+        let mut checkpointer = Checkpointer { store, checkpoint };
+        checkpointer.save(context, Vec::new()).await;
+        let result = self
+            .run_plan(plan, context, Some(&mut checkpointer), None)
+            .await?;
+
+        checkpointer.checkpoint.finished = true;
+        checkpointer.save(context, Vec::new()).await;
+        Ok(result)
+    }
 
-        Ok(())
+    /// Latest checkpoint of `execution_id` in the context store
+    pub async fn checkpoint(&self, execution_id: &str) -> Result<Option<Checkpoint>> {
+        let Some(store) = &self.context_store else {
+            return Ok(None);
+        };
+        Checkpoint::load(store.as_ref(), execution_id).await
     }
 
-    /// Execute the graph in topological order
-    pub async fn execute(&mut self, graph: &mut Graph) -> Result<()> {
-        eprintln!("\n⚡⚡⚡ CORE EXECUTOR.EXECUTE called ⚡⚡⚡");
-        eprintln!(
-            "Graph has {} nodes, {} edges",
-            graph.def.nodes.len(),
-            graph.def.edges.len()
+    /// Run `plan` in a `graph_execution` span, reporting its start and
+    /// finish to listeners
+    async fn run_plan(
+        &self,
+        plan: &ExecutionPlan,
+        context: &mut Context,
+        checkpointer: Option<&mut Checkpointer>,
+        cancel: Option<CancellationToken>,
+    ) -> Result<ExecutionResult> {
+        let graph = plan.def().name.clone();
+        let parent = TraceContext::current();
+        let trace = parent
+            .as_ref()
+            .map_or_else(TraceContext::new_root, TraceContext::child);
+        let span = info_span!(
+            "graph_execution",
+            graph.name = Empty,
+            graph.status = Empty,
+            trace_id = trace.trace_id(),
+            span_id = trace.span_id(),
+            parent_span_id = Empty,
+            otel.status_code = Empty,
         );
+        if let Some(name) = &graph {
+            span.record("graph.name", name.as_str());
+        }
+        if let Some(parent) = &parent {
+            span.record("parent_span_id", parent.span_id());
+        }
+
+        self.emit(|| ExecutionEvent::GraphStarted {
+            graph: graph.clone(),
+            node_count: plan.topological_order().count(),
+        })
+        .await;
+
+        let start = Instant::now();
+        let mut result = trace
+            .clone()
+            .scope(self.run_nodes(plan, context, checkpointer, cancel, &trace))
+            .instrument(span.clone())
+            .await;
+        match &mut result {
+            Ok(result) => {
+                span.record("graph.status", result.status.as_str());
+                span.record("otel.status_code", "OK");
+            }
+            Err(error) => {
+                let trace_id = trace.trace_id().to_string();
+                error
+                    .context
+                    .metadata
+                    .push(("trace_id".to_string(), trace_id));
+                span.record("graph.status", "failed");
+                span.record("otel.status_code", "ERROR");
+            }
+        }
+
+        self.emit(|| ExecutionEvent::GraphFinished {
+            graph,
+            status: result.as_ref().ok().map(|result| result.status),
+            error: result
+                .as_ref()
+                .err()
+                .map(|e| format!("[{}] {}", e.code, e.message)),
+            duration: start.elapsed(),
+        })
+        .await;
+        result
+    }
+
+    /// Run the nodes of `plan` from a ready queue.
+    ///
+    /// A node becomes ready once every node it has an incoming edge from has
+    /// finished or been skipped, and starts as soon as fewer than
+    /// `max_concurrency` nodes are running. It works on a copy of the context
+    /// taken when it starts; its changes are merged back when it finishes.
+    async fn run_nodes(
+        &self,
+        plan: &ExecutionPlan,
+        context: &mut Context,
+        mut checkpointer: Option<&mut Checkpointer>,
+        cancel: Option<CancellationToken>,
+        trace: &TraceContext,
+    ) -> Result<ExecutionResult> {
         info!("Executor: Starting graph execution");
         let execution_start = Instant::now();
+        let mut metrics = ExecutionMetrics::default();
+        let mut skipped = vec![false; plan.node_count()];
+        let mut statuses = BTreeMap::new();
+        let mut execution_order = Vec::new();
+        // First failure of a required node, which stops the run
+        let mut aborted: Option<(String, RuleError)> = None;
+        let deadline = self
+            .execution_timeout
+            .map(|timeout| execution_start + timeout);
+        let run = Arc::new(RunState::new(plan, deadline, cancel));
+        let mut recording = self.recording.then(|| ExecutionRecording {
+            started_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            initial_context: context.data.clone(),
+            ..Default::default()
+        });
+
+        // Incoming edges of each node whose source has not finished yet
+        let mut waiting: Vec<usize> = (0..plan.node_count())
+            .map(|node| plan.incoming(node).len())
+            .collect();
+        // Ready nodes start in node id order
+        let mut ready: BTreeSet<usize> = plan
+            .layer_indices()
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|&node| waiting[node] == 0)
+            .collect();
+        let mut running = FuturesUnordered::new();
+        let mut in_flight = BTreeSet::new();
+        // Context the nodes started since the last merge work from
+        let mut snapshot: Option<Arc<Context>> = None;
+        // Node that last wrote each context entry
+        let mut writers: HashMap<String, usize> = HashMap::new();
+        let mut stopped = false;
+        // Nodes the checkpoint this run resumes from had skipped or completed
+        let mut restored_skipped = vec![false; plan.node_count()];
+        let mut restored_completed = vec![false; plan.node_count()];
+        if let Some(checkpoint) = checkpointer.as_deref().map(|c| &c.checkpoint) {
+            let skipped = checkpoint.skipped_nodes();
+            let completed = checkpoint.completed_nodes();
+            for node in 0..plan.node_count() {
+                restored_skipped[node] = skipped.contains(plan.node_id(node));
+                restored_completed[node] = completed.contains(plan.node_id(node));
+            }
+        }
 
-        // Reset metrics
-        self.metrics = ExecutionMetrics::default();
+        loop {
+            while !stopped && running.len() < self.max_concurrency {
+                let Some(&node) = ready.first() else {
+                    break;
+                };
+                if run.is_cancelled() {
+                    info!("Executor: Execution cancelled, not starting further nodes");
+                    stopped = true;
+                    break;
+                }
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    run.deadline_exceeded.store(true, Ordering::Relaxed);
+                    stopped = true;
+                    break;
+                }
+                ready.remove(&node);
+                let node_id = plan.node_id(node);
+
+                if restored_skipped[node] {
+                    skipped[node] = true;
+                    metrics.nodes_skipped += 1;
+                    statuses.insert(node_id.to_string(), not_taken());
+                    self.emit(|| ExecutionEvent::NodeSkipped {
+                        node_id: node_id.to_string(),
+                        reason: NOT_TAKEN.to_string(),
+                    })
+                    .await;
+                    release(plan, node, &mut waiting, &mut ready);
+                    continue;
+                }
+                if restored_completed[node] {
+                    debug!("Node '{}' completed before the checkpoint", node_id);
+                    metrics.nodes_restored += 1;
+                    statuses.insert(node_id.to_string(), NodeStatus::Restored);
+                    release(plan, node, &mut waiting, &mut ready);
+                    continue;
+                }
 
-        // Validate graph structure first
-        graph.def.validate()?;
+                let decisions = recording.as_mut().map(|r| &mut r.edges);
+                let scheduled = self
+                    .schedule_node(plan, &run, node, context, &skipped, decisions)
+                    .map_err(|error| plan.def().named_error(error))?;
+                let Some(scheduled) = scheduled else {
+                    info!(
+                        "Skipping node '{}': none of its incoming edges were taken",
+                        node_id
+                    );
+                    skipped[node] = true;
+                    metrics.nodes_skipped += 1;
+                    statuses.insert(node_id.to_string(), not_taken());
+                    self.emit(|| ExecutionEvent::NodeSkipped {
+                        node_id: node_id.to_string(),
+                        reason: NOT_TAKEN.to_string(),
+                    })
+                    .await;
+                    if let Some(recording) = &mut recording {
+                        recording.skipped.push(node_id.to_string());
+                    }
+                    if let Some(checkpointer) = checkpointer.as_deref_mut() {
+                        checkpointer.checkpoint.skipped.push(node_id.to_string());
+                    }
+                    release(plan, node, &mut waiting, &mut ready);
+                    continue;
+                };
+
+                self.emit(|| ExecutionEvent::NodeScheduled {
+                    node_id: scheduled.node_id.clone(),
+                })
+                .await;
+                let base = snapshot
+                    .get_or_insert_with(|| Arc::new(context.clone()))
+                    .clone();
+                let run = &run;
+                running.push(async move {
+                    let (outcome, view) = self
+                        .run_scheduled(plan, run, scheduled, (*base).clone())
+                        .await;
+                    (node, outcome, base, view)
+                });
+                in_flight.insert(node);
+            }
 
-        // Warn about disconnected components
-        if graph.def.has_disconnected_components() {
-            warn!("Graph has disconnected components - some nodes may not be reachable");
-        }
+            let Some((node, mut outcome, base, view)) = running.next().await else {
+                break;
+            };
+            in_flight.remove(&node);
+
+            // Nodes not ordered by an edge may finish in any order; when they
+            // write the same entry, the one with the larger id wins
+            snapshot = None;
+            for (key, value) in context_changes(&base, &view) {
+                if let Some(&writer) = writers.get(&key) {
+                    if writer > node && !plan.reaches(writer, node) {
+                        continue;
+                    }
+                }
+                match value {
+                    Some(value) => context.data.insert(key.clone(), value),
+                    None => context.data.remove(&key),
+                };
+                writers.insert(key, node);
+            }
 
-        // First, detect cycles in the graph
-        self.detect_cycles(graph)?;
+            if let Some(checkpointer) = checkpointer.as_deref_mut() {
+                // A resumed run starts cancelled and aborting nodes again
+                if outcome.completed(plan) {
+                    checkpointer
+                        .checkpoint
+                        .completed
+                        .push(outcome.node_id.clone());
+                }
+                let pending = in_flight
+                    .iter()
+                    .map(|&node| plan.node_id(node).to_string())
+                    .collect();
+                checkpointer.save(context, pending).await;
+            }
 
-        // Build adjacency list and in-degree map
-        let mut adj_list: HashMap<String, Vec<String>> = HashMap::new();
-        let mut in_degree: HashMap<String, usize> = HashMap::new();
+            if let Some(recording) = &mut recording {
+                recording.nodes.append(&mut outcome.records);
+            }
+            for (prefix, nested) in &outcome.nested_metrics {
+                metrics.merge_nested(prefix, nested);
+            }
+            if outcome.cache_hit {
+                metrics.cache_hits += 1;
+            }
 
-        // Initialize all nodes with 0 in-degree
-        for node_id in graph.def.nodes.keys() {
-            in_degree.insert(node_id.clone(), 0);
-            adj_list.insert(node_id.clone(), Vec::new());
+            let status = match &outcome.result {
+                Err(e) if e.is_cancelled() => {
+                    info!("Node '{}' was cancelled", outcome.node_id);
+                    metrics.nodes_cancelled += 1;
+                    None
+                }
+                Ok(_) => {
+                    info!(
+                        "Node '{}' executed successfully in {:?}",
+                        outcome.node_id, outcome.duration
+                    );
+                    metrics.nodes_executed += 1;
+                    Some(match outcome.cache_hit {
+                        true => NodeStatus::Cached,
+                        false => NodeStatus::Succeeded,
+                    })
+                }
+                Err(e) => {
+                    warn!("Node '{}' execution failed: {:?}", outcome.node_id, e);
+                    metrics.nodes_failed += 1;
+                    if e.is_timeout() {
+                        metrics.nodes_timed_out += 1;
+                    }
+                    if aborted.is_none() && plan.def().is_required(&outcome.node_id) {
+                        warn!(
+                            "Executor: Required node '{}' failed, not starting further nodes",
+                            outcome.node_id
+                        );
+                        aborted = Some((outcome.node_id.clone(), e.clone()));
+                        stopped = true;
+                        if let Some(stop) = &run.stop {
+                            stop.cancel();
+                        }
+                    }
+                    let error = e.clone();
+                    Some(match outcome.fallback {
+                        true => NodeStatus::Fallback { error },
+                        false => NodeStatus::Failed { error },
+                    })
+                }
+            };
+            if let Some(status) = status {
+                execution_order.push(outcome.node_id.clone());
+                statuses.insert(outcome.node_id.clone(), status);
+            }
+            metrics.node_stats.push(NodeExecutionStats {
+                node_id: outcome.node_id,
+                duration: outcome.duration,
+                cache_hit: outcome.cache_hit,
+                success: outcome.result.is_ok(),
+                timed_out: outcome.result.as_ref().is_err_and(RuleError::is_timeout),
+                cancelled: outcome.result.as_ref().is_err_and(RuleError::is_cancelled),
+                error: outcome.result.err(),
+                rule_traces: outcome.rule_traces,
+            });
+
+            release(plan, node, &mut waiting, &mut ready);
         }
+        drop(running);
 
-        // Build the graph structure
-        for edge in &graph.def.edges {
-            adj_list
-                .entry(edge.from.clone())
-                .or_insert_with(Vec::new)
-                .push(edge.to.clone());
+        metrics.total_duration = execution_start.elapsed();
+        metrics.rule_set_versions.extend(
+            run.rule_sets
+                .lock()
+                .iter()
+                .map(|(name, rule_set)| (name.clone(), rule_set.version())),
+        );
 
-            *in_degree.entry(edge.to.clone()).or_insert(0) += 1;
+        info!(
+            "Executor: Completed execution in {:?}. Executed: {}, Skipped: {}, Failed: {}, Cache hits: {}",
+            metrics.total_duration,
+            metrics.nodes_executed,
+            metrics.nodes_skipped,
+            metrics.nodes_failed,
+            metrics.cache_hits
+        );
+        if let Some(mut recording) = recording {
+            recording.final_context = context.data.clone();
+            recording.total_duration = metrics.total_duration;
+            *self.last_recording.lock() = Some(recording);
+        }
+        let completed = metrics.nodes_executed + metrics.nodes_failed;
+        let failed = metrics.failures().next().is_some();
+        *self.metrics.lock() = metrics.clone();
+
+        let progress = ErrorContext::new()
+            .with_step("execute")
+            .add_metadata("completed_nodes", completed.to_string())
+            .add_metadata("total_nodes", plan.node_count().to_string());
+        if run.is_cancelled() {
+            let error = RustLogicGraphError::cancelled_error("Graph execution was cancelled")
+                .with_context(progress.with_metrics(metrics));
+            return Err(plan.def().named_error(error));
+        }
+        if let Some((node_id, e)) = aborted {
+            let mut error = RustLogicGraphError::from(e)
+                .with_context(progress.with_metrics(metrics))
+                .with_node(&node_id);
+            error.message = format!("Required node '{}' failed: {}", node_id, error.message);
+            return Err(plan.def().named_error(error));
+        }
+        if run.deadline_exceeded.load(Ordering::Relaxed) {
+            let timeout = self.execution_timeout.unwrap_or_default();
+            let error = RustLogicGraphError::timeout_error(format!(
+                "Graph execution did not finish within {}ms",
+                timeout.as_millis()
+            ))
+            .with_context(progress.with_metrics(metrics));
+            return Err(plan.def().named_error(error));
         }
 
-        // Find all nodes with in-degree 0 (starting nodes)
-        let mut queue: VecDeque<String> = in_degree
+        let outputs = plan
+            .graph_outputs()
             .iter()
-            .filter(|(_, &degree)| degree == 0)
-            .map(|(id, _)| id.clone())
+            .filter_map(|binding| {
+                let value = binding.resolve(context)?;
+                Some((binding.port.clone(), value.clone()))
+            })
             .collect();
+        let status = match failed {
+            true => ExecutionStatus::CompletedWithFailures,
+            false => ExecutionStatus::Completed,
+        };
+        Ok(ExecutionResult {
+            status,
+            outputs,
+            nodes: statuses,
+            execution_order,
+            metrics,
+            trace_id: trace.trace_id().to_string(),
+        })
+    }
+
+    /// Decide whether a node runs, returning its cache key if it does.
+    ///
+    /// A node runs when it has no inputs or at least one incoming edge is
+    /// taken: its source was not skipped and its condition holds. Nodes whose
+    /// inputs are all dead are skipped, which propagates skipping down the
+    /// graph (dead-path elimination).
+    fn schedule_node(
+        &self,
+        plan: &ExecutionPlan,
+        run: &RunState,
+        node: usize,
+        context: &Context,
+        skipped: &[bool],
+        mut decisions: Option<&mut Vec<EdgeDecision>>,
+    ) -> Result<Option<ScheduledNode>> {
+        let node_id = plan.node_id(node);
+        info!("Executor: Processing node '{}'", node_id);
+
+        let incoming = plan.incoming(node);
+        let mut taken_sources = Vec::with_capacity(incoming.len());
+        for &edge_index in incoming {
+            let source = plan.edge_source(edge_index);
+            if skipped[source] {
+                continue;
+            }
 
-        if queue.is_empty() {
-            return Err(anyhow::anyhow!(
-                "No starting nodes found in graph (all nodes have incoming edges). \
-                This indicates either a cycle (which should have been caught earlier) \
-                or an invalid graph structure. Nodes: {:?}",
-                graph.def.nodes.keys().collect::<Vec<_>>()
-            ));
+            let edge = &plan.def().edges[edge_index];
+            let taken = plan
+                .conditions()
+                .is_satisfied(edge_index, &context.data)
+                .map_err(|e| {
+                    RustLogicGraphError::rule_evaluation_error(format!(
+                        "Failed to evaluate condition on edge {} -> {}: {}",
+                        edge.from, edge.to, e
+                    ))
+                    .with_node(node_id)
+                })?;
+            debug!(
+                "Edge {} -> {} (rule {:?}) taken: {}",
+                edge.from, edge.to, edge.rule, taken
+            );
+            if let Some(decisions) = decisions.as_deref_mut() {
+                decisions.push(EdgeDecision {
+                    from: edge.from.clone(),
+                    to: edge.to.clone(),
+                    rule: edge.rule.clone(),
+                    taken,
+                });
+            }
+            if taken {
+                taken_sources.push(source);
+            }
         }
 
-        let mut executed = HashSet::new();
-        let mut execution_order = Vec::new();
+        if !incoming.is_empty() && taken_sources.is_empty() {
+            return Ok(None);
+        }
 
-        // Topological sort & execution
-        while let Some(node_id) = queue.pop_front() {
-            if executed.contains(&node_id) {
-                continue;
+        let inputs: std::result::Result<Vec<_>, _> = plan
+            .inputs(node)
+            .iter()
+            .map(|binding| match binding.resolve(context) {
+                Some(value) => Ok((binding.port.clone(), value.clone())),
+                None => Err(RuleError::MissingVariable(format!(
+                    "{} (input '{}' of node '{}')",
+                    binding.source(),
+                    binding.port,
+                    node_id
+                ))),
+            })
+            .collect();
+
+        // A node missing an input fails without looking at the cache
+        let cache_key = match (&self.cache, &inputs) {
+            (Some(_), Ok(inputs)) => {
+                Some(self.cache_key(plan, run, node, context, &taken_sources, inputs)?)
             }
+            _ => None,
+        };
+
+        Ok(Some(ScheduledNode {
+            node,
+            node_id: node_id.to_string(),
+            cache_key,
+            inputs,
+        }))
+    }
 
-            info!("Executor: Processing node '{}'", node_id);
+    /// Version of the rule set `name` that `run` uses; the first lookup in a
+    /// run pins the active version for the rest of it
+    fn pinned_rule_set(
+        &self,
+        run: &RunState,
+        name: &str,
+    ) -> std::result::Result<Arc<RuleSet>, RuleError> {
+        let mut pinned = run.rule_sets.lock();
+        if let Some(rule_set) = pinned.get(name) {
+            return Ok(rule_set.clone());
+        }
 
-            // Check if all incoming edges have their rules satisfied
-            // IMPORTANT: Only check edges from nodes that have been executed
-            let incoming_edges: Vec<_> = graph
-                .def
-                .edges
-                .iter()
-                .filter(|e| e.to == node_id && executed.contains(&e.from))
-                .collect();
-
-            let mut should_execute = true;
-
-            for edge in &incoming_edges {
-                if let Some(rule_id) = &edge.rule {
-                    let rule = Rule::new(rule_id, "true"); // Default condition
-
-                    match rule.evaluate(&graph.context.data) {
-                        Ok(result) => {
-                            debug!(
-                                "Rule '{}' for edge {} -> {} evaluated to: {:?}",
-                                rule_id, edge.from, edge.to, result
-                            );
-
-                            if let serde_json::Value::Bool(false) = result {
-                                should_execute = false;
-                                info!(
-                                    "Skipping node '{}' due to failed rule '{}' from executed node '{}'",
-                                    node_id, rule_id, edge.from
-                                );
-                                self.metrics.nodes_skipped += 1;
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            warn!(
-                                "Rule '{}' evaluation failed: {}. Assuming true.",
-                                rule_id, e
-                            );
-                        }
-                    }
+        let manager = self.rule_sets.as_ref().ok_or_else(|| {
+            RuleError::Configuration(format!(
+                "Rule set '{}' requested but the executor has no rule set manager",
+                name
+            ))
+        })?;
+        let rule_set = manager.active(name).ok_or_else(|| {
+            RuleError::Configuration(format!("Rule set '{}' has not been loaded", name))
+        })?;
+        pinned.insert(name.to_string(), rule_set.clone());
+        Ok(rule_set)
+    }
+
+    /// Cache key of `node`: its id with the results of the nodes it was
+    /// reached from, its input port values, the version of the rule set it
+    /// runs and, for root nodes, the initial parameters in the context
+    fn cache_key(
+        &self,
+        plan: &ExecutionPlan,
+        run: &RunState,
+        node: usize,
+        context: &Context,
+        taken_sources: &[usize],
+        inputs: &[(String, serde_json::Value)],
+    ) -> Result<CacheKey> {
+        let node_id = plan.node_id(node);
+        let mut relevant_context: HashMap<String, serde_json::Value> = taken_sources
+            .iter()
+            .filter_map(|&source| {
+                context
+                    .data
+                    .get(plan.result_key(source))
+                    .map(|v| (plan.node_id(source).to_string(), v.clone()))
+            })
+            .collect();
+
+        // Different initial parameters (e.g. product_id) make different entries
+        if plan.incoming(node).is_empty() {
+            for (key, value) in &context.data {
+                if !key.ends_with("_result") {
+                    relevant_context.insert(format!("_initial_{}", key), value.clone());
                 }
             }
+        }
 
-            // Execute the node
-            if should_execute {
-                eprintln!("🔵 About to execute node: {}", node_id);
-                if let Some(node) = self.nodes.get(&node_id) {
-                    let node_start = Instant::now();
-                    let mut cache_hit = false;
-
-                    // Create cache key based on node ID and relevant context
-                    // Include both upstream node results AND initial parameters
-                    let mut relevant_context: HashMap<String, serde_json::Value> = incoming_edges
-                        .iter()
-                        .filter_map(|edge| {
-                            graph
-                                .context
-                                .data
-                                .get(&format!("{}_result", edge.from))
-                                .map(|v| (edge.from.clone(), v.clone()))
-                        })
-                        .collect();
-
-                    // For nodes with no incoming edges (root nodes), include initial params in cache key
-                    // This ensures different initial parameters (e.g., product_id) create different cache entries
-                    if incoming_edges.is_empty() {
-                        // Include all non-result context keys as initial params
-                        for (key, value) in &graph.context.data {
-                            if !key.ends_with("_result") {
-                                relevant_context.insert(format!("_initial_{}", key), value.clone());
-                            }
-                        }
-                    }
+        for (port, value) in inputs {
+            relevant_context.insert(format!("_input_{}", port), value.clone());
+        }
 
-                    let context_value = serde_json::to_value(&relevant_context)?;
-                    let cache_key = CacheKey::new(&node_id, &context_value);
-
-                    // Check cache first
-                    let cached_result = if let Some(cache) = &self.cache {
-                        cache.get(&cache_key)
-                    } else {
-                        None
-                    };
-
-                    let result = if let Some(cached_value) = cached_result {
-                        info!("Node '{}' result retrieved from cache", node_id);
-                        cache_hit = true;
-                        self.metrics.cache_hits += 1;
-
-                        // Merge cached result into context
-                        if let serde_json::Value::Object(cached_obj) = cached_value {
-                            for (k, v) in cached_obj {
-                                graph.context.data.insert(k, v);
-                            }
-                        }
+        // A new rule set version must not be answered with the old one's results
+        let rule_set = self.nodes.get(node_id).and_then(|n| n.rule_set());
+        if let Some(Ok(rule_set)) = rule_set.map(|name| self.pinned_rule_set(run, name)) {
+            relevant_context.insert(
+                format!("_rule_set_{}", rule_set.name()),
+                serde_json::Value::from(rule_set.version()),
+            );
+        }
 
-                        Ok(serde_json::Value::Null) // Successfully used cache
-                    } else {
-                        // Execute node and cache result
-                        let exec_result = node.run(&mut graph.context).await;
-
-                        // On failure, attempt graceful degradation via fallback handler
-                        if exec_result.is_err() {
-                            let fallback = crate::fault_tolerance::degradation::degrade_on_failure(
-                                &node_id,
-                                &mut graph.context,
-                                self.fallback_handler,
-                            );
-                            if fallback.is_some() {
-                                info!("Applied fallback for node '{}'", node_id);
-                            }
-                        }
+        let context_value = serde_json::to_value(&relevant_context).map_err(|e| {
+            RustLogicGraphError::serialization_error(format!(
+                "Failed to build the cache key of node '{}'",
+                node_id
+            ))
+            .with_node(node_id)
+            .with_source(e)
+        })?;
+        Ok(CacheKey::new(
+            format!("{}{}", self.cache_namespace, node_id),
+            &context_value,
+        ))
+    }
 
-                        // Store result in cache if execution succeeded (or fallback set _result)
-                        if let Some(cache) = &self.cache {
-                            let mut result_only: HashMap<String, serde_json::Value> =
-                                HashMap::new();
-                            for (key, value) in &graph.context.data {
-                                if key.ends_with("_result") {
-                                    result_only.insert(key.clone(), value.clone());
-                                }
-                            }
-                            let context_result = serde_json::to_value(&result_only)?;
-                            if let Err(e) = cache.put(cache_key, context_result, None) {
-                                warn!("Failed to cache result for node '{}': {}", node_id, e);
-                            }
-                        }
+    /// Attempt graceful degradation of a failed node via the fallback
+    /// handler, returning whether it supplied a result
+    fn apply_fallback(&self, node_id: &str, ctx: &mut Context) -> bool {
+        let fallback = crate::fault_tolerance::degradation::degrade_on_failure(
+            node_id,
+            ctx,
+            self.fallback_handler,
+        );
+        if fallback.is_some() {
+            info!("Applied fallback for node '{}'", node_id);
+        }
+        fallback.is_some()
+    }
 
-                        exec_result
-                    };
-
-                    match result {
-                        Ok(_) => {
-                            let duration = node_start.elapsed();
-                            info!("Node '{}' executed successfully in {:?}", node_id, duration);
-                            execution_order.push(node_id.clone());
-
-                            self.metrics.nodes_executed += 1;
-                            self.metrics.node_stats.push(NodeExecutionStats {
-                                node_id: node_id.clone(),
-                                duration,
-                                cache_hit,
-                                success: true,
-                            });
-                        }
-                        Err(e) => {
-                            let duration = node_start.elapsed();
-                            warn!("Node '{}' execution failed: {:?}", node_id, e);
-
-                            self.metrics.nodes_failed += 1;
-                            self.metrics.node_stats.push(NodeExecutionStats {
-                                node_id: node_id.clone(),
-                                duration,
-                                cache_hit,
-                                success: false,
-                            });
-                        }
-                    }
+    /// Apply the recorded outcome of `node`'s next invocation to `ctx` if the
+    /// executor replays it; `None` when the node has to run
+    fn replay_node(
+        &self,
+        run: &RunState,
+        node: &dyn Node,
+        ctx: &mut Context,
+    ) -> Option<RuleResult> {
+        let replay = self.replay.as_ref()?;
+        if !replay.replays(node.id(), &node.node_type()) {
+            return None;
+        }
+
+        let occurrence = {
+            let mut replayed = run.replayed.lock();
+            let count = replayed.entry(node.id().to_string()).or_insert(0);
+            *count += 1;
+            *count - 1
+        };
+        info!("Replaying node '{}' from recording", node.id());
+        Some(replay.apply(node.id(), occurrence, ctx))
+    }
+
+    /// Run `node` within its `timeout_ms` and the run's deadline, and until
+    /// the run is cancelled if `cancellable`.
+    ///
+    /// A node that runs out of time is cancelled, its changes to `ctx` are
+    /// undone and it fails with [`RuleError::Timeout`]. A node dropped
+    /// because the run was cancelled has its changes undone too and fails
+    /// with [`RuleError::Cancelled`]; nodes that observe cancellation
+    /// themselves are left running.
+    async fn run_limited(
+        &self,
+        run: &RunState,
+        node: &dyn Node,
+        ctx: &mut Context,
+        scope: &ExecutionScope<'_>,
+        cancellable: bool,
+    ) -> RuleResult {
+        let cancel = run.stop.as_ref().filter(|_| cancellable);
+        if cancel.is_some_and(CancellationToken::is_cancelled) {
+            return Err(cancelled(node.id()));
+        }
+        let cancel = cancel.filter(|_| !node.observes_cancellation());
+
+        let limit = run.time_limit(node.id());
+        if limit.is_none() && cancel.is_none() {
+            return node.run_scoped(ctx, scope).await;
+        }
+
+        let before = ctx.clone();
+        let outcome = {
+            let call = node.run_scoped(ctx, scope);
+            let limited = async {
+                match limit {
+                    Some((limit, _)) => tokio::time::timeout(limit, call).await.ok(),
+                    None => Some(call.await),
+                }
+            };
+            match cancel {
+                Some(cancel) => tokio::select! {
+                    biased;
+                    _ = cancel.cancelled() => Err(()),
+                    outcome = limited => Ok(outcome),
+                },
+                None => Ok(limited.await),
+            }
+        };
+
+        match outcome {
+            Ok(Some(result)) => result,
+            Err(()) => {
+                *ctx = before;
+                info!("Node '{}' dropped: execution cancelled", node.id());
+                Err(cancelled(node.id()))
+            }
+            Ok(None) => {
+                let (limit, is_deadline) = limit.unwrap_or_default();
+                *ctx = before;
+                if is_deadline {
+                    run.deadline_exceeded.store(true, Ordering::Relaxed);
+                    warn!("Node '{}' cancelled at the execution deadline", node.id());
+                    Err(RuleError::Timeout(format!(
+                        "Node '{}' was cancelled at the execution deadline",
+                        node.id()
+                    )))
                 } else {
-                    warn!("Node '{}' not found in executor", node_id);
+                    warn!("Node '{}' timed out after {:?}", node.id(), limit);
+                    Err(RuleError::Timeout(format!(
+                        "Node '{}' timed out after {}ms",
+                        node.id(),
+                        limit.as_millis()
+                    )))
                 }
             }
+        }
+    }
 
-            executed.insert(node_id.clone());
-
-            // Add downstream nodes to queue
-            if let Some(neighbors) = adj_list.get(&node_id) {
-                for neighbor in neighbors {
-                    if let Some(degree) = in_degree.get_mut(neighbor) {
-                        *degree = degree.saturating_sub(1);
-                        if *degree == 0 && !executed.contains(neighbor) {
-                            queue.push_back(neighbor.clone());
-                        }
-                    }
+    /// Record of `node` turning the context `before` into `after`
+    fn node_record(
+        &self,
+        node: &dyn Node,
+        before: &Context,
+        after: &Context,
+        result: &RuleResult,
+    ) -> NodeRecord {
+        let mut output = HashMap::new();
+        let mut removed = Vec::new();
+        for (key, value) in context_changes(before, after) {
+            match value {
+                Some(value) => {
+                    output.insert(key, value);
                 }
+                None => removed.push(key),
             }
         }
 
-        self.metrics.total_duration = execution_start.elapsed();
+        NodeRecord {
+            node_id: node.id().to_string(),
+            node_type: node.node_type(),
+            context: before.data.clone(),
+            inputs: HashMap::new(),
+            output,
+            removed,
+            result: result.as_ref().ok().cloned(),
+            error: result.as_ref().err().cloned(),
+            duration: Duration::ZERO,
+            cache_hit: false,
+            replayed: self
+                .replay
+                .as_ref()
+                .is_some_and(|replay| replay.replays(node.id(), &node.node_type())),
+        }
+    }
+
+    /// Run a single node on its own view of the context in a
+    /// `node_execution` span, reporting its start and finish to listeners
+    async fn run_scheduled(
+        &self,
+        plan: &ExecutionPlan,
+        run: &Arc<RunState>,
+        scheduled: ScheduledNode,
+        ctx: Context,
+    ) -> (NodeOutcome, Context) {
+        let parent = TraceContext::current();
+        let trace = parent
+            .as_ref()
+            .map_or_else(TraceContext::new_root, TraceContext::child);
+        let node_type = self.nodes.get(&scheduled.node_id).map(|n| n.node_type());
+        let span = info_span!(
+            "node_execution",
+            node.id = scheduled.node_id.as_str(),
+            "node.type" = node_type.as_ref().map_or("", |t| t.name()),
+            node.cache_hit = Empty,
+            node.outcome = Empty,
+            trace_id = trace.trace_id(),
+            span_id = trace.span_id(),
+            parent_span_id = Empty,
+            otel.status_code = Empty,
+        );
+        if let Some(parent) = &parent {
+            span.record("parent_span_id", parent.span_id());
+        }
 
-        info!(
-            "Executor: Completed execution in {:?}. Executed: {}, Skipped: {}, Failed: {}, Cache hits: {}",
-            self.metrics.total_duration,
-            self.metrics.nodes_executed,
-            self.metrics.nodes_skipped,
-            self.metrics.nodes_failed,
-            self.metrics.cache_hits
+        self.emit(|| ExecutionEvent::NodeStarted {
+            node_id: scheduled.node_id.clone(),
+        })
+        .await;
+        let (outcome, ctx) = trace
+            .scope(self.run_recorded(plan, run, scheduled, ctx))
+            .instrument(span.clone())
+            .await;
+        span.record("node.cache_hit", outcome.cache_hit);
+        span.record("node.outcome", outcome.label());
+        span.record(
+            "otel.status_code",
+            if outcome.result.is_ok() {
+                "OK"
+            } else {
+                "ERROR"
+            },
         );
 
-        // Verify all nodes were executed (should not happen with cycle detection)
-        let unexecuted: Vec<_> = graph
-            .def
-            .nodes
-            .keys()
-            .filter(|id| !executed.contains(*id))
+        self.emit(|| ExecutionEvent::NodeFinished {
+            node_id: outcome.node_id.clone(),
+            result: outcome.result.clone(),
+            duration: outcome.duration,
+        })
+        .await;
+        (outcome, ctx)
+    }
+
+    /// Run a single node on its own view of the context, recording the
+    /// invocation when recording is enabled
+    async fn run_recorded(
+        &self,
+        plan: &ExecutionPlan,
+        run: &Arc<RunState>,
+        scheduled: ScheduledNode,
+        ctx: Context,
+    ) -> (NodeOutcome, Context) {
+        let node = self.nodes.get(&scheduled.node_id);
+        let (Some(node), true) = (node, self.recording) else {
+            return self.run_view(plan, run, scheduled, ctx).await;
+        };
+
+        let before = ctx.clone();
+        let inputs = scheduled
+            .inputs
+            .as_ref()
+            .map(|inputs| inputs.iter().cloned().collect())
+            .unwrap_or_default();
+        let (mut outcome, ctx) = self.run_view(plan, run, scheduled, ctx).await;
+
+        let mut record = self.node_record(node.as_ref(), &before, &ctx, &outcome.result);
+        record.inputs = inputs;
+        record.duration = outcome.duration;
+        record.cache_hit = outcome.cache_hit;
+        outcome.records.push(record);
+        (outcome, ctx)
+    }
+
+    /// Run a single node on its own view of the context
+    async fn run_view(
+        &self,
+        plan: &ExecutionPlan,
+        run: &Arc<RunState>,
+        scheduled: ScheduledNode,
+        mut ctx: Context,
+    ) -> (NodeOutcome, Context) {
+        let node_id = scheduled.node_id;
+        let node_start = Instant::now();
+
+        let Some(node) = self.nodes.get(&node_id) else {
+            warn!("Node '{}' not found in executor", node_id);
+            let outcome = NodeOutcome {
+                result: Err(RuleError::Configuration(format!(
+                    "Node '{}' not found in executor",
+                    node_id
+                ))),
+                node_id,
+                duration: node_start.elapsed(),
+                cache_hit: false,
+                fallback: false,
+                nested_metrics: Vec::new(),
+                rule_traces: Vec::new(),
+                records: Vec::new(),
+            };
+            return (outcome, ctx);
+        };
+
+        let inputs = match scheduled.inputs {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                let fallback =
+                    !plan.def().is_required(&node_id) && self.apply_fallback(&node_id, &mut ctx);
+                if fallback {
+                    self.emit(|| ExecutionEvent::FallbackApplied {
+                        node_id: node_id.clone(),
+                        error: e.clone(),
+                    })
+                    .await;
+                }
+                let outcome = NodeOutcome {
+                    node_id,
+                    result: Err(e),
+                    duration: node_start.elapsed(),
+                    cache_hit: false,
+                    fallback,
+                    nested_metrics: Vec::new(),
+                    rule_traces: Vec::new(),
+                    records: Vec::new(),
+                };
+                return (outcome, ctx);
+            }
+        };
+
+        if let Some(result) = self.replay_node(run, node.as_ref(), &mut ctx) {
+            let outcome = NodeOutcome {
+                node_id,
+                result,
+                duration: node_start.elapsed(),
+                cache_hit: false,
+                fallback: false,
+                nested_metrics: Vec::new(),
+                rule_traces: Vec::new(),
+                records: Vec::new(),
+            };
+            return (outcome, ctx);
+        }
+
+        // Check cache first
+        let cached_result = self
+            .cache
+            .as_ref()
+            .zip(scheduled.cache_key.as_ref())
+            .and_then(|(cache, key)| cache.get(key));
+
+        if let Some(cached_value) = cached_result {
+            info!("Node '{}' result retrieved from cache", node_id);
+            self.emit(|| ExecutionEvent::CacheHit {
+                node_id: node_id.clone(),
+            })
+            .await;
+
+            let result = apply_cache_entry(cached_value, &mut ctx);
+            let outcome = NodeOutcome {
+                node_id,
+                result: Ok(result),
+                duration: node_start.elapsed(),
+                cache_hit: true,
+                fallback: false,
+                nested_metrics: Vec::new(),
+                rule_traces: Vec::new(),
+                records: Vec::new(),
+            };
+            return (outcome, ctx);
+        }
+
+        // Execute node with its input ports bound, and cache result
+        let before = scheduled.cache_key.as_ref().map(|_| ctx.clone());
+        let shadowed: Vec<_> = inputs
+            .into_iter()
+            .map(|(port, value)| {
+                let previous = ctx.data.insert(port.clone(), value);
+                (port, previous)
+            })
             .collect();
 
-        if !unexecuted.is_empty() {
-            return Err(anyhow::anyhow!(
-                "Some nodes were not executed: {:?}. This indicates a bug in the executor logic.",
-                unexecuted
-            ));
+        let scope = ExecutionScope::with_run(self, run.clone());
+        let result = self
+            .run_limited(run, node.as_ref(), &mut ctx, &scope, true)
+            .await;
+        let nested_metrics = scope.take_nested_metrics();
+        let rule_traces = scope.take_rule_traces();
+        let records = scope.take_records();
+
+        for (port, previous) in shadowed {
+            match previous {
+                Some(value) => ctx.data.insert(port, value),
+                None => ctx.data.remove(&port),
+            };
+        }
+
+        // Every declared output must be a field of the node's result
+        let produced = ctx.get(plan.result_key(scheduled.node));
+        let missing_output = plan
+            .outputs(scheduled.node)
+            .iter()
+            .find(|output| produced.and_then(|r| r.get(output.as_str())).is_none());
+        let result = match (result, missing_output) {
+            (Ok(_), Some(output)) => Err(RuleError::Configuration(format!(
+                "Node '{}' did not produce declared output '{}'",
+                node_id, output
+            ))),
+            (result, _) => result,
+        };
+
+        // A cancelled node's result is discarded along with the run, as is
+        // that of a required node whose failure aborts the run
+        let aborts = |e: &RuleError| e.is_cancelled() || plan.def().is_required(&node_id);
+        if result.as_ref().is_err_and(aborts) {
+            let outcome = NodeOutcome {
+                node_id,
+                result,
+                duration: node_start.elapsed(),
+                cache_hit: false,
+                fallback: false,
+                nested_metrics,
+                rule_traces,
+                records,
+            };
+            return (outcome, ctx);
         }
 
-        Ok(())
+        // On failure, attempt graceful degradation via fallback handler
+        let fallback = result.is_err() && self.apply_fallback(&node_id, &mut ctx);
+        if let (true, Err(error)) = (fallback, &result) {
+            self.emit(|| ExecutionEvent::FallbackApplied {
+                node_id: node_id.clone(),
+                error: error.clone(),
+            })
+            .await;
+        }
+
+        // Store result in cache if execution succeeded; a failed node runs
+        // again next time, even when the fallback handler covered for it
+        if let (Some(cache), Some(cache_key), Some(before), Ok(value)) =
+            (&self.cache, scheduled.cache_key, before, &result)
+        {
+            let entry = cache_entry(value, &before, &ctx);
+            if let Err(e) = cache.put(cache_key, entry, None) {
+                warn!("Failed to cache result for node '{}': {}", node_id, e);
+            }
+        }
+
+        let outcome = NodeOutcome {
+            node_id,
+            result,
+            duration: node_start.elapsed(),
+            cache_hit: false,
+            fallback,
+            nested_metrics,
+            rule_traces,
+            records,
+        };
+        (outcome, ctx)
     }
 }
 
+/// A node selected to run
+struct ScheduledNode {
+    node: usize,
+    node_id: String,
+    /// Set when the executor has a cache
+    cache_key: Option<CacheKey>,
+    inputs: std::result::Result<Vec<(String, serde_json::Value)>, RuleError>,
+}
+
+/// Result of running one node
+struct NodeOutcome {
+    node_id: String,
+    result: RuleResult,
+    duration: Duration,
+    cache_hit: bool,
+    /// Whether the fallback handler supplied the result of the failed node
+    fallback: bool,
+    nested_metrics: Vec<(String, ExecutionMetrics)>,
+    rule_traces: Vec<RuleTrace>,
+    /// Invocations of this node and of the nodes it ran through its scope,
+    /// when recording is enabled
+    records: Vec<NodeRecord>,
+}
+
+impl NodeOutcome {
+    /// How the node ended, as reported on its span
+    fn label(&self) -> &'static str {
+        match &self.result {
+            Ok(_) if self.cache_hit => "cached",
+            Ok(_) => "succeeded",
+            Err(e) if e.is_cancelled() => "cancelled",
+            Err(_) if self.fallback => "fallback",
+            Err(_) => "failed",
+        }
+    }
+
+    /// Whether a resumed run can keep this outcome: the node was not
+    /// cancelled, and did not fail if its failure aborts the run
+    fn completed(&self, plan: &ExecutionPlan) -> bool {
+        match &self.result {
+            Ok(_) => true,
+            Err(e) => !e.is_cancelled() && !plan.def().is_required(&self.node_id),
+        }
+    }
+}
+
+/// Progress of a checkpointed run and the store it is saved to
+struct Checkpointer {
+    store: Arc<dyn ContextStore>,
+    checkpoint: Checkpoint,
+}
+
+impl Checkpointer {
+    /// Save `context` as the state after the completed nodes, with `pending`
+    /// nodes still running. A failed save is logged; the run goes on.
+    async fn save(&mut self, context: &Context, pending: Vec<String>) {
+        self.checkpoint.context = context.data.clone();
+        self.checkpoint.pending = pending;
+        if let Err(e) = self.checkpoint.save(self.store.as_ref()).await {
+            warn!("{}", e);
+        }
+    }
+}
+
+/// Why a node is skipped by dead-path elimination
+const NOT_TAKEN: &str = "none of its incoming edges were taken";
+
+/// Status of a node skipped by dead-path elimination
+fn not_taken() -> NodeStatus {
+    NodeStatus::Skipped {
+        reason: NOT_TAKEN.to_string(),
+    }
+}
+
+/// Error of a node dropped because its execution was cancelled
+fn cancelled(node_id: &str) -> RuleError {
+    RuleError::Cancelled(format!(
+        "Node '{}' was cancelled with its execution",
+        node_id
+    ))
+}
+
+/// Mark `node` finished, moving the successors it was the last unfinished
+/// source of to `ready`
+fn release(plan: &ExecutionPlan, node: usize, waiting: &mut [usize], ready: &mut BTreeSet<usize>) {
+    for successor in plan.successors(node) {
+        waiting[successor] -= 1;
+        if waiting[successor] == 0 {
+            ready.insert(successor);
+        }
+    }
+}
+
+/// Cache entry for a node run that turned `before` into `after`: its result
+/// and every change it made to the context, not only its `_result` entry, so
+/// that facts a rule node writes are restored on a cache hit too
+fn cache_entry(result: &serde_json::Value, before: &Context, after: &Context) -> serde_json::Value {
+    let mut changes = serde_json::Map::new();
+    let mut removed = Vec::new();
+    for (key, value) in context_changes(before, after) {
+        match value {
+            Some(value) => {
+                changes.insert(key, value);
+            }
+            None => removed.push(serde_json::Value::String(key)),
+        }
+    }
+    serde_json::json!({
+        "result": result,
+        "changes": changes,
+        "removed": removed,
+    })
+}
+
+/// Apply a [`cache_entry`] to `ctx`, returning the cached result
+fn apply_cache_entry(entry: serde_json::Value, ctx: &mut Context) -> serde_json::Value {
+    let serde_json::Value::Object(mut entry) = entry else {
+        return serde_json::Value::Null;
+    };
+    if let Some(serde_json::Value::Object(changes)) = entry.remove("changes") {
+        ctx.data.extend(changes);
+    }
+    if let Some(serde_json::Value::Array(removed)) = entry.remove("removed") {
+        for key in removed.iter().filter_map(serde_json::Value::as_str) {
+            ctx.data.remove(key);
+        }
+    }
+    entry.remove("result").unwrap_or_default()
+}
+
+/// Entries a node added, changed (`Some`) or removed (`None`) in its view of
+/// the context, in key order
+fn context_changes(before: &Context, after: &Context) -> Vec<(String, Option<serde_json::Value>)> {
+    let mut changes: Vec<_> = after
+        .data
+        .iter()
+        .filter(|(key, value)| before.data.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), Some(value.clone())))
+        .chain(
+            before
+                .data
+                .keys()
+                .filter(|key| !after.data.contains_key(*key))
+                .map(|key| (key.clone(), None)),
+        )
+        .collect();
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    changes
+}
+
 impl Default for Executor {
     fn default() -> Self {
         Self::new()
@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::error::{Result, RustLogicGraphError};
 use crate::node::NodeType;
+use crate::rule::{DecisionTableDef, HitPolicy};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Edge {
     pub from: String,
     pub to: String,
+    /// Condition guarding the edge: the name of a rule in [`GraphDef::rules`]
+    /// or an inline expression (e.g. `"score >= 700"`)
     pub rule: Option<String>,
 }
 
@@ -26,6 +30,10 @@ impl Edge {
 }
 
 /// Configuration for a node in the graph
+///
+/// Fields beyond `node_type` are read by the node's factory (see
+/// [`NodeRegistry`](crate::node::NodeRegistry)); each node type only uses the
+/// fields that apply to it.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct NodeConfig {
     pub node_type: NodeType,
@@ -37,8 +45,111 @@ pub struct NodeConfig {
     pub prompt: Option<String>,
     /// Optional list of context keys to extract as query parameters
     /// Example: ["product_id", "user_id"] will extract ctx.get("product_id") and ctx.get("user_id")
+    /// Nested values can be addressed by path, e.g. "customer_result.profile.id"
     #[serde(default)]
     pub params: Option<Vec<String>>,
+    /// Cancel the node and fail it with a timeout error if it runs longer
+    /// than this; applies to every node type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Whether a failure of this node aborts the execution (`true`) or is
+    /// tolerated (`false`); unset follows the graph's [`FailurePolicy`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// RuleNode: inline GRL rule set, run instead of `condition`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grl: Option<String>,
+    /// RuleNode: path to a `.grl` file, run instead of `condition`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grl_file: Option<String>,
+    /// RuleNode: name of a rule set loaded into the executor's
+    /// `RuleSetManager`, run instead of `condition`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_set: Option<String>,
+
+    /// DecisionTableNode: inline decision table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<DecisionTableDef>,
+    /// DecisionTableNode: path to a `.csv` or YAML decision table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table_file: Option<String>,
+    /// DecisionTableNode: hit policy, overriding the table's own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit_policy: Option<HitPolicy>,
+
+    /// SubgraphNode: inline child graph definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subgraph: Option<Box<GraphDef>>,
+    /// SubgraphNode: path to a YAML/JSON child graph definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subgraph_file: Option<String>,
+    /// SubgraphNode: parent context key -> child context key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_mapping: Option<HashMap<String, String>>,
+    /// SubgraphNode: child context key -> parent context key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_mapping: Option<HashMap<String, String>>,
+
+    /// ConditionalNode: node selected when the condition holds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub true_branch: Option<String>,
+    /// ConditionalNode: node selected when the condition fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub false_branch: Option<String>,
+
+    /// LoopNode: context key holding the array to iterate over
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection_key: Option<String>,
+    /// LoopNode: safety limit on the number of iterations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+    /// LoopNode: node executed on every iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_node: Option<String>,
+
+    /// TryCatchNode: node executed in the try block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub try_node: Option<String>,
+    /// TryCatchNode: node executed when the try block fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catch_node: Option<String>,
+    /// TryCatchNode: node executed after the try/catch blocks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finally_node: Option<String>,
+
+    /// RetryNode / CircuitBreakerNode: node being protected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_node: Option<String>,
+    /// RetryNode: number of retries after the first attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<usize>,
+    /// RetryNode: delay before the first retry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_delay_ms: Option<u64>,
+    /// RetryNode: factor applied to the delay after every retry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff_multiplier: Option<f64>,
+
+    /// CircuitBreakerNode: consecutive failures before the circuit opens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_threshold: Option<usize>,
+    /// CircuitBreakerNode: how long the circuit stays open before probing again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_timeout_ms: Option<u64>,
+
+    /// Free-form settings for custom node types registered by users
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<serde_json::Value>,
+
+    /// Named inputs: port name -> where the executor reads its value from.
+    /// Each port is set in the node's context under the port name while the
+    /// node runs.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub inputs: HashMap<String, InputSource>,
+    /// Fields this node's result object is guaranteed to contain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<String>,
 }
 
 impl NodeConfig {
@@ -46,19 +157,60 @@ impl NodeConfig {
         Self {
             node_type: NodeType::RuleNode,
             condition: Some(condition.into()),
-            query: None,
-            prompt: None,
-            params: None,
+            ..Default::default()
+        }
+    }
+
+    /// Create a RuleNode configuration running an inline GRL rule set
+    pub fn grl_rule_node(grl: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::RuleNode,
+            grl: Some(grl.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a RuleNode configuration running the GRL rules of a `.grl` file
+    pub fn grl_rule_file(path: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::RuleNode,
+            grl_file: Some(path.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a RuleNode configuration running a managed, hot-reloadable rule set
+    pub fn rule_set_node(rule_set: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::RuleNode,
+            rule_set: Some(rule_set.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a DecisionTableNode configuration with an inline table
+    pub fn decision_table_node(table: DecisionTableDef) -> Self {
+        Self {
+            node_type: NodeType::DecisionTableNode,
+            table: Some(table),
+            ..Default::default()
+        }
+    }
+
+    /// Create a DecisionTableNode configuration reading a `.csv` or YAML table
+    pub fn decision_table_file(path: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::DecisionTableNode,
+            table_file: Some(path.into()),
+            ..Default::default()
         }
     }
 
     pub fn db_node(query: impl Into<String>) -> Self {
         Self {
             node_type: NodeType::DBNode,
-            condition: None,
             query: Some(query.into()),
-            prompt: None,
-            params: None,
+            ..Default::default()
         }
     }
 
@@ -66,20 +218,17 @@ impl NodeConfig {
     pub fn db_node_with_params(query: impl Into<String>, params: Vec<String>) -> Self {
         Self {
             node_type: NodeType::DBNode,
-            condition: None,
             query: Some(query.into()),
-            prompt: None,
             params: Some(params),
+            ..Default::default()
         }
     }
 
     pub fn ai_node(prompt: impl Into<String>) -> Self {
         Self {
             node_type: NodeType::AINode,
-            condition: None,
-            query: None,
             prompt: Some(prompt.into()),
-            params: None,
+            ..Default::default()
         }
     }
 
@@ -88,20 +237,305 @@ impl NodeConfig {
         Self {
             node_type: NodeType::GrpcNode,
             query: Some(format!("{}#{}", service_url.into(), method.into())),
-            condition: None,
-            prompt: None,
-            params: None,
+            ..Default::default()
+        }
+    }
+
+    /// Create a SubgraphNode configuration with an inline child graph
+    pub fn subgraph_node(graph_def: GraphDef) -> Self {
+        Self {
+            node_type: NodeType::SubgraphNode,
+            subgraph: Some(Box::new(graph_def)),
+            ..Default::default()
+        }
+    }
+
+    /// Create a SubgraphNode configuration that loads its child graph from a file
+    pub fn subgraph_file(path: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::SubgraphNode,
+            subgraph_file: Some(path.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a ConditionalNode configuration
+    pub fn conditional_node(
+        condition: impl Into<String>,
+        true_branch: impl Into<String>,
+        false_branch: impl Into<String>,
+    ) -> Self {
+        Self {
+            node_type: NodeType::ConditionalNode,
+            condition: Some(condition.into()),
+            true_branch: Some(true_branch.into()),
+            false_branch: Some(false_branch.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a while-style LoopNode configuration
+    pub fn while_loop(condition: impl Into<String>, max_iterations: usize) -> Self {
+        Self {
+            node_type: NodeType::LoopNode,
+            condition: Some(condition.into()),
+            max_iterations: Some(max_iterations),
+            ..Default::default()
+        }
+    }
+
+    /// Create a LoopNode configuration iterating over a context array
+    pub fn foreach_loop(collection_key: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::LoopNode,
+            collection_key: Some(collection_key.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a TryCatchNode configuration
+    pub fn try_catch_node(try_node: impl Into<String>) -> Self {
+        Self {
+            node_type: NodeType::TryCatchNode,
+            try_node: Some(try_node.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a RetryNode configuration
+    pub fn retry_node(target_node: impl Into<String>, max_retries: usize) -> Self {
+        Self {
+            node_type: NodeType::RetryNode,
+            target_node: Some(target_node.into()),
+            max_retries: Some(max_retries),
+            ..Default::default()
         }
     }
+
+    /// Create a CircuitBreakerNode configuration
+    pub fn circuit_breaker_node(target_node: impl Into<String>, failure_threshold: usize) -> Self {
+        Self {
+            node_type: NodeType::CircuitBreakerNode,
+            target_node: Some(target_node.into()),
+            failure_threshold: Some(failure_threshold),
+            ..Default::default()
+        }
+    }
+
+    /// Create a configuration for a user-registered node type
+    pub fn custom(type_name: impl Into<String>, settings: serde_json::Value) -> Self {
+        Self {
+            node_type: NodeType::Custom(type_name.into()),
+            settings: Some(settings),
+            ..Default::default()
+        }
+    }
+
+    /// Bind the input port `port` to `source`
+    pub fn with_input(mut self, port: impl Into<String>, source: InputSource) -> Self {
+        self.inputs.insert(port.into(), source);
+        self
+    }
+
+    /// Mark the node required (its failure aborts the execution) or
+    /// optional (its failure is tolerated), whatever the graph's
+    /// [`FailurePolicy`]
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = Some(required);
+        self
+    }
+
+    /// Declare the fields of this node's result object
+    pub fn with_outputs<I, S>(mut self, outputs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.outputs = outputs.into_iter().map(Into::into).collect();
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Where the value of a node's input port comes from
+///
+/// ```yaml
+/// inputs:
+///   product: { context: product_id }            # initial context key
+///   stock: { node: check_inventory }            # whole upstream result
+///   price: { node: pricing, output: total }     # declared upstream output
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum InputSource {
+    /// Result of an upstream node, or one of its declared outputs
+    Node {
+        node: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+    },
+    /// Value stored in the context under `context`
+    Context { context: String },
+}
+
+impl InputSource {
+    /// The whole result of upstream node `node`
+    pub fn node(node: impl Into<String>) -> Self {
+        InputSource::Node {
+            node: node.into(),
+            output: None,
+        }
+    }
+
+    /// The declared output `output` of upstream node `node`
+    pub fn node_output(node: impl Into<String>, output: impl Into<String>) -> Self {
+        InputSource::Node {
+            node: node.into(),
+            output: Some(output.into()),
+        }
+    }
+
+    /// The context value stored under `key`
+    pub fn context(key: impl Into<String>) -> Self {
+        InputSource::Context {
+            context: key.into(),
+        }
+    }
+}
+
+/// A named rule that edges can reference by name
+///
+/// Written either as a plain condition expression or as a GRL rule set:
+///
+/// ```yaml
+/// rules:
+///   approved: "credit_score >= 700 && income > 50000"
+///   needs_review:
+///     grl: |
+///       rule "Review" {
+///         when amount > 10000 then passed = true;
+///       }
+///     outcome: passed
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RuleDef {
+    /// Condition expression evaluated with [`Rule`](crate::rule::Rule)
+    Condition(String),
+    /// GRL rule set run with a [`RuleEngine`](crate::rule::RuleEngine); the
+    /// edge is taken when the rules set the `outcome` fact to `true`
+    Grl {
+        grl: String,
+        #[serde(default = "RuleDef::default_outcome")]
+        outcome: String,
+    },
+}
+
+impl RuleDef {
+    fn default_outcome() -> String {
+        "passed".to_string()
+    }
+
+    /// Build a GRL rule with the default `passed` outcome fact
+    pub fn grl(grl: impl Into<String>) -> Self {
+        RuleDef::Grl {
+            grl: grl.into(),
+            outcome: Self::default_outcome(),
+        }
+    }
+}
+
+/// What an execution does when a node fails
+///
+/// Nodes can override the policy with their `required` flag (see
+/// [`NodeConfig::with_required`]).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Apply the node's fallback and go on with the rest of the graph
+    #[default]
+    Continue,
+    /// Cancel the running nodes, stop scheduling and fail the execution with
+    /// the node's error
+    FailFast,
+}
+
+impl FailurePolicy {
+    fn is_continue(&self) -> bool {
+        *self == FailurePolicy::Continue
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct GraphDef {
+    /// Name reported in the errors of the graph's executions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     pub nodes: HashMap<String, NodeConfig>,
     pub edges: Vec<Edge>,
+    /// Named rules referenced by [`Edge::rule`]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rules: HashMap<String, RuleDef>,
+    /// What executions do when a node fails
+    #[serde(default, skip_serializing_if = "FailurePolicy::is_continue")]
+    pub failure_policy: FailurePolicy,
+    /// Values reported in the [`ExecutionResult`](crate::core::ExecutionResult)
+    /// of every run: output name -> where the value is read from
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub outputs: HashMap<String, InputSource>,
 }
 
 impl GraphDef {
+    pub fn new(nodes: HashMap<String, NodeConfig>, edges: Vec<Edge>) -> Self {
+        Self {
+            name: None,
+            nodes,
+            edges,
+            rules: HashMap::new(),
+            failure_policy: FailurePolicy::Continue,
+            outputs: HashMap::new(),
+        }
+    }
+
+    /// Name the graph
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set what executions of the graph do when a node fails
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = policy;
+        self
+    }
+
+    /// Report the value of `source` as the output `name` of every run
+    pub fn with_output(mut self, name: impl Into<String>, source: InputSource) -> Self {
+        self.outputs.insert(name.into(), source);
+        self
+    }
+
+    /// Whether a failure of `node_id` aborts executions of the graph
+    pub fn is_required(&self, node_id: &str) -> bool {
+        self.nodes
+            .get(node_id)
+            .and_then(|config| config.required)
+            .unwrap_or(self.failure_policy == FailurePolicy::FailFast)
+    }
+
+    /// `error` with this graph's name attached, if it has one
+    pub(crate) fn named_error(&self, error: RustLogicGraphError) -> RustLogicGraphError {
+        match &self.name {
+            Some(name) => error.with_graph(name),
+            None => error,
+        }
+    }
+
+    /// Add a named rule that edges can reference
+    pub fn with_rule(mut self, name: impl Into<String>, rule: RuleDef) -> Self {
+        self.rules.insert(name.into(), rule);
+        self
+    }
+
     /// Create a GraphDef from simple node types (backward compatibility helper)
     pub fn from_node_types(nodes: HashMap<String, NodeType>, edges: Vec<Edge>) -> Self {
         let nodes = nodes
@@ -121,40 +555,164 @@ impl GraphDef {
                     NodeType::TryCatchNode => NodeConfig::rule_node("true"),
                     NodeType::RetryNode => NodeConfig::rule_node("true"),
                     NodeType::CircuitBreakerNode => NodeConfig::rule_node("true"),
+                    // There is no table to default to; building the node fails
+                    NodeType::DecisionTableNode => NodeConfig {
+                        node_type: NodeType::DecisionTableNode,
+                        ..Default::default()
+                    },
+                    NodeType::Custom(name) => NodeConfig {
+                        node_type: NodeType::Custom(name),
+                        ..Default::default()
+                    },
                 };
                 (id, config)
             })
             .collect();
 
-        Self { nodes, edges }
+        Self::new(nodes, edges)
     }
 
     /// Validate graph structure
-    pub fn validate(&self) -> anyhow::Result<()> {
+    pub fn validate(&self) -> Result<()> {
+        self.validate_structure()
+            .and_then(|()| crate::core::condition::EdgeConditions::compile(self).map(drop))
+            .map_err(|error| self.named_error(error))
+    }
+
+    /// Everything [`GraphDef::validate`] checks except that edge conditions
+    /// compile, for callers that compile them anyway
+    pub(crate) fn validate_structure(&self) -> Result<()> {
         // Check for empty graph
         if self.nodes.is_empty() {
-            return Err(anyhow::anyhow!("Graph has no nodes"));
+            return Err(RustLogicGraphError::graph_validation_error(
+                "Graph has no nodes",
+            ));
         }
 
         // Check for invalid edge references
         for edge in &self.edges {
             if !self.nodes.contains_key(&edge.from) {
-                return Err(anyhow::anyhow!(
+                return Err(RustLogicGraphError::graph_validation_error(format!(
                     "Edge references non-existent source node: '{}'",
                     edge.from
-                ));
+                )));
             }
             if !self.nodes.contains_key(&edge.to) {
-                return Err(anyhow::anyhow!(
+                return Err(RustLogicGraphError::graph_validation_error(format!(
                     "Edge references non-existent target node: '{}'",
                     edge.to
-                ));
+                )));
             }
         }
 
+        if let Some((id, _)) = self
+            .nodes
+            .iter()
+            .find(|(_, config)| config.timeout_ms == Some(0))
+        {
+            return Err(RustLogicGraphError::configuration_error(format!(
+                "Node '{}' has a timeout_ms of 0; omit it to run without a timeout",
+                id
+            ))
+            .with_node(id));
+        }
+
+        self.validate_inputs()?;
+        self.validate_outputs()?;
+
         Ok(())
     }
 
+    /// Check that every graph output reading from a node reads from an
+    /// existing node, and only from an output that node declares
+    fn validate_outputs(&self) -> Result<()> {
+        for (name, source) in &self.outputs {
+            let InputSource::Node { node, output } = source else {
+                continue;
+            };
+
+            let Some(config) = self.nodes.get(node) else {
+                return Err(RustLogicGraphError::graph_validation_error(format!(
+                    "Graph output '{}' reads from non-existent node '{}'",
+                    name, node
+                )));
+            };
+
+            if let Some(output) = output {
+                if !config.outputs.contains(output) {
+                    return Err(RustLogicGraphError::graph_validation_error(format!(
+                        "Graph output '{}' reads output '{}', which node '{}' does not declare",
+                        name, output, node
+                    ))
+                    .with_node(node));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every node input reading from a node reads from one of its
+    /// upstream nodes, and only from outputs that node declares
+    fn validate_inputs(&self) -> Result<()> {
+        for (id, config) in &self.nodes {
+            let mut upstream: Option<HashSet<&str>> = None;
+
+            for (port, source) in &config.inputs {
+                let InputSource::Node { node, output } = source else {
+                    continue;
+                };
+
+                let Some(source_config) = self.nodes.get(node) else {
+                    return Err(RustLogicGraphError::graph_validation_error(format!(
+                        "Input '{}' of node '{}' reads from non-existent node '{}'",
+                        port, id, node
+                    ))
+                    .with_node(id));
+                };
+
+                let upstream = upstream.get_or_insert_with(|| self.upstream_of(id));
+                if !upstream.contains(node.as_str()) {
+                    return Err(RustLogicGraphError::graph_validation_error(format!(
+                        "Input '{}' of node '{}' reads from node '{}', which is not upstream of it",
+                        port, id, node
+                    ))
+                    .with_node(id));
+                }
+
+                if let Some(output) = output {
+                    if !source_config.outputs.contains(output) {
+                        return Err(RustLogicGraphError::graph_validation_error(format!(
+                            "Input '{}' of node '{}' reads output '{}', which node '{}' does not declare",
+                            port,
+                            id,
+                            output,
+                            node
+                        )).with_node(id));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ids of all nodes with a path of edges to `node_id`
+    fn upstream_of(&self, node_id: &str) -> HashSet<&str> {
+        let mut upstream = HashSet::new();
+        let mut stack = vec![node_id];
+
+        while let Some(node) = stack.pop() {
+            for edge in self.edges.iter().filter(|e| e.to == node) {
+                if upstream.insert(edge.from.as_str()) {
+                    stack.push(edge.from.as_str());
+                }
+            }
+        }
+
+        upstream
+    }
+
     /// Check if graph has disconnected components
     pub fn has_disconnected_components(&self) -> bool {
         if self.nodes.is_empty() {
@@ -192,7 +750,7 @@ impl GraphDef {
     }
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Context {
     pub data: HashMap<String, serde_json::Value>,
 }
@@ -209,8 +767,20 @@ impl Context {
         self.data.insert(key.into(), value);
     }
 
+    /// Get a value by key, or by a path into nested values such as
+    /// `inventory_result.items[0].qty` (see [`crate::core::path`])
     pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
-        self.data.get(key)
+        crate::core::path::get(&self.data, key)
+    }
+
+    /// Set a value at a path such as `order.items[0].sku`, creating
+    /// intermediate objects and arrays as needed (see [`crate::core::path::set`])
+    pub fn set_path(
+        &mut self,
+        path: &str,
+        value: serde_json::Value,
+    ) -> std::result::Result<(), crate::core::path::PathError> {
+        crate::core::path::set(&mut self.data, path, value)
     }
 
     /// Check if key exists in context
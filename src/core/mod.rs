@@ -1,5 +1,22 @@
+pub mod checkpoint;
+pub mod condition;
+pub mod events;
 pub mod executor;
 pub mod graph;
+pub mod path;
+pub mod plan;
+pub mod recording;
+pub mod result;
+pub mod telemetry;
 
-pub use executor::{ExecutionMetrics, Executor, NodeExecutionStats};
-pub use graph::{Context, Edge, Graph, GraphDef, NodeConfig};
+pub use checkpoint::Checkpoint;
+pub use condition::{EdgeCondition, EdgeConditions};
+pub use events::{AsyncExecutionListener, ExecutionEvent, ExecutionListener};
+pub use executor::{ExecutionMetrics, ExecutionScope, Executor, NodeExecutionStats};
+pub use graph::{Context, Edge, FailurePolicy, Graph, GraphDef, InputSource, NodeConfig, RuleDef};
+pub use path::PathError;
+pub use plan::ExecutionPlan;
+pub use recording::{EdgeDecision, ExecutionRecording, NodeRecord, Replay};
+pub use result::{ExecutionResult, ExecutionStatus, NodeStatus};
+pub use telemetry::TraceContext;
+pub use tokio_util::sync::CancellationToken;
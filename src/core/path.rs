@@ -0,0 +1,215 @@
+//! Dotted paths into nested context values, such as `order.items[0].sku`.
+//!
+//! A path starts with a top-level context key, followed by `.field` and
+//! `[index]` segments. A top-level key that itself contains dots or brackets
+//! always wins over path resolution, so existing flat keys keep working.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PathError {
+    #[error("Invalid path '{0}'")]
+    Invalid(String),
+
+    #[error("Cannot write path '{path}': '{at}' is not {expected}")]
+    Conflict {
+        path: String,
+        at: String,
+        expected: &'static str,
+    },
+
+    #[error("Cannot write path '{path}': index {index} is past the end of '{at}' (length {len})")]
+    OutOfRange {
+        path: String,
+        at: String,
+        index: usize,
+        len: usize,
+    },
+}
+
+/// One step of a parsed path
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Split `path` into segments; the first segment is always a key
+pub fn parse(path: &str) -> Result<Vec<PathSegment>, PathError> {
+    let invalid = || PathError::Invalid(path.to_string());
+    let mut segments = Vec::new();
+
+    for (i, part) in path.split('.').enumerate() {
+        let (key, mut rest) = match part.find('[') {
+            Some(pos) => part.split_at(pos),
+            None => (part, ""),
+        };
+        if key.is_empty() && (i == 0 || rest.is_empty()) {
+            return Err(invalid());
+        }
+        if !key.is_empty() {
+            if key.contains(']') {
+                return Err(invalid());
+            }
+            segments.push(PathSegment::Key(key.to_string()));
+        }
+
+        while !rest.is_empty() {
+            let close = rest.find(']').ok_or_else(invalid)?;
+            let index = rest[1..close].parse().map_err(|_| invalid())?;
+            segments.push(PathSegment::Index(index));
+            rest = &rest[close + 1..];
+            if !rest.is_empty() && !rest.starts_with('[') {
+                return Err(invalid());
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Read `path` from `data`; exact top-level keys take precedence
+pub fn get<'a>(data: &'a HashMap<String, Value>, path: &str) -> Option<&'a Value> {
+    if let Some(value) = data.get(path) {
+        return Some(value);
+    }
+
+    let segments = parse(path).ok()?;
+    let (PathSegment::Key(root), rest) = segments.split_first()? else {
+        return None;
+    };
+    rest.iter()
+        .try_fold(data.get(root)?, |value, segment| match segment {
+            PathSegment::Key(key) => value.get(key),
+            PathSegment::Index(index) => value.get(index),
+        })
+}
+
+/// Write `value` at `path` in `data`, creating intermediate objects and
+/// arrays as needed. An index may replace an item or append one right after
+/// the last; indexes further out are an error rather than padding.
+pub fn set(data: &mut HashMap<String, Value>, path: &str, value: Value) -> Result<(), PathError> {
+    let segments = parse(path)?;
+    let Some((PathSegment::Key(root), rest)) = segments.split_first() else {
+        return Err(PathError::Invalid(path.to_string()));
+    };
+
+    let mut target = data.entry(root.clone()).or_insert(Value::Null);
+    let mut walked = root.clone();
+    for segment in rest {
+        target = match segment {
+            PathSegment::Key(key) => {
+                if target.is_null() {
+                    *target = Value::Object(Default::default());
+                }
+                let Value::Object(map) = target else {
+                    return Err(conflict(path, &walked, "an object"));
+                };
+                walked = format!("{}.{}", walked, key);
+                map.entry(key.clone()).or_insert(Value::Null)
+            }
+            PathSegment::Index(index) => {
+                if target.is_null() {
+                    *target = Value::Array(Vec::new());
+                }
+                let Value::Array(items) = target else {
+                    return Err(conflict(path, &walked, "an array"));
+                };
+                if *index > items.len() {
+                    return Err(PathError::OutOfRange {
+                        path: path.to_string(),
+                        at: walked,
+                        index: *index,
+                        len: items.len(),
+                    });
+                }
+                if *index == items.len() {
+                    items.push(Value::Null);
+                }
+                walked = format!("{}[{}]", walked, index);
+                &mut items[*index]
+            }
+        };
+    }
+
+    *target = value;
+    Ok(())
+}
+
+fn conflict(path: &str, at: &str, expected: &'static str) -> PathError {
+    PathError::Conflict {
+        path: path.to_string(),
+        at: at.to_string(),
+        expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("a.b[0][2].c").unwrap(),
+            vec![
+                PathSegment::Key("a".into()),
+                PathSegment::Key("b".into()),
+                PathSegment::Index(0),
+                PathSegment::Index(2),
+                PathSegment::Key("c".into()),
+            ]
+        );
+        for bad in ["", ".a", "a.", "a..b", "a[x]", "a[0", "a[0]b", "[0]"] {
+            assert!(parse(bad).is_err(), "{} should be invalid", bad);
+        }
+    }
+
+    #[test]
+    fn test_get_nested_and_exact_keys() {
+        let data = HashMap::from([
+            (
+                "inventory_result".to_string(),
+                json!({"items": [{"qty": 3}, {"qty": 7}]}),
+            ),
+            ("a.b".to_string(), json!("flat")),
+        ]);
+
+        assert_eq!(get(&data, "inventory_result.items[1].qty"), Some(&json!(7)));
+        assert_eq!(get(&data, "inventory_result.items[5].qty"), None);
+        assert_eq!(get(&data, "a.b"), Some(&json!("flat")));
+    }
+
+    #[test]
+    fn test_set_creates_intermediate_values() {
+        let mut data = HashMap::new();
+        set(&mut data, "order.items[0].sku", json!("X")).unwrap();
+        set(&mut data, "order.items[1]", json!("Y")).unwrap();
+
+        assert_eq!(data["order"], json!({"items": [{"sku": "X"}, "Y"]}));
+
+        let err = set(&mut data, "order.items.sku", json!(1)).unwrap_err();
+        assert!(matches!(err, PathError::Conflict { .. }));
+    }
+
+    #[test]
+    fn test_set_rejects_index_past_the_end() {
+        let mut data = HashMap::from([("order".to_string(), json!({"items": ["a"]}))]);
+
+        let err = set(&mut data, "order.items[18446744073709551615]", json!(1)).unwrap_err();
+        assert_eq!(
+            err,
+            PathError::OutOfRange {
+                path: "order.items[18446744073709551615]".to_string(),
+                at: "order.items".to_string(),
+                index: usize::MAX,
+                len: 1,
+            }
+        );
+        assert!(set(&mut data, "order.items[2]", json!(1)).is_err());
+        assert_eq!(data["order"], json!({"items": ["a"]}));
+    }
+}
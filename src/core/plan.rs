@@ -0,0 +1,479 @@
+//! Execution plans compiled once from a [`GraphDef`].
+//!
+//! Compiling validates the graph, rejects cycles, resolves edge conditions
+//! and groups nodes into layers, so an [`Executor`](crate::core::Executor)
+//! running the same graph many times only walks precomputed indices.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::core::{Context, EdgeConditions, GraphDef, InputSource};
+use crate::error::{Result, RustLogicGraphError};
+
+/// A node of the plan, with the edges touching it
+#[derive(Debug)]
+struct PlanNode {
+    id: String,
+    result_key: String,
+    incoming: Vec<usize>,
+    outgoing: Vec<usize>,
+    inputs: Vec<PortBinding>,
+    outputs: Vec<String>,
+}
+
+/// An input port resolved to the context entry it reads
+#[derive(Debug)]
+pub(crate) struct PortBinding {
+    pub port: String,
+    key: String,
+    field: Option<String>,
+}
+
+impl PortBinding {
+    fn new(port: &str, source: &InputSource) -> Self {
+        let (key, field) = match source {
+            InputSource::Node { node, output } => (format!("{}_result", node), output.clone()),
+            InputSource::Context { context } => (context.clone(), None),
+        };
+        Self {
+            port: port.to_string(),
+            key,
+            field,
+        }
+    }
+
+    /// Value of the port in `ctx`, if it has been produced
+    pub fn resolve<'c>(&self, ctx: &'c Context) -> Option<&'c serde_json::Value> {
+        let value = ctx.get(&self.key)?;
+        match &self.field {
+            Some(field) => value.get(field),
+            None => Some(value),
+        }
+    }
+
+    /// Human-readable description of the port's source
+    pub fn source(&self) -> String {
+        match &self.field {
+            Some(field) => format!("{}.{}", self.key, field),
+            None => self.key.clone(),
+        }
+    }
+}
+
+/// Immutable, precomputed schedule for running a graph.
+///
+/// Nodes are grouped into layers: every node of a layer depends only on nodes
+/// of earlier layers, so a layer can run concurrently. Within a layer, nodes
+/// are ordered by id. Nodes wrapped by another node (e.g. a `RetryNode`
+/// target) are left out, since their wrapper runs them.
+///
+/// Plans are built with [`Executor::compile`](crate::core::Executor::compile)
+/// and can be shared across threads and reused for any number of runs.
+#[derive(Debug)]
+pub struct ExecutionPlan {
+    def: GraphDef,
+    nodes: Vec<PlanNode>,
+    index: HashMap<String, usize>,
+    edge_sources: Vec<usize>,
+    edge_targets: Vec<usize>,
+    layers: Vec<Vec<usize>>,
+    conditions: EdgeConditions,
+    /// The graph's declared outputs, in name order
+    graph_outputs: Vec<PortBinding>,
+    /// `timeout_ms` of the nodes that have one, shared with every run
+    timeouts: Arc<HashMap<String, Duration>>,
+    /// Whether the failure of some node aborts the run
+    has_required_nodes: bool,
+    /// Per node, a bitset of the nodes a path of edges leads to
+    reachable: Vec<Vec<u64>>,
+}
+
+impl ExecutionPlan {
+    /// Compile `def`, leaving the `wrapped` nodes out of the schedule
+    pub(crate) fn new(def: &GraphDef, wrapped: &HashSet<String>) -> Result<Self> {
+        Self::build(def, wrapped).map_err(|error| def.named_error(error))
+    }
+
+    fn build(def: &GraphDef, wrapped: &HashSet<String>) -> Result<Self> {
+        // Conditions are compiled below, along with the schedule
+        def.validate_structure()?;
+
+        if def.has_disconnected_components() {
+            warn!("Graph has disconnected components - some nodes may not be reachable");
+        }
+
+        detect_cycles(def)?;
+
+        if let Some(edge) = def
+            .edges
+            .iter()
+            .find(|e| wrapped.contains(&e.from) || wrapped.contains(&e.to))
+        {
+            return Err(RustLogicGraphError::graph_validation_error(format!(
+                "Edge {} -> {} references a node that is wrapped by another node; \
+                wrapped nodes are run by their wrapper and cannot have edges",
+                edge.from, edge.to
+            )));
+        }
+
+        let conditions = EdgeConditions::compile(def)?;
+
+        let mut ids: Vec<&String> = def.nodes.keys().collect();
+        ids.sort();
+        let index: HashMap<String, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.to_string(), i))
+            .collect();
+        let mut nodes: Vec<PlanNode> = ids
+            .iter()
+            .map(|id| {
+                let config = &def.nodes[*id];
+                let mut inputs: Vec<PortBinding> = config
+                    .inputs
+                    .iter()
+                    .map(|(port, source)| PortBinding::new(port, source))
+                    .collect();
+                inputs.sort_by(|a, b| a.port.cmp(&b.port));
+
+                PlanNode {
+                    id: id.to_string(),
+                    result_key: format!("{}_result", id),
+                    incoming: Vec::new(),
+                    outgoing: Vec::new(),
+                    inputs,
+                    outputs: config.outputs.clone(),
+                }
+            })
+            .collect();
+
+        let mut edge_sources = Vec::with_capacity(def.edges.len());
+        let mut edge_targets = Vec::with_capacity(def.edges.len());
+        for (edge_index, edge) in def.edges.iter().enumerate() {
+            let from = index[&edge.from];
+            let to = index[&edge.to];
+            nodes[from].outgoing.push(edge_index);
+            nodes[to].incoming.push(edge_index);
+            edge_sources.push(from);
+            edge_targets.push(to);
+        }
+
+        // A node's layer is the length of the longest path reaching it
+        let mut in_degree: Vec<usize> = nodes.iter().map(|n| n.incoming.len()).collect();
+        let mut layer_of = vec![0; nodes.len()];
+        let mut queue: Vec<usize> = (0..nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(node) = queue.pop() {
+            order.push(node);
+            for &edge_index in &nodes[node].outgoing {
+                let to = edge_targets[edge_index];
+                layer_of[to] = layer_of[to].max(layer_of[node] + 1);
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    queue.push(to);
+                }
+            }
+        }
+
+        // Successors come before their predecessors in reverse topological
+        // order, so each node's set is complete when a predecessor reads it
+        let words = nodes.len().div_ceil(64);
+        let mut reachable = vec![vec![0u64; words]; nodes.len()];
+        for &node in order.iter().rev() {
+            let mut reach = vec![0u64; words];
+            for &edge_index in &nodes[node].outgoing {
+                let to = edge_targets[edge_index];
+                reach[to / 64] |= 1 << (to % 64);
+                for (word, successor) in reach.iter_mut().zip(&reachable[to]) {
+                    *word |= successor;
+                }
+            }
+            reachable[node] = reach;
+        }
+
+        let mut layers: Vec<Vec<usize>> = Vec::new();
+        for (node, &layer) in layer_of.iter().enumerate() {
+            if wrapped.contains(&nodes[node].id) {
+                continue;
+            }
+            if layers.len() <= layer {
+                layers.resize_with(layer + 1, Vec::new);
+            }
+            layers[layer].push(node);
+        }
+
+        let mut graph_outputs: Vec<PortBinding> = def
+            .outputs
+            .iter()
+            .map(|(name, source)| PortBinding::new(name, source))
+            .collect();
+        graph_outputs.sort_by(|a, b| a.port.cmp(&b.port));
+
+        let timeouts = def
+            .nodes
+            .iter()
+            .filter_map(|(id, config)| {
+                Some((id.clone(), Duration::from_millis(config.timeout_ms?)))
+            })
+            .collect();
+
+        Ok(Self {
+            def: def.clone(),
+            nodes,
+            index,
+            edge_sources,
+            edge_targets,
+            layers,
+            conditions,
+            graph_outputs,
+            timeouts: Arc::new(timeouts),
+            has_required_nodes: def.nodes.keys().any(|id| def.is_required(id)),
+            reachable,
+        })
+    }
+
+    /// The graph definition this plan was compiled from
+    pub fn def(&self) -> &GraphDef {
+        &self.def
+    }
+
+    /// Node ids grouped into layers that can run concurrently
+    pub fn layers(&self) -> Vec<Vec<&str>> {
+        self.layers
+            .iter()
+            .map(|layer| layer.iter().map(|&n| self.nodes[n].id.as_str()).collect())
+            .collect()
+    }
+
+    /// Scheduled node ids in topological order
+    pub fn topological_order(&self) -> impl Iterator<Item = &str> {
+        self.layers
+            .iter()
+            .flatten()
+            .map(|&n| self.nodes[n].id.as_str())
+    }
+
+    /// Indices into `def().edges` of the edges ending at `node_id`
+    pub fn incoming_edges(&self, node_id: &str) -> &[usize] {
+        self.index
+            .get(node_id)
+            .map_or(&[], |&n| self.nodes[n].incoming.as_slice())
+    }
+
+    /// Indices into `def().edges` of the edges starting at `node_id`
+    pub fn outgoing_edges(&self, node_id: &str) -> &[usize] {
+        self.index
+            .get(node_id)
+            .map_or(&[], |&n| self.nodes[n].outgoing.as_slice())
+    }
+
+    /// Compiled edge conditions
+    pub fn conditions(&self) -> &EdgeConditions {
+        &self.conditions
+    }
+
+    pub(crate) fn layer_indices(&self) -> &[Vec<usize>] {
+        &self.layers
+    }
+
+    pub(crate) fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub(crate) fn node_id(&self, node: usize) -> &str {
+        &self.nodes[node].id
+    }
+
+    pub(crate) fn result_key(&self, node: usize) -> &str {
+        &self.nodes[node].result_key
+    }
+
+    pub(crate) fn inputs(&self, node: usize) -> &[PortBinding] {
+        &self.nodes[node].inputs
+    }
+
+    pub(crate) fn outputs(&self, node: usize) -> &[String] {
+        &self.nodes[node].outputs
+    }
+
+    pub(crate) fn graph_outputs(&self) -> &[PortBinding] {
+        &self.graph_outputs
+    }
+
+    pub(crate) fn timeouts(&self) -> &Arc<HashMap<String, Duration>> {
+        &self.timeouts
+    }
+
+    pub(crate) fn has_required_nodes(&self) -> bool {
+        self.has_required_nodes
+    }
+
+    pub(crate) fn incoming(&self, node: usize) -> &[usize] {
+        &self.nodes[node].incoming
+    }
+
+    pub(crate) fn edge_source(&self, edge_index: usize) -> usize {
+        self.edge_sources[edge_index]
+    }
+
+    /// Nodes at the end of the edges starting at `node`
+    pub(crate) fn successors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes[node]
+            .outgoing
+            .iter()
+            .map(|&edge_index| self.edge_targets[edge_index])
+    }
+
+    /// Whether a path of edges leads from `from` to `to`
+    pub(crate) fn reaches(&self, from: usize, to: usize) -> bool {
+        self.reachable[from][to / 64] & (1 << (to % 64)) != 0
+    }
+}
+
+/// Detect cycles in the graph using DFS
+fn detect_cycles(def: &GraphDef) -> Result<()> {
+    let mut visited = HashSet::new();
+    let mut rec_stack = HashSet::new();
+
+    // Build adjacency list for cycle detection
+    let mut adj_list: HashMap<String, Vec<String>> = HashMap::new();
+    for edge in &def.edges {
+        adj_list
+            .entry(edge.from.clone())
+            .or_default()
+            .push(edge.to.clone());
+    }
+
+    // DFS to detect cycles
+    fn dfs_cycle_check(
+        node: &str,
+        adj_list: &HashMap<String, Vec<String>>,
+        visited: &mut HashSet<String>,
+        rec_stack: &mut HashSet<String>,
+        path: &mut Vec<String>,
+    ) -> Option<Vec<String>> {
+        visited.insert(node.to_string());
+        rec_stack.insert(node.to_string());
+        path.push(node.to_string());
+
+        if let Some(neighbors) = adj_list.get(node) {
+            for neighbor in neighbors {
+                if !visited.contains(neighbor) {
+                    if let Some(cycle) =
+                        dfs_cycle_check(neighbor, adj_list, visited, rec_stack, path)
+                    {
+                        return Some(cycle);
+                    }
+                } else if rec_stack.contains(neighbor) {
+                    // Found a cycle - return the cycle path
+                    let cycle_start = path.iter().position(|n| n == neighbor).unwrap();
+                    return Some(path[cycle_start..].to_vec());
+                }
+            }
+        }
+
+        path.pop();
+        rec_stack.remove(node);
+        None
+    }
+
+    // Check all nodes
+    for node_id in def.nodes.keys() {
+        if !visited.contains(node_id) {
+            let mut path = Vec::new();
+            if let Some(cycle) =
+                dfs_cycle_check(node_id, &adj_list, &mut visited, &mut rec_stack, &mut path)
+            {
+                return Err(RustLogicGraphError::graph_validation_error(format!(
+                    "Cycle detected in graph: {} -> {}",
+                    cycle.join(" -> "),
+                    cycle.first().unwrap()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Edge, NodeConfig};
+
+    fn def(ids: &[&str], edges: Vec<Edge>) -> GraphDef {
+        let nodes = ids
+            .iter()
+            .map(|id| (id.to_string(), NodeConfig::rule_node("true")))
+            .collect();
+        GraphDef::new(nodes, edges)
+    }
+
+    #[test]
+    fn test_layers_follow_longest_path() {
+        // a -> b -> d, a -> d, c
+        let def = def(
+            &["a", "b", "c", "d"],
+            vec![
+                Edge::new("a", "b"),
+                Edge::new("b", "d"),
+                Edge::new("a", "d"),
+            ],
+        );
+        let plan = ExecutionPlan::new(&def, &HashSet::new()).unwrap();
+
+        assert_eq!(plan.layers(), vec![vec!["a", "c"], vec!["b"], vec!["d"]]);
+        assert_eq!(
+            plan.topological_order().collect::<Vec<_>>(),
+            ["a", "c", "b", "d"]
+        );
+        assert_eq!(plan.incoming_edges("d"), &[1, 2]);
+        assert_eq!(plan.outgoing_edges("a"), &[0, 2]);
+        assert!(plan.reaches(0, 3));
+        assert!(!plan.reaches(3, 0));
+        assert!(!plan.reaches(2, 3));
+    }
+
+    #[test]
+    fn test_reachability_spans_bitset_words() {
+        // n00 -> n01 -> ... -> n69
+        let ids: Vec<String> = (0..70).map(|i| format!("n{:02}", i)).collect();
+        let edges = ids.windows(2).map(|w| Edge::new(&w[0], &w[1])).collect();
+        let def = def(&ids.iter().map(String::as_str).collect::<Vec<_>>(), edges);
+        let plan = ExecutionPlan::new(&def, &HashSet::new()).unwrap();
+
+        assert!(plan.reaches(0, 69));
+        assert!(plan.reaches(63, 64));
+        assert!(!plan.reaches(69, 0));
+        assert!(!plan.reaches(5, 5));
+    }
+
+    #[test]
+    fn test_node_timeouts_resolved_at_compile_time() {
+        let mut def = def(&["slow", "fast"], vec![]);
+        def.nodes.get_mut("slow").unwrap().timeout_ms = Some(250);
+        let plan = ExecutionPlan::new(&def, &HashSet::new()).unwrap();
+
+        assert_eq!(plan.timeouts().len(), 1);
+        assert_eq!(plan.timeouts()["slow"], Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_wrapped_nodes_are_not_scheduled() {
+        let def = def(&["retry", "target"], vec![]);
+        let wrapped = HashSet::from(["target".to_string()]);
+        let plan = ExecutionPlan::new(&def, &wrapped).unwrap();
+
+        assert_eq!(plan.layers(), vec![vec!["retry"]]);
+    }
+
+    #[test]
+    fn test_cycle_rejected() {
+        let def = def(&["a", "b"], vec![Edge::new("a", "b"), Edge::new("b", "a")]);
+        let err = ExecutionPlan::new(&def, &HashSet::new()).unwrap_err();
+
+        assert!(err.to_string().contains("Cycle detected"));
+    }
+}
@@ -0,0 +1,347 @@
+//! Execution recordings and deterministic replay.
+//!
+//! With [`Executor::set_recording`] enabled, every run produces an
+//! [`ExecutionRecording`]: the initial context, every node invocation with the
+//! context it saw, its input port values, the context entries it changed, its
+//! result or error and its timing, plus the decision taken on every evaluated
+//! edge. Recordings serialize to JSON or MessagePack.
+//!
+//! Feeding a recording back with [`Executor::set_replay`] re-runs the graph
+//! with nodes that talk to external systems (DB, AI and gRPC nodes, subgraphs,
+//! plus any node added with [`Replay::node`]) answered from the recording
+//! instead of being run. Every other node runs for real, so a change to rules
+//! or graph wiring can be checked against production inputs.
+//!
+//! ```no_run
+//! use rust_logic_graph::core::{ExecutionRecording, Executor, Graph, GraphDef};
+//!
+//! # async fn example(def: GraphDef) -> anyhow::Result<()> {
+//! let mut executor = Executor::from_graph_def(&def)?;
+//! executor.set_recording(true);
+//! executor.execute(&mut Graph::new(def.clone())).await?;
+//! executor.last_recording().unwrap().save("run.msgpack")?;
+//!
+//! let mut replayer = Executor::from_graph_def(&def)?;
+//! replayer.set_replay(ExecutionRecording::load("run.msgpack")?);
+//! replayer.execute(&mut Graph::new(def)).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The children of a SubgraphNode are not recorded one by one: the subgraph
+//! is recorded as a single node, and replaying it applies its recorded
+//! outputs without running the nested graph, so external calls made inside
+//! it are not repeated.
+//!
+//! [`Executor::set_recording`]: crate::core::Executor::set_recording
+//! [`Executor::set_replay`]: crate::core::Executor::set_replay
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::core::Context;
+use crate::error::{Result, RustLogicGraphError};
+use crate::node::NodeType;
+use crate::rule::{RuleError, RuleResult};
+
+/// Everything that happened during one run of a graph
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionRecording {
+    /// Start of the run (Unix timestamp in milliseconds)
+    pub started_at: u64,
+    pub initial_context: HashMap<String, Value>,
+    /// Node invocations in completion order; a wrapper is listed after the
+    /// nodes it invoked
+    pub nodes: Vec<NodeRecord>,
+    /// Edges whose condition was evaluated, in evaluation order
+    pub edges: Vec<EdgeDecision>,
+    /// Nodes skipped because none of their incoming edges were taken
+    pub skipped: Vec<String>,
+    pub final_context: HashMap<String, Value>,
+    pub total_duration: Duration,
+}
+
+/// One invocation of a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeRecord {
+    pub node_id: String,
+    pub node_type: NodeType,
+    /// Context the node was given, without its input ports
+    pub context: HashMap<String, Value>,
+    /// Values bound to the node's input ports
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub inputs: HashMap<String, Value>,
+    /// Context entries the node added or changed
+    pub output: HashMap<String, Value>,
+    /// Context entries the node removed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+    /// Value returned by the node; `None` when it failed
+    pub result: Option<Value>,
+    pub error: Option<RuleError>,
+    pub duration: Duration,
+    pub cache_hit: bool,
+    /// Whether the invocation was itself answered from a replayed recording
+    #[serde(default)]
+    pub replayed: bool,
+}
+
+/// Whether an edge was taken
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeDecision {
+    pub from: String,
+    pub to: String,
+    pub rule: Option<String>,
+    pub taken: bool,
+}
+
+impl ExecutionRecording {
+    /// Invocations of `node_id`, in order
+    pub fn node_records<'a>(&'a self, node_id: &'a str) -> impl Iterator<Item = &'a NodeRecord> {
+        self.nodes
+            .iter()
+            .filter(move |record| record.node_id == node_id)
+    }
+
+    /// Whether the edge `from -> to` was taken
+    pub fn edge_taken(&self, from: &str, to: &str) -> Option<bool> {
+        self.edges
+            .iter()
+            .find(|edge| edge.from == from && edge.to == to)
+            .map(|edge| edge.taken)
+    }
+
+    /// Serialize to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| serialize_error(e, "serialize"))
+    }
+
+    /// Deserialize from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| serialize_error(e, "deserialize"))
+    }
+
+    /// Serialize to MessagePack
+    pub fn to_msgpack(&self) -> Result<Vec<u8>> {
+        rmp_serde::to_vec_named(self).map_err(|e| serialize_error(e, "serialize"))
+    }
+
+    /// Deserialize from MessagePack
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self> {
+        rmp_serde::from_slice(bytes).map_err(|e| serialize_error(e, "deserialize"))
+    }
+
+    /// Write the recording to `path`, as JSON for `.json` files and
+    /// MessagePack otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = if is_json(path) {
+            self.to_json()?.into_bytes()
+        } else {
+            self.to_msgpack()?
+        };
+        std::fs::write(path, bytes).map_err(|e| file_error(e, "write", path))
+    }
+
+    /// Read a recording written by [`ExecutionRecording::save`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| file_error(e, "read", path))?;
+        if is_json(path) {
+            let json =
+                std::str::from_utf8(&bytes).map_err(|e| serialize_error(e, "deserialize"))?;
+            Self::from_json(json)
+        } else {
+            Self::from_msgpack(&bytes)
+        }
+    }
+}
+
+impl NodeRecord {
+    /// The outcome the node produced
+    pub fn outcome(&self) -> RuleResult {
+        match &self.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(self.result.clone().unwrap_or(Value::Null)),
+        }
+    }
+
+    /// Apply the recorded context changes to `ctx`
+    pub fn apply(&self, ctx: &mut Context) {
+        for (key, value) in &self.output {
+            ctx.data.insert(key.clone(), value.clone());
+        }
+        for key in &self.removed {
+            ctx.data.remove(key);
+        }
+    }
+}
+
+/// A recording fed back to an executor, and the nodes answered from it
+#[derive(Debug, Clone)]
+pub struct Replay {
+    recording: ExecutionRecording,
+    nodes: HashSet<String>,
+}
+
+impl Replay {
+    /// Replay the DB, AI, gRPC and subgraph nodes of `recording`
+    pub fn new(recording: ExecutionRecording) -> Self {
+        Self {
+            recording,
+            nodes: HashSet::new(),
+        }
+    }
+
+    /// Also answer `node_id` from the recording, e.g. a custom node that
+    /// calls an external service
+    pub fn node(mut self, node_id: impl Into<String>) -> Self {
+        self.nodes.insert(node_id.into());
+        self
+    }
+
+    pub fn recording(&self) -> &ExecutionRecording {
+        &self.recording
+    }
+
+    /// Whether the node `node_id` of type `node_type` is answered from the
+    /// recording
+    pub fn replays(&self, node_id: &str, node_type: &NodeType) -> bool {
+        matches!(
+            node_type,
+            NodeType::DBNode | NodeType::AINode | NodeType::GrpcNode | NodeType::SubgraphNode
+        ) || self.nodes.contains(node_id)
+    }
+
+    /// Apply the `occurrence`-th recorded invocation of `node_id` to `ctx`,
+    /// returning its recorded outcome
+    pub(crate) fn apply(&self, node_id: &str, occurrence: usize, ctx: &mut Context) -> RuleResult {
+        let record = self
+            .recording
+            .node_records(node_id)
+            .nth(occurrence)
+            .ok_or_else(|| {
+                RuleError::Configuration(format!(
+                    "Recording has no invocation #{} of node '{}' to replay",
+                    occurrence + 1,
+                    node_id
+                ))
+            })?;
+        record.apply(ctx);
+        record.outcome()
+    }
+}
+
+impl From<ExecutionRecording> for Replay {
+    fn from(recording: ExecutionRecording) -> Self {
+        Self::new(recording)
+    }
+}
+
+fn serialize_error(
+    error: impl std::error::Error + Send + Sync + 'static,
+    action: &str,
+) -> RustLogicGraphError {
+    RustLogicGraphError::serialization_error(format!(
+        "Failed to {} execution recording: {}",
+        action, error
+    ))
+    .with_source(error)
+}
+
+fn file_error(error: std::io::Error, action: &str, path: &Path) -> RustLogicGraphError {
+    RustLogicGraphError::configuration_error(format!(
+        "Failed to {} execution recording {}: {}",
+        action,
+        path.display(),
+        error
+    ))
+    .with_source(error)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn recording() -> ExecutionRecording {
+        ExecutionRecording {
+            initial_context: HashMap::from([("product_id".to_string(), json!("P-1"))]),
+            nodes: vec![
+                NodeRecord {
+                    node_id: "fetch".to_string(),
+                    node_type: NodeType::DBNode,
+                    context: HashMap::new(),
+                    inputs: HashMap::new(),
+                    output: HashMap::from([("fetch_result".to_string(), json!({"qty": 3}))]),
+                    removed: vec!["stale".to_string()],
+                    result: Some(json!({"qty": 3})),
+                    error: None,
+                    duration: Duration::from_millis(12),
+                    cache_hit: false,
+                    replayed: false,
+                },
+                NodeRecord {
+                    node_id: "fetch".to_string(),
+                    node_type: NodeType::DBNode,
+                    context: HashMap::new(),
+                    inputs: HashMap::new(),
+                    output: HashMap::new(),
+                    removed: Vec::new(),
+                    result: None,
+                    error: Some(RuleError::Eval("timeout".to_string())),
+                    duration: Duration::from_millis(30),
+                    cache_hit: false,
+                    replayed: false,
+                },
+            ],
+            edges: vec![EdgeDecision {
+                from: "fetch".to_string(),
+                to: "decide".to_string(),
+                rule: Some("qty > 0".to_string()),
+                taken: true,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_json_and_msgpack_round_trip() {
+        let recording = recording();
+        let json = recording.to_json().unwrap();
+        assert_eq!(ExecutionRecording::from_json(&json).unwrap(), recording);
+
+        let bytes = recording.to_msgpack().unwrap();
+        assert_eq!(ExecutionRecording::from_msgpack(&bytes).unwrap(), recording);
+        assert_eq!(recording.edge_taken("fetch", "decide"), Some(true));
+    }
+
+    #[test]
+    fn test_replay_applies_invocations_in_order() {
+        let replay = Replay::new(recording());
+        assert!(replay.replays("fetch", &NodeType::DBNode));
+        assert!(!replay.replays("decide", &NodeType::RuleNode));
+
+        let mut ctx = Context::new();
+        ctx.data.insert("stale".to_string(), json!(true));
+        assert_eq!(
+            replay.apply("fetch", 0, &mut ctx).unwrap(),
+            json!({"qty": 3})
+        );
+        assert_eq!(ctx.data.get("fetch_result"), Some(&json!({"qty": 3})));
+        assert!(!ctx.data.contains_key("stale"));
+
+        assert!(matches!(
+            replay.apply("fetch", 1, &mut ctx),
+            Err(RuleError::Eval(message)) if message == "timeout"
+        ));
+        assert!(replay.apply("fetch", 2, &mut ctx).is_err());
+    }
+}
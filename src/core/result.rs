@@ -0,0 +1,118 @@
+//! Structured results of graph executions.
+//!
+//! Every successful run of an [`Executor`](crate::core::Executor) returns an
+//! [`ExecutionResult`]: whether any node failed along the way, the values of
+//! the outputs the graph declares, what happened to each node, the order the
+//! nodes ran in and the run's metrics. It serializes to JSON, so a service can
+//! return it from a request handler as-is.
+//!
+//! ```no_run
+//! use rust_logic_graph::core::{Executor, Graph, GraphDef, InputSource};
+//!
+//! # async fn example(def: GraphDef) -> rust_logic_graph::RLGResult<()> {
+//! let def = def.with_output("total", InputSource::node_output("pricing", "total"));
+//! let executor = Executor::from_graph_def(&def)?;
+//! let result = executor.execute(&mut Graph::new(def)).await?;
+//!
+//! println!("total = {:?}", result.output("total"));
+//! println!("{}", serde_json::to_string(&result).unwrap());
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::core::ExecutionMetrics;
+use crate::rule::RuleError;
+
+/// Overall outcome of a run that finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    /// Every node that ran succeeded
+    Completed,
+    /// Some nodes failed, but the graph's failure policy tolerated them
+    CompletedWithFailures,
+}
+
+impl ExecutionStatus {
+    /// The status as written in JSON, e.g. `"completed"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionStatus::Completed => "completed",
+            ExecutionStatus::CompletedWithFailures => "completed_with_failures",
+        }
+    }
+}
+
+/// What happened to one node during a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum NodeStatus {
+    /// The node ran and succeeded
+    Succeeded,
+    /// The node's result was taken from the cache
+    Cached,
+    /// The node did not run
+    Skipped { reason: String },
+    /// The node failed and has no result
+    Failed { error: RuleError },
+    /// The node failed and the fallback handler supplied its result
+    Fallback { error: RuleError },
+    /// The node had completed in the checkpointed run this one resumed
+    Restored,
+}
+
+impl NodeStatus {
+    /// Error the node failed with, if it failed
+    pub fn error(&self) -> Option<&RuleError> {
+        match self {
+            NodeStatus::Failed { error } | NodeStatus::Fallback { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Result of a graph execution that ran to completion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionResult {
+    pub status: ExecutionStatus,
+    /// Values of the graph's declared outputs, by output name; outputs whose
+    /// source produced no value (e.g. a skipped node) are left out
+    pub outputs: HashMap<String, Value>,
+    /// What happened to every scheduled node, by node id
+    pub nodes: BTreeMap<String, NodeStatus>,
+    /// Ids of the nodes that ran, in the order they finished and their
+    /// results were applied to the context
+    pub execution_order: Vec<String>,
+    pub metrics: ExecutionMetrics,
+    /// Id of the trace the run's spans belong to (see
+    /// [`TraceContext`](crate::core::TraceContext))
+    pub trace_id: String,
+}
+
+impl ExecutionResult {
+    /// Whether every node that ran succeeded
+    pub fn is_success(&self) -> bool {
+        self.status == ExecutionStatus::Completed
+    }
+
+    /// Value of the declared output `name`
+    pub fn output(&self, name: &str) -> Option<&Value> {
+        self.outputs.get(name)
+    }
+
+    /// What happened to node `node_id`
+    pub fn node(&self, node_id: &str) -> Option<&NodeStatus> {
+        self.nodes.get(node_id)
+    }
+
+    /// Nodes that failed, with the error each one failed with
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &RuleError)> {
+        self.nodes
+            .iter()
+            .filter_map(|(node_id, status)| status.error().map(|error| (node_id.as_str(), error)))
+    }
+}
@@ -0,0 +1,140 @@
+//! Trace context for graph executions.
+//!
+//! Every run of an [`Executor`](crate::core::Executor) is wrapped in a
+//! `graph_execution` span of the [`tracing`] crate, with a `node_execution`
+//! child span per node. The spans carry OpenTelemetry-style attributes:
+//!
+//! - graph spans: `graph.name`, `graph.status`
+//! - node spans: `node.id`, `node.type`, `node.cache_hit`, `node.outcome`
+//!   (`succeeded`, `cached`, `failed`, `fallback` or `cancelled`)
+//! - both: `trace_id`, `span_id`, `parent_span_id` and `otel.status_code`
+//!
+//! The ids follow the [W3C Trace Context] format. While a node runs, its
+//! [`TraceContext`] is available from [`TraceContext::current`]; a node
+//! calling another service sends it along, e.g. in a
+//! [`DistributedContext`](crate::distributed::DistributedContext), and the
+//! receiving service runs its graph in [`TraceContext::scope`], so both runs
+//! share one trace id. Subgraphs join their parent's trace automatically.
+//!
+//! ```no_run
+//! use rust_logic_graph::core::{Executor, Graph, TraceContext};
+//! use rust_logic_graph::distributed::DistributedContext;
+//!
+//! # async fn handle(request: DistributedContext, executor: Executor, mut graph: Graph)
+//! # -> rust_logic_graph::RLGResult<()> {
+//! let parent = request.trace_context().unwrap_or_else(TraceContext::new_root);
+//! let result = parent.scope(executor.execute(&mut graph)).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [W3C Trace Context]: https://www.w3.org/TR/trace-context/
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+tokio::task_local! {
+    static CURRENT: TraceContext;
+}
+
+/// Position of a span in a distributed trace: the trace it belongs to and
+/// the span's own id
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TraceContext {
+    trace_id: String,
+    span_id: String,
+}
+
+impl TraceContext {
+    /// A span starting a new trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_hex(16),
+            span_id: random_hex(8),
+        }
+    }
+
+    /// A new span of the same trace, below this one
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: random_hex(8),
+        }
+    }
+
+    /// Id of the trace, 32 lowercase hex digits
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// Id of the span, 16 lowercase hex digits
+    pub fn span_id(&self) -> &str {
+        &self.span_id
+    }
+
+    /// The context as a W3C `traceparent` header value
+    pub fn to_traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id, self.span_id)
+    }
+
+    /// Parse a W3C `traceparent` header value
+    pub fn from_traceparent(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        if version != "00"
+            || flags.len() != 2
+            || !is_id(trace_id, 32)
+            || !is_id(span_id, 16)
+            || parts.next().is_some()
+        {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+        })
+    }
+
+    /// Trace context of the graph or node running on the current task, if
+    /// any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Run `future` with this as the current trace context; graphs executed
+    /// inside it join this trace. Does not carry over to spawned tasks.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+}
+
+/// Whether `id` is `len` lowercase hex digits, not all zero
+fn is_id(id: &str, len: usize) -> bool {
+    id.len() == len
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        && id.bytes().any(|b| b != b'0')
+}
+
+/// `bytes` random bytes as lowercase hex
+fn random_hex(bytes: usize) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hex = String::with_capacity(bytes * 2 + 16);
+    while hex.len() < bytes * 2 {
+        // Randomly keyed hasher, fed a counter so ids never repeat
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hex.push_str(&format!("{:016x}", hasher.finish() | 1));
+    }
+    hex.truncate(bytes * 2);
+    hex
+}
@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::core::TraceContext;
+
 /// A distributed context that can be serialized and shared across services
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DistributedContext {
@@ -39,6 +41,11 @@ pub struct ContextMetadata {
 
     /// Tags for categorization
     pub tags: Vec<String>,
+
+    /// W3C `traceparent` of the span that sent this context, so the
+    /// receiving service can continue the same trace
+    #[serde(default)]
+    pub trace_parent: Option<String>,
 }
 
 impl DistributedContext {
@@ -67,6 +74,7 @@ impl DistributedContext {
                 version: 1,
                 modified_by: None,
                 tags: Vec::new(),
+                trace_parent: None,
             },
         }
     }
@@ -150,6 +158,19 @@ impl DistributedContext {
         }
     }
 
+    /// Propagate `trace` to the service this context is sent to
+    pub fn set_trace_context(&mut self, trace: &TraceContext) {
+        self.metadata.trace_parent = Some(trace.to_traceparent());
+    }
+
+    /// Trace context propagated by the service that sent this context
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        self.metadata
+            .trace_parent
+            .as_deref()
+            .and_then(TraceContext::from_traceparent)
+    }
+
     /// Set the service that modified this context
     pub fn set_modified_by(&mut self, service: impl Into<String>) {
         self.metadata.modified_by = Some(service.into());
@@ -272,6 +293,19 @@ mod tests {
         assert_eq!(ctx1.get("key2"), Some(&json!("value2")));
     }
 
+    #[test]
+    fn test_trace_context_round_trip() {
+        let trace = TraceContext::new_root();
+        let mut ctx = DistributedContext::new("test");
+        assert_eq!(ctx.trace_context(), None);
+
+        ctx.set_trace_context(&trace);
+        let bytes = ctx.serialize().unwrap();
+        let received = DistributedContext::deserialize(&bytes).unwrap();
+
+        assert_eq!(received.trace_context(), Some(trace));
+    }
+
     #[tokio::test]
     async fn test_shared_context() {
         let ctx = SharedContext::new("test");
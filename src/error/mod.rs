@@ -9,6 +9,9 @@
 
 use std::fmt;
 
+use crate::core::executor::ExecutionMetrics;
+use crate::rule::RuleError;
+
 /// Error classification for retry strategies
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorCategory {
@@ -35,6 +38,9 @@ pub struct ErrorContext {
     pub service_name: Option<String>,
     /// Additional context key-value pairs
     pub metadata: Vec<(String, String)>,
+    /// Metrics of the nodes that finished before a graph execution stopped
+    /// (cancelled, timed out or aborted by a failed required node)
+    pub metrics: Option<Box<ExecutionMetrics>>,
 }
 
 impl ErrorContext {
@@ -45,6 +51,7 @@ impl ErrorContext {
             execution_step: None,
             service_name: None,
             metadata: Vec::new(),
+            metrics: None,
         }
     }
 
@@ -72,6 +79,11 @@ impl ErrorContext {
         self.metadata.push((key.into(), value.into()));
         self
     }
+
+    pub fn with_metrics(mut self, metrics: ExecutionMetrics) -> Self {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
 }
 
 impl Default for ErrorContext {
@@ -138,6 +150,32 @@ impl RustLogicGraphError {
         self
     }
 
+    /// Set the node where the error occurred, keeping the rest of the context
+    pub fn with_node(mut self, node_id: impl Into<String>) -> Self {
+        self.context.node_id = Some(node_id.into());
+        self
+    }
+
+    /// Set the graph being executed, keeping the rest of the context
+    pub fn with_graph(mut self, graph_name: impl Into<String>) -> Self {
+        self.context.graph_name = Some(graph_name.into());
+        self
+    }
+
+    /// Metrics of the nodes that finished before the graph execution that
+    /// returned this error stopped
+    pub fn metrics(&self) -> Option<&ExecutionMetrics> {
+        self.context.metrics.as_deref()
+    }
+
+    /// Nodes that failed before the graph execution that returned this error
+    /// stopped, with the error each one failed with
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &RuleError)> {
+        self.metrics()
+            .into_iter()
+            .flat_map(ExecutionMetrics::failures)
+    }
+
     /// Check if error is retryable
     pub fn is_retryable(&self) -> bool {
         matches!(
@@ -284,6 +322,42 @@ impl RustLogicGraphError {
         Self::new("E012", message, ErrorCategory::Transient)
             .with_suggestion("Review transaction logic and compensation handlers. Check for deadlocks or isolation issues.")
     }
+
+    /// Execution cancelled by the caller
+    pub fn cancelled_error(message: impl Into<String>) -> Self {
+        Self::new("E013", message, ErrorCategory::Permanent).with_suggestion(
+            "The caller cancelled the execution. Run it again if its result is still needed.",
+        )
+    }
+
+    /// Check if this error reports a cancelled execution
+    pub fn is_cancelled(&self) -> bool {
+        self.code == "E013"
+    }
+}
+
+/// Classify a node failure: timeouts are transient, cancellations and
+/// expression errors are permanent, configuration errors are configuration
+/// errors, anything else the node reports is a retryable execution error
+impl From<RuleError> for RustLogicGraphError {
+    fn from(error: RuleError) -> Self {
+        let message = error.to_string();
+        match error {
+            RuleError::Timeout(_) => Self::timeout_error(message),
+            RuleError::Cancelled(_) => Self::cancelled_error(message),
+            RuleError::MissingVariable(_) => Self::context_error(message),
+            RuleError::Configuration(_) => Self::configuration_error(message),
+            RuleError::TypeMismatch(_) | RuleError::InvalidExpression(_) => {
+                Self::rule_evaluation_error(message)
+            }
+            // The failed node is not known here; callers attach it with
+            // `with_node`
+            RuleError::Eval(_) => Self {
+                context: ErrorContext::new(),
+                ..Self::node_execution_error("", message)
+            },
+        }
+    }
 }
 
 /// Result type alias for convenience
@@ -346,4 +420,33 @@ mod tests {
 
         assert_eq!(err.context.metadata.len(), 2);
     }
+
+    #[test]
+    fn test_from_rule_error() {
+        let err = RustLogicGraphError::from(RuleError::Timeout("slow".to_string()))
+            .with_node("fetch_orders")
+            .with_graph("orders");
+        assert_eq!(err.code, "E005");
+        assert!(err.is_retryable());
+        assert_eq!(err.context.node_id.as_deref(), Some("fetch_orders"));
+        assert_eq!(err.context.graph_name.as_deref(), Some("orders"));
+
+        let err = RustLogicGraphError::from(RuleError::InvalidExpression("x +".to_string()));
+        assert_eq!(err.code, "E003");
+        assert!(err.is_permanent());
+
+        let err = RustLogicGraphError::from(RuleError::Cancelled("stop".to_string()));
+        assert!(err.is_cancelled());
+
+        let err = RustLogicGraphError::from(RuleError::Eval("refused".to_string()));
+        assert_eq!(err.code, "E001");
+        assert!(err.is_retryable());
+        assert!(err.suggestion.is_some());
+        assert_eq!(err.context.node_id, None);
+
+        let err = RustLogicGraphError::from(RuleError::Configuration("no node".to_string()));
+        assert_eq!(err.code, "E004");
+        assert!(err.is_permanent());
+        assert!(!RuleError::Configuration("no node".to_string()).is_retryable());
+    }
 }
@@ -12,6 +12,17 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+impl CircuitState {
+    /// Short label for the state (`closed`, `open` or `half_open`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open { .. } => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CircuitConfig {
     pub failure_threshold: u32,
@@ -71,15 +82,12 @@ impl CircuitBreaker {
             let mut f = self.failures.write().await;
             *f = 0;
         }
-
-        let state_debug = {
+        {
             let mut s = self.state.write().await;
             *s = CircuitState::Closed;
-            format!("{:?}", *s)
-        };
+        }
 
-        let failures_val = *self.failures.read().await;
-        let _ = self.persist_state_payload(state_debug, failures_val).await;
+        let _ = self.persist_state().await;
     }
 
     pub async fn record_failure(&self) {
@@ -91,13 +99,54 @@ impl CircuitBreaker {
         let failures_val = *self.failures.read().await;
         if failures_val >= self.config.failure_threshold {
             let until = Instant::now() + self.config.recovery_timeout;
-            let state_debug = {
-                let mut s = self.state.write().await;
-                *s = CircuitState::Open { until };
-                format!("{:?}", *s)
-            };
-            let _ = self.persist_state_payload(state_debug, failures_val).await;
+            let mut s = self.state.write().await;
+            *s = CircuitState::Open { until };
         }
+
+        let _ = self.persist_state().await;
+    }
+
+    /// Load the last persisted state for this breaker from its store.
+    ///
+    /// Returns `true` if a persisted state was found. An open circuit whose
+    /// recovery deadline has already passed is restored as half-open.
+    pub async fn restore(&self) -> anyhow::Result<bool> {
+        let ctx = match self.store.load(&self.store_key()).await? {
+            Some(ctx) => ctx,
+            None => return Ok(false),
+        };
+
+        let payload: serde_json::Value = match ctx.get("payload").and_then(|p| p.as_str()) {
+            Some(raw) => serde_json::from_str(raw)?,
+            None => return Ok(false),
+        };
+
+        let failures = payload
+            .get("failures")
+            .and_then(|f| f.as_u64())
+            .unwrap_or(0) as u32;
+        let state = match payload.get("state").and_then(|s| s.as_str()) {
+            Some("open") => {
+                let open_until_ms = payload
+                    .get("open_until_ms")
+                    .and_then(|u| u.as_u64())
+                    .unwrap_or(0);
+                let remaining = open_until_ms.saturating_sub(unix_millis());
+                if remaining > 0 {
+                    CircuitState::Open {
+                        until: Instant::now() + Duration::from_millis(remaining),
+                    }
+                } else {
+                    CircuitState::HalfOpen
+                }
+            }
+            Some("half_open") => CircuitState::HalfOpen,
+            _ => CircuitState::Closed,
+        };
+
+        *self.failures.write().await = failures;
+        *self.state.write().await = state;
+        Ok(true)
     }
 
     pub async fn is_allowed(&self) -> bool {
@@ -107,9 +156,8 @@ impl CircuitBreaker {
             CircuitState::Open { until } => {
                 if Instant::now() >= *until {
                     *s = CircuitState::HalfOpen;
-                    let state_debug = format!("{:?}", *s);
                     let failures_val = *self.failures.read().await;
-                    let _ = self.persist_state_payload(state_debug, failures_val).await;
+                    let _ = self.persist_state_payload(&s, failures_val).await;
                     true
                 } else {
                     false
@@ -162,26 +210,38 @@ impl CircuitBreaker {
         }
     }
 
+    fn store_key(&self) -> String {
+        format!("cb:{}", self.key)
+    }
+
     async fn persist_state_payload(
         &self,
-        state_debug: String,
+        state: &CircuitState,
         failures: u32,
     ) -> anyhow::Result<()> {
         use serde_json::json;
-        let payload = json!({"state": state_debug, "failures": failures, "version": self.config.failure_threshold});
-        let mut ctx = crate::distributed::DistributedContext::new(format!("cb:{}", self.key));
+        let open_until_ms = match state {
+            CircuitState::Open { until } => Some(
+                unix_millis() + until.saturating_duration_since(Instant::now()).as_millis() as u64,
+            ),
+            _ => None,
+        };
+        let payload = json!({
+            "state": state.as_str(),
+            "failures": failures,
+            "open_until_ms": open_until_ms,
+            "version": self.config.failure_threshold
+        });
+        let mut ctx = crate::distributed::DistributedContext::new(self.store_key());
         ctx.set("payload", serde_json::Value::String(payload.to_string()));
         let _ = self.store.save(&ctx, None).await;
         Ok(())
     }
 
     async fn persist_state(&self) -> anyhow::Result<()> {
-        let state_debug = {
-            let s = self.state.read().await;
-            format!("{:?}", *s)
-        };
+        let state = self.state.read().await.clone();
         let failures = *self.failures.read().await;
-        self.persist_state_payload(state_debug, failures).await
+        self.persist_state_payload(&state, failures).await
     }
 
     pub async fn current_state(&self) -> CircuitState {
@@ -189,6 +249,13 @@ impl CircuitBreaker {
     }
 }
 
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +287,24 @@ mod tests {
         cb.record_success().await;
         assert_eq!(cb.current_state().await, CircuitState::Closed);
     }
+
+    #[tokio::test]
+    async fn test_state_restored_from_store() {
+        let store: Arc<dyn ContextStore> = Arc::new(InMemoryStore::new());
+        let config = CircuitConfig {
+            failure_threshold: 2,
+            recovery_timeout: Duration::from_secs(60),
+            probe_interval: Duration::from_secs(5),
+        };
+
+        let cb = CircuitBreaker::new("svc-b", Some(store.clone()), Some(config.clone()));
+        cb.record_failure().await;
+        cb.record_failure().await;
+        assert!(!cb.is_allowed().await);
+
+        // A fresh breaker sharing the store picks up the open circuit
+        let restored = CircuitBreaker::new("svc-b", Some(store), Some(config));
+        assert!(restored.restore().await.unwrap());
+        assert!(!restored.is_allowed().await);
+    }
 }
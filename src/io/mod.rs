@@ -1,43 +1,80 @@
-use anyhow::{Context, Result};
 use serde_json;
 use std::fs;
 use std::path::Path;
 
 use crate::core::GraphDef;
+use crate::error::{Result, RustLogicGraphError};
 
 pub struct GraphIO;
 
 impl GraphIO {
     /// Load a graph definition from a JSON file
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<GraphDef> {
-        let data = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read file: {:?}", path.as_ref()))?;
+        let data = read_file(path.as_ref())?;
 
-        let graph_def: GraphDef =
-            serde_json::from_str(&data).with_context(|| "Failed to parse JSON")?;
+        let graph_def: GraphDef = serde_json::from_str(&data).map_err(|e| {
+            RustLogicGraphError::serialization_error("Failed to parse JSON").with_source(e)
+        })?;
 
         Ok(graph_def)
     }
 
+    /// Load a graph definition from a YAML file
+    pub fn load_from_yaml_file<P: AsRef<Path>>(path: P) -> Result<GraphDef> {
+        let data = read_file(path.as_ref())?;
+
+        Self::from_yaml(&data)
+    }
+
+    /// Load a graph definition from a JSON or YAML file, based on its extension
+    pub fn load_from_any_file<P: AsRef<Path>>(path: P) -> Result<GraphDef> {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::load_from_file(path),
+            _ => Self::load_from_yaml_file(path),
+        }
+    }
+
     /// Save a graph definition to a JSON file
     pub fn save_to_file<P: AsRef<Path>>(graph_def: &GraphDef, path: P) -> Result<()> {
-        let json = serde_json::to_string_pretty(graph_def)
-            .with_context(|| "Failed to serialize graph definition")?;
+        let json = Self::to_json(graph_def)?;
 
-        fs::write(&path, json)
-            .with_context(|| format!("Failed to write file: {:?}", path.as_ref()))?;
+        fs::write(&path, json).map_err(|e| {
+            RustLogicGraphError::configuration_error(format!(
+                "Failed to write file: {:?}",
+                path.as_ref()
+            ))
+            .with_source(e)
+        })?;
 
         Ok(())
     }
 
     /// Load a graph definition from a JSON string
     pub fn from_json(json: &str) -> Result<GraphDef> {
-        serde_json::from_str(json).with_context(|| "Failed to parse JSON string")
+        serde_json::from_str(json).map_err(|e| {
+            RustLogicGraphError::serialization_error("Failed to parse JSON string").with_source(e)
+        })
+    }
+
+    /// Load a graph definition from a YAML string
+    pub fn from_yaml(yaml: &str) -> Result<GraphDef> {
+        serde_yaml::from_str(yaml).map_err(|e| {
+            RustLogicGraphError::serialization_error("Failed to parse YAML string").with_source(e)
+        })
     }
 
     /// Convert a graph definition to JSON string
     pub fn to_json(graph_def: &GraphDef) -> Result<String> {
-        serde_json::to_string_pretty(graph_def)
-            .with_context(|| "Failed to serialize graph definition")
+        serde_json::to_string_pretty(graph_def).map_err(|e| {
+            RustLogicGraphError::serialization_error("Failed to serialize graph definition")
+                .with_source(e)
+        })
     }
 }
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| {
+        RustLogicGraphError::configuration_error(format!("Failed to read file: {:?}", path))
+            .with_source(e)
+    })
+}
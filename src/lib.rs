@@ -18,7 +18,10 @@ pub mod streaming;
 // Re-export main types
 pub use cache::{CacheConfig, CacheManager, EvictionPolicy};
 pub use core::{
-    Context, Edge, ExecutionMetrics, Executor, Graph, GraphDef, NodeConfig, NodeExecutionStats,
+    AsyncExecutionListener, CancellationToken, Context, Edge, ExecutionEvent, ExecutionListener,
+    ExecutionMetrics, ExecutionPlan, ExecutionResult, ExecutionStatus, Executor, FailurePolicy,
+    Graph, GraphDef, InputSource, NodeConfig, NodeExecutionStats, NodeStatus,
+    TraceContext,
 };
 pub use error::{ErrorCategory, ErrorContext, Result as RLGResult, RustLogicGraphError};
 pub use fault_tolerance::{
@@ -32,10 +35,11 @@ pub use multi_db::{
     TransactionCoordinator,
 };
 pub use node::{
-    AINode, DBNode, DatabaseExecutor, GrpcNode, MockDatabaseExecutor, Node, NodeType, RuleNode,
+    AINode, DBNode, DatabaseExecutor, GrpcNode, MockDatabaseExecutor, Node, NodeFactory,
+    NodeRegistry, NodeType, RuleNode,
 };
 pub use orchestrator::Orchestrator;
-pub use rule::{Rule, RuleEngine, RuleError, RuleResult};
+pub use rule::{Rule, RuleEngine, RuleError, RuleResult, RuleSetManager};
 pub use saga::*;
 
 // Re-export rust-rule-engine types for advanced usage
@@ -0,0 +1,447 @@
+//! Node factories and the registry used to build nodes from a [`GraphDef`].
+//!
+//! Every node type is built by a [`NodeFactory`] registered under the node
+//! type's name (see [`NodeType::name`]). The built-in types are registered by
+//! [`NodeRegistry::default`]; applications add their own kinds with
+//! [`NodeRegistry::register`] and reference them from YAML/JSON by name:
+//!
+//! ```no_run
+//! use rust_logic_graph::node::{Node, NodeRegistry, RuleNode};
+//! use rust_logic_graph::{Executor, GraphIO, RustLogicGraphError};
+//! use std::sync::Arc;
+//!
+//! # fn example() -> rust_logic_graph::RLGResult<()> {
+//! let mut registry = NodeRegistry::default();
+//! registry.register("HttpNode", |id: &str, config: &rust_logic_graph::NodeConfig| {
+//!     let url = config
+//!         .settings
+//!         .as_ref()
+//!         .and_then(|s| s.get("url"))
+//!         .and_then(|u| u.as_str())
+//!         .ok_or_else(|| {
+//!             RustLogicGraphError::configuration_error(format!(
+//!                 "HttpNode '{}' requires settings.url",
+//!                 id
+//!             ))
+//!         })?;
+//!     // Build your own node type here
+//!     Ok(Box::new(RuleNode::new(id, url)) as Box<dyn Node>)
+//! });
+//!
+//! let def = GraphIO::load_from_yaml_file("workflow.yaml")?;
+//! let executor = Executor::from_graph_def_with_registry(&def, Arc::new(registry))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`GraphDef`]: crate::core::GraphDef
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{
+    AINode, CircuitBreakerNode, ConditionalNode, DBNode, DecisionTableNode, GrpcNode, LoopNode,
+    Node, NodeType, RetryNode, RuleNode, SubgraphNode, TryCatchNode,
+};
+use crate::core::NodeConfig;
+use crate::error::{Result, RustLogicGraphError};
+use crate::io::GraphIO;
+use crate::rule::{DecisionTable, Rule};
+
+/// Builds a node instance from its id and configuration
+///
+/// Implemented for any `Fn(&str, &NodeConfig) -> Result<Box<dyn Node>>`, so
+/// plain functions and closures can be registered directly.
+pub trait NodeFactory: Send + Sync {
+    fn create(&self, node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>>;
+}
+
+impl<F> NodeFactory for F
+where
+    F: Fn(&str, &NodeConfig) -> Result<Box<dyn Node>> + Send + Sync,
+{
+    fn create(&self, node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+        self(node_id, config)
+    }
+}
+
+/// Registry of node factories keyed by node type name
+#[derive(Clone)]
+pub struct NodeRegistry {
+    factories: HashMap<String, Arc<dyn NodeFactory>>,
+}
+
+impl NodeRegistry {
+    /// Create an empty registry (no built-in node types)
+    pub fn empty() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Create a registry with factories for all built-in node types
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::empty();
+        registry.register(NodeType::RuleNode.name(), build_rule_node);
+        registry.register(NodeType::DBNode.name(), build_db_node);
+        registry.register(NodeType::AINode.name(), build_ai_node);
+        registry.register(NodeType::GrpcNode.name(), build_grpc_node);
+        registry.register(NodeType::SubgraphNode.name(), build_subgraph_node);
+        registry.register(NodeType::ConditionalNode.name(), build_conditional_node);
+        registry.register(NodeType::LoopNode.name(), build_loop_node);
+        registry.register(NodeType::TryCatchNode.name(), build_try_catch_node);
+        registry.register(NodeType::RetryNode.name(), build_retry_node);
+        registry.register(
+            NodeType::CircuitBreakerNode.name(),
+            build_circuit_breaker_node,
+        );
+        registry.register(
+            NodeType::DecisionTableNode.name(),
+            build_decision_table_node,
+        );
+        registry
+    }
+
+    /// Register (or replace) the factory for a node type name
+    pub fn register(&mut self, type_name: impl Into<String>, factory: impl NodeFactory + 'static) {
+        self.factories.insert(type_name.into(), Arc::new(factory));
+    }
+
+    /// Check whether a factory is registered for the node type name
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.factories.contains_key(type_name)
+    }
+
+    /// Names of all registered node types
+    pub fn type_names(&self) -> Vec<&str> {
+        self.factories.keys().map(|k| k.as_str()).collect()
+    }
+
+    /// Build a node using the factory registered for `config.node_type`
+    pub fn create(&self, node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+        let type_name = config.node_type.name();
+        let factory = self.factories.get(type_name).ok_or_else(|| {
+            RustLogicGraphError::configuration_error(format!(
+                "No factory registered for node type '{}' (node '{}')",
+                type_name, node_id
+            ))
+        })?;
+
+        factory
+            .create(node_id, config)
+            .map_err(|error| error.with_node(node_id))
+    }
+}
+
+impl Default for NodeRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+impl fmt::Debug for NodeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self.type_names();
+        names.sort_unstable();
+        f.debug_struct("NodeRegistry")
+            .field("node_types", &names)
+            .finish()
+    }
+}
+
+fn required<'a>(value: &'a Option<String>, field: &str, node_id: &str) -> Result<&'a str> {
+    value.as_deref().ok_or_else(|| {
+        RustLogicGraphError::configuration_error(format!(
+            "Node '{}' is missing required field '{}'",
+            node_id, field
+        ))
+    })
+}
+
+/// Parse a condition up front so invalid expressions fail when the graph is
+/// built; the node keeps the parsed rule
+fn checked_condition(condition: &str, node_id: &str) -> Result<Rule> {
+    Rule::compile(node_id, condition).map_err(|e| {
+        RustLogicGraphError::configuration_error(format!(
+            "Node '{}' has an invalid condition: {}",
+            node_id, e
+        ))
+    })
+}
+
+fn build_rule_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let grl = match (&config.grl, &config.grl_file) {
+        (Some(grl), _) => Some(grl.clone()),
+        (None, Some(path)) => Some(std::fs::read_to_string(path).map_err(|e| {
+            RustLogicGraphError::configuration_error(format!(
+                "RuleNode '{}': failed to read GRL file '{}': {}",
+                node_id, path, e
+            ))
+        })?),
+        (None, None) => None,
+    };
+    if let Some(grl) = grl {
+        let node = RuleNode::from_grl(node_id, &grl).map_err(|e| {
+            RustLogicGraphError::configuration_error(format!("RuleNode '{}': {}", node_id, e))
+        })?;
+        return Ok(Box::new(node));
+    }
+    if let Some(rule_set) = &config.rule_set {
+        return Ok(Box::new(RuleNode::with_rule_set(node_id, rule_set)));
+    }
+
+    let rule = checked_condition(config.condition.as_deref().unwrap_or("true"), node_id)?;
+    Ok(Box::new(RuleNode::from_rule(node_id, rule)))
+}
+
+fn build_decision_table_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let table = match (&config.table, &config.table_file) {
+        (Some(def), _) => {
+            let mut def = def.clone();
+            def.name.get_or_insert_with(|| node_id.to_string());
+            DecisionTable::from_def(&def)
+        }
+        (None, Some(path)) => DecisionTable::from_file(path),
+        (None, None) => {
+            return Err(RustLogicGraphError::configuration_error(format!(
+                "DecisionTableNode '{}' requires either 'table' or 'table_file'",
+                node_id
+            )))
+        }
+    };
+    let mut table = table.map_err(|e| {
+        RustLogicGraphError::configuration_error(format!("DecisionTableNode '{}': {}", node_id, e))
+    })?;
+    if let Some(hit_policy) = config.hit_policy {
+        table = table.with_hit_policy(hit_policy).map_err(|e| {
+            RustLogicGraphError::configuration_error(format!(
+                "DecisionTableNode '{}': {}",
+                node_id, e
+            ))
+        })?;
+    }
+    Ok(Box::new(DecisionTableNode::new(node_id, table)))
+}
+
+fn build_db_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let query = config
+        .query
+        .clone()
+        .unwrap_or_else(|| format!("SELECT * FROM {}", node_id));
+
+    // Create DBNode with params if specified
+    if let Some(params) = config.params.clone() {
+        Ok(Box::new(DBNode::with_params(node_id, query, params)))
+    } else {
+        Ok(Box::new(DBNode::new(node_id, query)))
+    }
+}
+
+fn build_ai_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let prompt = config
+        .prompt
+        .clone()
+        .unwrap_or_else(|| format!("Process data for {}", node_id));
+    Ok(Box::new(AINode::new(node_id, prompt)))
+}
+
+fn build_grpc_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    // Parse query field as "service_url#method"
+    let query = config
+        .query
+        .clone()
+        .unwrap_or_else(|| format!("http://localhost:50051#{}_method", node_id));
+    let (service_url, method) = query
+        .split_once('#')
+        .unwrap_or((query.as_str(), "UnknownMethod"));
+    Ok(Box::new(GrpcNode::new(node_id, service_url, method)))
+}
+
+fn build_subgraph_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let graph_def = match (&config.subgraph, &config.subgraph_file) {
+        (Some(def), _) => (**def).clone(),
+        (None, Some(path)) => GraphIO::load_from_any_file(path).map_err(|e| {
+            RustLogicGraphError::configuration_error(format!(
+                "SubgraphNode '{}': {}",
+                node_id, e.message
+            ))
+            .with_source(e)
+        })?,
+        (None, None) => {
+            return Err(RustLogicGraphError::configuration_error(format!(
+                "SubgraphNode '{}' requires either 'subgraph' or 'subgraph_file'",
+                node_id
+            )))
+        }
+    };
+
+    let mut node = SubgraphNode::new(node_id, graph_def);
+    if let Some(mapping) = &config.input_mapping {
+        node = node.with_input_mapping(mapping.clone());
+    }
+    if let Some(mapping) = &config.output_mapping {
+        node = node.with_output_mapping(mapping.clone());
+    }
+    Ok(Box::new(node))
+}
+
+fn build_conditional_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let condition = required(&config.condition, "condition", node_id)?;
+    let mut node = ConditionalNode::from_rule(node_id, checked_condition(condition, node_id)?);
+    node.true_branch = config.true_branch.clone();
+    node.false_branch = config.false_branch.clone();
+    Ok(Box::new(node))
+}
+
+fn build_loop_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let mut node = match &config.collection_key {
+        Some(collection_key) => LoopNode::new_foreach(node_id, collection_key),
+        None => {
+            let condition = required(&config.condition, "condition", node_id)?;
+            LoopNode::while_rule(node_id, checked_condition(condition, node_id)?, 10000)
+        }
+    };
+    if let Some(max_iterations) = config.max_iterations {
+        node.max_iterations = max_iterations;
+    }
+    if let Some(body_node) = &config.body_node {
+        node = node.with_body_node(body_node);
+    }
+    Ok(Box::new(node))
+}
+
+fn build_try_catch_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let try_node = required(&config.try_node, "try_node", node_id)?;
+    let mut node = TryCatchNode::new(node_id, try_node);
+    if let Some(catch_node) = &config.catch_node {
+        node = node.with_catch(catch_node);
+    }
+    if let Some(finally_node) = &config.finally_node {
+        node = node.with_finally(finally_node);
+    }
+    Ok(Box::new(node))
+}
+
+fn build_retry_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let target = required(&config.target_node, "target_node", node_id)?;
+    let mut node = RetryNode::new(node_id, target, config.max_retries.unwrap_or(3));
+    if let Some(delay) = config.initial_delay_ms {
+        node.initial_delay_ms = delay;
+    }
+    if let Some(multiplier) = config.backoff_multiplier {
+        node.backoff_multiplier = multiplier;
+    }
+    Ok(Box::new(node))
+}
+
+fn build_circuit_breaker_node(node_id: &str, config: &NodeConfig) -> Result<Box<dyn Node>> {
+    let target = required(&config.target_node, "target_node", node_id)?;
+    let mut node = CircuitBreakerNode::new(node_id, target, config.failure_threshold.unwrap_or(5));
+    if let Some(recovery_timeout_ms) = config.recovery_timeout_ms {
+        node.half_open_timeout_ms = recovery_timeout_ms;
+    }
+    Ok(Box::new(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GraphDef;
+    use serde_json::json;
+
+    #[test]
+    fn test_builtin_types_registered() {
+        let registry = NodeRegistry::default();
+        for node_type in [
+            NodeType::RuleNode,
+            NodeType::DBNode,
+            NodeType::AINode,
+            NodeType::GrpcNode,
+            NodeType::SubgraphNode,
+            NodeType::ConditionalNode,
+            NodeType::LoopNode,
+            NodeType::TryCatchNode,
+            NodeType::RetryNode,
+            NodeType::CircuitBreakerNode,
+            NodeType::DecisionTableNode,
+        ] {
+            assert!(registry.contains(node_type.name()));
+        }
+    }
+
+    #[test]
+    fn test_advanced_nodes_built_with_their_type() {
+        let registry = NodeRegistry::default();
+
+        let node = registry
+            .create("retry", &NodeConfig::retry_node("call_api", 2))
+            .unwrap();
+        assert_eq!(node.node_type(), NodeType::RetryNode);
+
+        let node = registry
+            .create("guard", &NodeConfig::try_catch_node("risky"))
+            .unwrap();
+        assert_eq!(node.node_type(), NodeType::TryCatchNode);
+
+        let sub = GraphDef::new(
+            HashMap::from([("inner".to_string(), NodeConfig::rule_node("true"))]),
+            vec![],
+        );
+        let node = registry
+            .create("sub", &NodeConfig::subgraph_node(sub))
+            .unwrap();
+        assert_eq!(node.node_type(), NodeType::SubgraphNode);
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let registry = NodeRegistry::default();
+        let config = NodeConfig {
+            node_type: NodeType::TryCatchNode,
+            ..Default::default()
+        };
+
+        let err = registry.create("guard", &config).err().unwrap();
+        assert!(err.to_string().contains("try_node"));
+    }
+
+    #[test]
+    fn test_custom_factory() {
+        let mut registry = NodeRegistry::empty();
+        registry.register("EchoNode", |id: &str, config: &NodeConfig| {
+            let value = config.settings.clone().unwrap_or(json!(null));
+            Ok(Box::new(RuleNode::new(id, value.to_string())) as Box<dyn Node>)
+        });
+
+        let config = NodeConfig::custom("EchoNode", json!("hello"));
+        let node = registry.create("echo", &config).unwrap();
+        assert_eq!(node.id(), "echo");
+
+        let unknown = NodeConfig::custom("MissingNode", json!({}));
+        assert!(registry.create("x", &unknown).is_err());
+    }
+
+    #[test]
+    fn test_custom_node_type_from_yaml() {
+        let yaml = r#"
+nodes:
+  lookup:
+    node_type: PostgresNode
+    settings:
+      table: orders
+  check:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: lookup
+    to: check
+"#;
+        let def: GraphDef = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            def.nodes["lookup"].node_type,
+            NodeType::Custom("PostgresNode".to_string())
+        );
+        assert_eq!(def.nodes["check"].node_type, NodeType::RuleNode);
+    }
+}
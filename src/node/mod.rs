@@ -1,13 +1,18 @@
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::core::Context;
-use crate::rule::RuleResult;
+use crate::core::{Context, ExecutionScope};
+use crate::rule::{DecisionTable, Rule, RuleEngine, RuleError, RuleResult};
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+mod factory;
+
+pub use factory::{NodeFactory, NodeRegistry};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 pub enum NodeType {
     #[default]
     RuleNode,
@@ -20,6 +25,30 @@ pub enum NodeType {
     TryCatchNode,
     RetryNode,
     CircuitBreakerNode,
+    DecisionTableNode,
+    /// User-defined node kind, built by a factory registered under this name
+    #[serde(untagged)]
+    Custom(String),
+}
+
+impl NodeType {
+    /// Name used to look up the node's factory in a [`NodeRegistry`]
+    pub fn name(&self) -> &str {
+        match self {
+            NodeType::RuleNode => "RuleNode",
+            NodeType::DBNode => "DBNode",
+            NodeType::AINode => "AINode",
+            NodeType::GrpcNode => "GrpcNode",
+            NodeType::SubgraphNode => "SubgraphNode",
+            NodeType::ConditionalNode => "ConditionalNode",
+            NodeType::LoopNode => "LoopNode",
+            NodeType::TryCatchNode => "TryCatchNode",
+            NodeType::RetryNode => "RetryNode",
+            NodeType::CircuitBreakerNode => "CircuitBreakerNode",
+            NodeType::DecisionTableNode => "DecisionTableNode",
+            NodeType::Custom(name) => name,
+        }
+    }
 }
 
 #[async_trait]
@@ -27,25 +56,147 @@ pub trait Node: Send + Sync {
     fn id(&self) -> &str;
     fn node_type(&self) -> NodeType;
     async fn run(&self, ctx: &mut Context) -> RuleResult;
+
+    /// Run the node with access to the executor that scheduled it.
+    ///
+    /// The executor always calls this method. Composite nodes (subgraphs,
+    /// wrappers) override it to reach the node registry, the cache or other
+    /// nodes; the default simply delegates to [`Node::run`].
+    async fn run_scoped(&self, ctx: &mut Context, _scope: &ExecutionScope<'_>) -> RuleResult {
+        self.run(ctx).await
+    }
+
+    /// Ids of the nodes this node invokes itself (through
+    /// [`ExecutionScope::run_node`]).
+    ///
+    /// The executor does not schedule wrapped nodes on their own; they only
+    /// run when their wrapper calls them.
+    fn wrapped_nodes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether the node handles cancellation of its execution itself.
+    ///
+    /// When an execution is cancelled, the executor drops the futures of the
+    /// nodes still running. Nodes returning true keep running instead: the
+    /// nodes they invoke are cancelled, and they get to run their cleanup
+    /// (e.g. a finally block) before returning. Defaults to true for wrapper
+    /// nodes.
+    fn observes_cancellation(&self) -> bool {
+        !self.wrapped_nodes().is_empty()
+    }
+
+    /// Name of the managed rule set this node runs, if any. The version the
+    /// run uses becomes part of the node's cache key.
+    fn rule_set(&self) -> Option<&str> {
+        None
+    }
 }
 
 // ============================================================
 // RuleNode - Evaluates conditions and transforms data
 // ============================================================
 
-#[derive(Debug, Clone)]
+/// Evaluates a condition expression, or GRL rules when built with
+/// [`RuleNode::from_grl`] or [`RuleNode::with_rule_set`]
+#[derive(Clone)]
 pub struct RuleNode {
     pub id: String,
     pub condition: String,
+    /// `condition`, parsed when the node is created
+    rule: Rule,
+    grl: Option<Arc<Mutex<RuleEngine>>>,
+    rule_set: Option<String>,
 }
 
 impl RuleNode {
     pub fn new(id: impl Into<String>, condition: impl Into<String>) -> Self {
+        let id = id.into();
+        let rule = Rule::new(id.clone(), condition);
+        Self::from_rule(id, rule)
+    }
+
+    /// Create a RuleNode evaluating an already parsed condition
+    pub(crate) fn from_rule(id: impl Into<String>, rule: Rule) -> Self {
         Self {
             id: id.into(),
-            condition: condition.into(),
+            condition: rule.condition.clone(),
+            rule,
+            grl: None,
+            rule_set: None,
+        }
+    }
+
+    /// Create a RuleNode that runs a GRL rule set against the context and
+    /// writes the facts it sets back to the context
+    pub fn from_grl(id: impl Into<String>, grl: &str) -> Result<Self, RuleError> {
+        let id = id.into();
+        let engine = RuleEngine::from_grl(grl)?;
+        if engine.inner().knowledge_base().rule_count() == 0 {
+            return Err(RuleError::InvalidExpression(format!(
+                "GRL for RuleNode '{}' does not define any rules",
+                id
+            )));
+        }
+        Ok(Self {
+            grl: Some(Arc::new(Mutex::new(engine))),
+            ..Self::new(id, "")
+        })
+    }
+
+    /// Create a RuleNode that runs the named rule set of the executor's
+    /// [`RuleSetManager`](crate::rule::RuleSetManager), picking up new
+    /// versions as they are loaded
+    pub fn with_rule_set(id: impl Into<String>, rule_set: impl Into<String>) -> Self {
+        Self {
+            rule_set: Some(rule_set.into()),
+            ..Self::new(id, "")
         }
     }
+
+    /// Whether this node runs GRL rules rather than a condition
+    pub fn is_grl(&self) -> bool {
+        self.grl.is_some() || self.rule_set.is_some()
+    }
+
+    /// Write the facts produced by GRL rules to the context, returning the
+    /// ones that are new or changed
+    fn apply_facts(&self, facts: Value, ctx: &mut Context) -> Value {
+        let mut changed = serde_json::Map::new();
+        if let Value::Object(facts) = facts {
+            for (key, value) in facts {
+                if !same_fact(ctx.data.get(&key), &value) {
+                    ctx.data.insert(key.clone(), value.clone());
+                    changed.insert(key, value);
+                }
+            }
+        }
+
+        debug!("RuleNode[{}]: Facts set = {:?}", self.id, changed);
+        Value::Object(changed)
+    }
+}
+
+/// Whether a fact returned by the engine matches the context value it came
+/// from; numbers compare by value, since rule arithmetic may turn integers
+/// into floats
+fn same_fact(current: Option<&Value>, fact: &Value) -> bool {
+    match (current, fact) {
+        (Some(Value::Number(a)), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Some(current), fact) => current == fact,
+        (None, _) => false,
+    }
+}
+
+impl std::fmt::Debug for RuleNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleNode")
+            .field("id", &self.id)
+            .field("condition", &self.condition)
+            .field("grl", &self.grl.is_some())
+            .field("rule_set", &self.rule_set)
+            .finish()
+    }
 }
 
 #[async_trait]
@@ -58,26 +209,136 @@ impl Node for RuleNode {
         NodeType::RuleNode
     }
 
+    fn rule_set(&self) -> Option<&str> {
+        self.rule_set.as_deref()
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        if let Some(name) = &self.rule_set {
+            return Err(RuleError::Configuration(format!(
+                "RuleNode '{}' uses rule set '{}' and must be run by an Executor",
+                self.id, name
+            )));
+        }
+
+        let result = match &self.grl {
+            Some(engine) => {
+                info!("RuleNode[{}]: Running GRL rule set", self.id);
+                let facts = engine.lock().evaluate(&ctx.data)?;
+                self.apply_facts(facts, ctx)
+            }
+            None => {
+                info!(
+                    "RuleNode[{}]: Evaluating condition '{}'",
+                    self.id, self.condition
+                );
+                let result = self.rule.evaluate(&ctx.data)?;
+                debug!("RuleNode[{}]: Result = {:?}", self.id, result);
+                result
+            }
+        };
+
+        ctx.data
+            .insert(format!("{}_result", self.id), result.clone());
+
+        Ok(result)
+    }
+
+    async fn run_scoped(&self, ctx: &mut Context, scope: &ExecutionScope<'_>) -> RuleResult {
+        let traced = scope.rule_tracing();
+        let (facts, trace) = match (&self.rule_set, &self.grl) {
+            (Some(name), _) => {
+                let rule_set = scope.rule_set(name)?;
+                info!(
+                    "RuleNode[{}]: Running rule set '{}' version {}",
+                    self.id,
+                    name,
+                    rule_set.version()
+                );
+                if traced {
+                    let (facts, trace) = rule_set.evaluate_traced(&ctx.data)?;
+                    (facts, Some(trace))
+                } else {
+                    (rule_set.evaluate(&ctx.data)?, None)
+                }
+            }
+            (None, Some(engine)) if traced => {
+                info!("RuleNode[{}]: Running GRL rule set with tracing", self.id);
+                let (facts, trace) = engine.lock().evaluate_traced(&ctx.data)?;
+                (facts, Some(trace))
+            }
+            _ => return self.run(ctx).await,
+        };
+
+        let mut result = self.apply_facts(facts, ctx);
+        if let Some(trace) = trace {
+            debug!("RuleNode[{}]: Rule trace:\n{}", self.id, trace);
+            result["_trace"] = serde_json::to_value(&trace)
+                .map_err(|e| RuleError::Eval(format!("Failed to serialize rule trace: {}", e)))?;
+            scope.record_rule_trace(trace);
+        }
+
+        ctx.data
+            .insert(format!("{}_result", self.id), result.clone());
+
+        Ok(result)
+    }
+}
+
+// ============================================================
+// DecisionTableNode - Evaluates a decision table
+// ============================================================
+
+/// Evaluates a [`DecisionTable`] against the context and writes the outputs
+/// of the selected row (or, with the `collect` hit policy, arrays of the
+/// outputs of all matching rows) to the context
+#[derive(Debug, Clone)]
+pub struct DecisionTableNode {
+    pub id: String,
+    pub table: Arc<DecisionTable>,
+}
+
+impl DecisionTableNode {
+    pub fn new(id: impl Into<String>, table: DecisionTable) -> Self {
+        Self {
+            id: id.into(),
+            table: Arc::new(table),
+        }
+    }
+}
+
+#[async_trait]
+impl Node for DecisionTableNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::DecisionTableNode
+    }
+
     async fn run(&self, ctx: &mut Context) -> RuleResult {
         info!(
-            "RuleNode[{}]: Evaluating condition '{}'",
-            self.id, self.condition
+            "DecisionTableNode[{}]: Evaluating table '{}' ({} rows, hit policy {})",
+            self.id,
+            self.table.name(),
+            self.table.len(),
+            self.table.hit_policy()
         );
 
-        // Simple condition evaluation (can be extended with proper parser)
-        let result = if self.condition == "true" {
-            Value::Bool(true)
-        } else if self.condition == "false" {
-            Value::Bool(false)
-        } else {
-            // Try to evaluate based on context
-            ctx.data
-                .get(&self.condition)
-                .cloned()
-                .unwrap_or(Value::Bool(true))
-        };
+        let result = self.table.evaluate(&ctx.data)?;
+        if let Value::Object(outputs) = &result {
+            for (output, value) in outputs {
+                ctx.set_path(output, value.clone()).map_err(|e| {
+                    RuleError::Eval(format!(
+                        "DecisionTableNode '{}': failed to set '{}': {}",
+                        self.id, output, e
+                    ))
+                })?;
+            }
+        }
+        debug!("DecisionTableNode[{}]: Result = {:?}", self.id, result);
 
-        debug!("RuleNode[{}]: Result = {:?}", self.id, result);
         ctx.data
             .insert(format!("{}_result", self.id), result.clone());
 
@@ -122,7 +383,7 @@ pub struct DBNode {
     pub id: String,
     pub query: String,
     executor: Option<Arc<dyn DatabaseExecutor>>,
-    /// Context keys to extract as query parameters
+    /// Context keys or paths (e.g. `customer.id`) to extract as query parameters
     param_keys: Option<Vec<String>>,
 }
 
@@ -372,15 +633,17 @@ impl Node for GrpcNode {
 // ============================================================
 
 use crate::core::executor::Executor;
-use crate::core::{Graph, GraphDef};
+use crate::core::{ExecutionPlan, GraphDef};
 use std::collections::HashMap;
+use tokio::sync::OnceCell;
 
-#[derive(Debug, Clone)]
 pub struct SubgraphNode {
     pub id: String,
     pub graph_def: GraphDef,
     pub input_mapping: HashMap<String, String>, // parent_key -> child_key
     pub output_mapping: HashMap<String, String>, // child_key -> parent_key
+    /// Plan of the child graph, compiled on the first run
+    plan: OnceCell<ExecutionPlan>,
 }
 
 impl SubgraphNode {
@@ -390,6 +653,7 @@ impl SubgraphNode {
             graph_def,
             input_mapping: HashMap::new(),
             output_mapping: HashMap::new(),
+            plan: OnceCell::new(),
         }
     }
 
@@ -402,6 +666,48 @@ impl SubgraphNode {
         self.output_mapping = mapping;
         self
     }
+
+    /// A child executor configured like the running one, and the child
+    /// graph's plan, compiled on the first call
+    async fn child(
+        &self,
+        scope: &ExecutionScope<'_>,
+    ) -> Result<(Executor, &ExecutionPlan), RuleError> {
+        let built = async {
+            let executor = scope.child_executor(&self.id, &self.graph_def)?;
+            let plan = self
+                .plan
+                .get_or_try_init(|| async { executor.compile(&self.graph_def) })
+                .await?;
+            Ok::<_, crate::error::RustLogicGraphError>((executor, plan))
+        };
+        built.await.map_err(|e| {
+            RuleError::Configuration(format!("Subgraph '{}' could not be built: {}", self.id, e))
+        })
+    }
+}
+
+impl Clone for SubgraphNode {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            graph_def: self.graph_def.clone(),
+            input_mapping: self.input_mapping.clone(),
+            output_mapping: self.output_mapping.clone(),
+            plan: OnceCell::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SubgraphNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubgraphNode")
+            .field("id", &self.id)
+            .field("graph_def", &self.graph_def)
+            .field("input_mapping", &self.input_mapping)
+            .field("output_mapping", &self.output_mapping)
+            .finish()
+    }
 }
 
 #[async_trait]
@@ -414,45 +720,78 @@ impl Node for SubgraphNode {
         NodeType::SubgraphNode
     }
 
+    fn observes_cancellation(&self) -> bool {
+        true
+    }
+
     async fn run(&self, ctx: &mut Context) -> RuleResult {
+        // Standalone run: build child nodes with the built-in factories, no cache
+        let executor = Executor::new();
+        let scope = ExecutionScope::new(&executor);
+        self.run_scoped(ctx, &scope).await
+    }
+
+    async fn run_scoped(&self, ctx: &mut Context, scope: &ExecutionScope<'_>) -> RuleResult {
         info!(
             "🔷 SubgraphNode[{}]: Executing nested graph with {} nodes",
             self.id,
             self.graph_def.nodes.len()
         );
 
-        // Create child graph with mapped inputs
-        let mut child_graph = Graph::new(self.graph_def.clone());
-
         // Map inputs from parent context to child context
+        let mut child_ctx = Context::new();
         for (parent_key, child_key) in &self.input_mapping {
             if let Some(value) = ctx.data.get(parent_key) {
                 debug!(
                     "SubgraphNode[{}]: Mapping {} -> {}",
                     self.id, parent_key, child_key
                 );
-                child_graph
-                    .context
-                    .data
-                    .insert(child_key.clone(), value.clone());
+                child_ctx.data.insert(child_key.clone(), value.clone());
             }
         }
 
-        // Create executor and register nodes from subgraph definition
-        let mut executor = Executor::new();
-
-        // TODO: Need to register nodes from graph_def
-        // This requires access to node constructors, which should be handled
-        // by a NodeFactory or similar pattern
-
-        // Execute child graph
-        executor.execute(&mut child_graph).await.map_err(|e| {
-            crate::rule::RuleError::Eval(format!("Subgraph execution failed: {}", e))
+        // Execute child graph, cancelled along with the parent execution
+        let (executor, plan) = self.child(scope).await?;
+        let outcome = match scope.cancellation() {
+            Some(cancel) => {
+                executor
+                    .execute_plan_with_cancellation(plan, &mut child_ctx, cancel.child_token())
+                    .await
+            }
+            None => executor.execute_plan(plan, &mut child_ctx).await,
+        };
+        let metrics = match &outcome {
+            Ok(result) => result.metrics.clone(),
+            Err(e) => e.metrics().cloned().unwrap_or_default(),
+        };
+        scope.record_nested_metrics(&self.id, &metrics);
+        outcome.map_err(|e| {
+            if scope.is_cancelled() {
+                RuleError::Cancelled(format!(
+                    "Subgraph '{}' was cancelled with its execution",
+                    self.id
+                ))
+            } else {
+                RuleError::Eval(format!("Subgraph execution failed: {}", e))
+            }
         })?;
 
+        let failed: Vec<&str> = metrics
+            .node_stats
+            .iter()
+            .filter(|s| !s.success)
+            .map(|s| s.node_id.as_str())
+            .collect();
+        if !failed.is_empty() {
+            return Err(RuleError::Eval(format!(
+                "Subgraph '{}' failed: node(s) {:?} failed",
+                self.id, failed
+            )));
+        }
+
         // Map outputs from child context back to parent context
         for (child_key, parent_key) in &self.output_mapping {
-            if let Some(value) = child_graph.context.data.get(child_key) {
+            if let Some(value) = child_ctx.data.get(child_key) {
                 debug!(
                     "SubgraphNode[{}]: Mapping output {} -> {}",
                     self.id, child_key, parent_key
@@ -464,7 +803,9 @@ impl Node for SubgraphNode {
         // Store subgraph result
         let result = serde_json::json!({
             "status": "completed",
-            "nodes_executed": self.graph_def.nodes.len()
+            "nodes_executed": metrics.nodes_executed,
+            "nodes_skipped": metrics.nodes_skipped,
+            "cache_hits": metrics.cache_hits
         });
 
         ctx.data
@@ -483,15 +824,24 @@ impl Node for SubgraphNode {
 pub struct ConditionalNode {
     pub id: String,
     pub condition: String,
+    rule: Rule,                       // `condition`, parsed once
     pub true_branch: Option<String>,  // Node ID to route to if true
     pub false_branch: Option<String>, // Node ID to route to if false
 }
 
 impl ConditionalNode {
     pub fn new(id: impl Into<String>, condition: impl Into<String>) -> Self {
+        let id = id.into();
+        let rule = Rule::new(id.clone(), condition);
+        Self::from_rule(id, rule)
+    }
+
+    /// Create a ConditionalNode branching on an already parsed condition
+    pub(crate) fn from_rule(id: impl Into<String>, rule: Rule) -> Self {
         Self {
             id: id.into(),
-            condition: condition.into(),
+            condition: rule.condition.clone(),
+            rule,
             true_branch: None,
             false_branch: None,
         }
@@ -524,15 +874,7 @@ impl Node for ConditionalNode {
             self.id, self.condition
         );
 
-        // Use existing rule engine to evaluate condition
-        use crate::rule::Rule;
-        let rule = Rule::new(&self.id, &self.condition);
-        let eval_result = rule.evaluate(&ctx.data)?;
-
-        let condition_met = match eval_result {
-            Value::Bool(b) => b,
-            _ => false,
-        };
+        let condition_met = self.rule.evaluate_bool(&ctx.data)?;
 
         let selected_branch = if condition_met {
             self.true_branch.as_ref()
@@ -574,6 +916,7 @@ impl Node for ConditionalNode {
 pub struct LoopNode {
     pub id: String,
     pub condition: String,              // Loop while this is true
+    rule: Rule,                         // `condition`, parsed once
     pub max_iterations: usize,          // Safety limit
     pub body_node_id: Option<String>,   // Node to execute in loop body
     pub collection_key: Option<String>, // For iterating over arrays
@@ -585,9 +928,17 @@ impl LoopNode {
         condition: impl Into<String>,
         max_iterations: usize,
     ) -> Self {
+        let id = id.into();
+        let rule = Rule::new(id.clone(), condition);
+        Self::while_rule(id, rule, max_iterations)
+    }
+
+    /// Create a while loop on an already parsed condition
+    pub(crate) fn while_rule(id: impl Into<String>, rule: Rule, max_iterations: usize) -> Self {
         Self {
             id: id.into(),
-            condition: condition.into(),
+            condition: rule.condition.clone(),
+            rule,
             max_iterations,
             body_node_id: None,
             collection_key: None,
@@ -595,8 +946,10 @@ impl LoopNode {
     }
 
     pub fn new_foreach(id: impl Into<String>, collection_key: impl Into<String>) -> Self {
+        let id = id.into();
         Self {
-            id: id.into(),
+            rule: Rule::new(id.clone(), "true"),
+            id,
             condition: "true".to_string(),
             max_iterations: 10000,
             body_node_id: None,
@@ -620,7 +973,17 @@ impl Node for LoopNode {
         NodeType::LoopNode
     }
 
+    fn wrapped_nodes(&self) -> Vec<String> {
+        self.body_node_id.iter().cloned().collect()
+    }
+
     async fn run(&self, ctx: &mut Context) -> RuleResult {
+        // Without an executor there are no other nodes to run as the body
+        let executor = Executor::new();
+        self.run_scoped(ctx, &ExecutionScope::new(&executor)).await
+    }
+
+    async fn run_scoped(&self, ctx: &mut Context, scope: &ExecutionScope<'_>) -> RuleResult {
         info!("🔁 LoopNode[{}]: Starting loop execution", self.id);
 
         let mut iterations = 0;
@@ -652,13 +1015,14 @@ impl Node for LoopNode {
                             .insert("_loop_index".to_string(), Value::from(index));
                         ctx.data.insert("_loop_item".to_string(), item.clone());
 
-                        // TODO: Execute body node if specified
-                        // This requires access to executor or node registry
-
-                        loop_results.push(serde_json::json!({
+                        let mut entry = serde_json::json!({
                             "iteration": index,
                             "item": item
-                        }));
+                        });
+                        if let Some(body) = &self.body_node_id {
+                            entry["result"] = scope.run_node(body, ctx).await?;
+                        }
+                        loop_results.push(entry);
 
                         iterations += 1;
                     }
@@ -666,28 +1030,21 @@ impl Node for LoopNode {
             }
         } else {
             // While loop
-            use crate::rule::Rule;
-            let rule = Rule::new(&self.id, &self.condition);
-
             while iterations < self.max_iterations {
-                let eval_result = rule.evaluate(&ctx.data)?;
-                let should_continue = match eval_result {
-                    Value::Bool(b) => b,
-                    _ => false,
-                };
-
-                if !should_continue {
+                if !self.rule.evaluate_bool(&ctx.data)? {
                     break;
                 }
 
                 ctx.data
                     .insert("_loop_iteration".to_string(), Value::from(iterations));
 
-                // TODO: Execute body node if specified
-
-                loop_results.push(serde_json::json!({
+                let mut entry = serde_json::json!({
                     "iteration": iterations
-                }));
+                });
+                if let Some(body) = &self.body_node_id {
+                    entry["result"] = scope.run_node(body, ctx).await?;
+                }
+                loop_results.push(entry);
 
                 iterations += 1;
             }
@@ -753,47 +1110,84 @@ impl Node for TryCatchNode {
         NodeType::TryCatchNode
     }
 
-    async fn run(&self, ctx: &mut Context) -> RuleResult {
-        info!("🛡️  TryCatchNode[{}]: Executing try block", self.id);
+    fn wrapped_nodes(&self) -> Vec<String> {
+        std::iter::once(&self.try_node_id)
+            .chain(&self.catch_node_id)
+            .chain(&self.finally_node_id)
+            .cloned()
+            .collect()
+    }
 
-        // TODO: Execute try_node_id
-        // For now, simulate success/failure based on context
-        let error_occurred = ctx
-            .data
-            .get("_simulate_error")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+    async fn run(&self, _ctx: &mut Context) -> RuleResult {
+        Err(wrapper_requires_executor(&self.id))
+    }
 
-        let result = if error_occurred {
-            info!(
-                "⚠️  TryCatchNode[{}]: Error occurred, executing catch block",
-                self.id
-            );
-            ctx.data.insert(
-                "_error".to_string(),
-                Value::String("Simulated error".to_string()),
-            );
+    async fn run_scoped(&self, ctx: &mut Context, scope: &ExecutionScope<'_>) -> RuleResult {
+        info!("🛡️  TryCatchNode[{}]: Executing try block", self.id);
 
-            serde_json::json!({
-                "status": "error_handled",
-                "try_node": self.try_node_id,
-                "catch_node": self.catch_node_id
-            })
-        } else {
-            serde_json::json!({
+        // Writes of a failed try block must not reach the catch block
+        let before = ctx.clone();
+
+        let outcome = match scope.run_node(&self.try_node_id, ctx).await {
+            Ok(value) => Ok(serde_json::json!({
                 "status": "success",
-                "try_node": self.try_node_id
-            })
+                "try_node": self.try_node_id,
+                "result": value
+            })),
+            // Cancellation is not an error for the catch block to handle
+            Err(error) if error.is_cancelled() => Err(error),
+            Err(error) => {
+                ctx.clone_from(&before);
+                ctx.data
+                    .insert("_error".to_string(), Value::String(error.to_string()));
+
+                match &self.catch_node_id {
+                    Some(catch_node_id) => {
+                        info!(
+                            "⚠️  TryCatchNode[{}]: Error occurred, executing catch block: {}",
+                            self.id, error
+                        );
+                        scope.run_node(catch_node_id, ctx).await.map(|value| {
+                            serde_json::json!({
+                                "status": "error_handled",
+                                "try_node": self.try_node_id,
+                                "catch_node": catch_node_id,
+                                "error": error.to_string(),
+                                "result": value
+                            })
+                        })
+                    }
+                    None => Err(error),
+                }
+            }
         };
 
-        // TODO: Execute finally block if specified
+        if let Some(finally_node_id) = &self.finally_node_id {
+            info!("TryCatchNode[{}]: Executing finally block", self.id);
+            if let Err(error) = scope.run_cleanup_node(finally_node_id, ctx).await {
+                // An error from the try/catch blocks takes precedence
+                if outcome.is_ok() {
+                    return Err(error);
+                }
+                warn!("TryCatchNode[{}]: Finally block failed: {}", self.id, error);
+            }
+        }
 
+        let result = outcome?;
         ctx.data
             .insert(format!("{}_result", self.id), result.clone());
         Ok(result)
     }
 }
 
+/// Error returned when a wrapper node is run outside of an executor
+fn wrapper_requires_executor(node_id: &str) -> crate::rule::RuleError {
+    crate::rule::RuleError::Configuration(format!(
+        "Node '{}' wraps other nodes and must be run by an Executor",
+        node_id
+    ))
+}
+
 // ============================================================
 // RetryNode - Automatic retry with exponential backoff
 // ============================================================
@@ -841,7 +1235,15 @@ impl Node for RetryNode {
         NodeType::RetryNode
     }
 
-    async fn run(&self, ctx: &mut Context) -> RuleResult {
+    fn wrapped_nodes(&self) -> Vec<String> {
+        vec![self.target_node_id.clone()]
+    }
+
+    async fn run(&self, _ctx: &mut Context) -> RuleResult {
+        Err(wrapper_requires_executor(&self.id))
+    }
+
+    async fn run_scoped(&self, ctx: &mut Context, scope: &ExecutionScope<'_>) -> RuleResult {
         info!(
             "🔄 RetryNode[{}]: Starting with max {} retries",
             self.id, self.max_retries
@@ -849,60 +1251,68 @@ impl Node for RetryNode {
 
         let mut attempt = 0;
         let mut delay_ms = self.initial_delay_ms;
+        // Writes of a failed attempt must not leak into the next one
+        let before = ctx.clone();
 
-        while attempt <= self.max_retries {
-            // TODO: Execute target_node_id
-            // For now, simulate retry logic
-
-            let should_retry = ctx
-                .data
-                .get("_simulate_failure")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false)
-                && attempt < self.max_retries;
+        loop {
+            attempt += 1;
 
-            if !should_retry {
-                let result = serde_json::json!({
-                    "status": "success",
-                    "attempts": attempt + 1,
-                    "target_node": self.target_node_id
-                });
-                ctx.data
-                    .insert(format!("{}_result", self.id), result.clone());
-                info!(
-                    "✅ RetryNode[{}]: Succeeded after {} attempts",
-                    self.id,
-                    attempt + 1
-                );
-                return Ok(result);
+            let outcome = scope.run_node(&self.target_node_id, ctx).await;
+            if outcome.is_err() {
+                ctx.clone_from(&before);
             }
+            match outcome {
+                Ok(value) => {
+                    let result = serde_json::json!({
+                        "status": "success",
+                        "attempts": attempt,
+                        "target_node": self.target_node_id,
+                        "result": value
+                    });
+                    ctx.data
+                        .insert(format!("{}_result", self.id), result.clone());
+                    info!(
+                        "✅ RetryNode[{}]: Succeeded after {} attempts",
+                        self.id, attempt
+                    );
+                    return Ok(result);
+                }
+                Err(error) if !error.is_retryable() => {
+                    info!(
+                        "❌ RetryNode[{}]: Attempt {} failed with a non-retryable error: {}",
+                        self.id, attempt, error
+                    );
+                    return Err(error);
+                }
+                Err(error) if attempt <= self.max_retries => {
+                    info!(
+                        "⚠️  RetryNode[{}]: Attempt {} failed ({}), retrying in {}ms",
+                        self.id, attempt, error, delay_ms
+                    );
 
-            info!(
-                "⚠️  RetryNode[{}]: Attempt {} failed, retrying in {}ms",
-                self.id,
-                attempt + 1,
-                delay_ms
-            );
-
-            sleep(Duration::from_millis(delay_ms)).await;
-            delay_ms = (delay_ms as f64 * self.backoff_multiplier) as u64;
-            attempt += 1;
+                    tokio::select! {
+                        _ = sleep(Duration::from_millis(delay_ms)) => {}
+                        _ = scope.cancelled() => {
+                            return Err(crate::rule::RuleError::Cancelled(format!(
+                                "RetryNode '{}' was cancelled with its execution",
+                                self.id
+                            )));
+                        }
+                    }
+                    delay_ms = (delay_ms as f64 * self.backoff_multiplier) as u64;
+                }
+                Err(error) => {
+                    info!(
+                        "❌ RetryNode[{}]: Failed after {} attempts",
+                        self.id, attempt
+                    );
+                    return Err(crate::rule::RuleError::Eval(format!(
+                        "RetryNode '{}': node '{}' failed after {} attempts: {}",
+                        self.id, self.target_node_id, attempt, error
+                    )));
+                }
+            }
         }
-
-        let error_result = serde_json::json!({
-            "status": "failed",
-            "attempts": attempt,
-            "target_node": self.target_node_id
-        });
-
-        ctx.data
-            .insert(format!("{}_result", self.id), error_result.clone());
-        info!(
-            "❌ RetryNode[{}]: Failed after {} attempts",
-            self.id, attempt
-        );
-
-        Ok(error_result)
     }
 }
 
@@ -910,13 +1320,25 @@ impl Node for RetryNode {
 // CircuitBreakerNode - Prevent cascading failures
 // ============================================================
 
-#[derive(Debug, Clone)]
+use crate::distributed::ContextStore;
+use crate::fault_tolerance::{CircuitBreaker, CircuitConfig};
+
+/// Guards a target node with a [`CircuitBreaker`].
+///
+/// The breaker is created on first use, keyed by this node's id. Its state is
+/// persisted in the node's own store (see [`CircuitBreakerNode::with_store`]),
+/// falling back to the executor's context store, and restored from there so
+/// an open circuit survives across executors and processes.
+///
+/// The target runs with its own `timeout_ms`; a timeout counts as a failure.
+#[derive(Clone)]
 pub struct CircuitBreakerNode {
     pub id: String,
     pub target_node_id: String,
     pub failure_threshold: usize,
-    pub timeout_ms: u64,
     pub half_open_timeout_ms: u64,
+    store: Option<Arc<dyn ContextStore>>,
+    breaker: OnceCell<Arc<CircuitBreaker>>,
 }
 
 impl CircuitBreakerNode {
@@ -929,10 +1351,57 @@ impl CircuitBreakerNode {
             id: id.into(),
             target_node_id: target_node_id.into(),
             failure_threshold,
-            timeout_ms: 5000,
             half_open_timeout_ms: 30000,
+            store: None,
+            breaker: OnceCell::new(),
         }
     }
+
+    /// Persist the circuit state in `store`
+    pub fn with_store(mut self, store: Arc<dyn ContextStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    async fn breaker(&self, scope: &ExecutionScope<'_>) -> &Arc<CircuitBreaker> {
+        self.breaker
+            .get_or_init(|| async {
+                let store = self
+                    .store
+                    .clone()
+                    .or_else(|| scope.context_store().cloned());
+                let persisted = store.is_some();
+                let config = CircuitConfig {
+                    failure_threshold: self.failure_threshold as u32,
+                    recovery_timeout: Duration::from_millis(self.half_open_timeout_ms),
+                    ..Default::default()
+                };
+
+                let breaker = CircuitBreaker::new(self.id.clone(), store, Some(config));
+                if persisted {
+                    if let Err(e) = breaker.restore().await {
+                        warn!(
+                            "CircuitBreakerNode[{}]: Failed to restore circuit state: {}",
+                            self.id, e
+                        );
+                    }
+                }
+                Arc::new(breaker)
+            })
+            .await
+    }
+}
+
+impl std::fmt::Debug for CircuitBreakerNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreakerNode")
+            .field("id", &self.id)
+            .field("target_node_id", &self.target_node_id)
+            .field("failure_threshold", &self.failure_threshold)
+            .field("half_open_timeout_ms", &self.half_open_timeout_ms)
+            .field("store", &self.store.is_some())
+            .finish()
+    }
 }
 
 #[async_trait]
@@ -945,46 +1414,56 @@ impl Node for CircuitBreakerNode {
         NodeType::CircuitBreakerNode
     }
 
-    async fn run(&self, ctx: &mut Context) -> RuleResult {
-        info!("⚡ CircuitBreakerNode[{}]: Checking circuit state", self.id);
+    fn wrapped_nodes(&self) -> Vec<String> {
+        vec![self.target_node_id.clone()]
+    }
 
-        // TODO: Implement proper circuit breaker state machine
-        // States: Closed, Open, HalfOpen
-        // For now, simple implementation
+    async fn run(&self, _ctx: &mut Context) -> RuleResult {
+        Err(wrapper_requires_executor(&self.id))
+    }
 
-        let is_circuit_open = ctx
-            .data
-            .get("_circuit_open")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+    async fn run_scoped(&self, ctx: &mut Context, scope: &ExecutionScope<'_>) -> RuleResult {
+        info!("⚡ CircuitBreakerNode[{}]: Checking circuit state", self.id);
 
-        if is_circuit_open {
+        let breaker = self.breaker(scope).await;
+        if !breaker.is_allowed().await {
             info!(
                 "🚫 CircuitBreakerNode[{}]: Circuit is OPEN, fast-failing",
                 self.id
             );
-            let result = serde_json::json!({
-                "status": "circuit_open",
-                "message": "Circuit breaker is open, request rejected"
-            });
-            ctx.data
-                .insert(format!("{}_result", self.id), result.clone());
-            return Ok(result);
+            return Err(crate::rule::RuleError::Eval(format!(
+                "Circuit breaker '{}' is open, request to '{}' rejected",
+                self.id, self.target_node_id
+            )));
         }
 
-        // Circuit is closed, execute target node
-        // TODO: Execute target_node_id and track failures
-
-        let result = serde_json::json!({
-            "status": "success",
-            "circuit_state": "closed",
-            "target_node": self.target_node_id
-        });
-
-        ctx.data
-            .insert(format!("{}_result", self.id), result.clone());
-        info!("✅ CircuitBreakerNode[{}]: Request completed", self.id);
+        match scope.run_node(&self.target_node_id, ctx).await {
+            Ok(value) => {
+                breaker.record_success().await;
+                let result = serde_json::json!({
+                    "status": "success",
+                    "circuit_state": breaker.current_state().await.as_str(),
+                    "target_node": self.target_node_id,
+                    "result": value
+                });
 
-        Ok(result)
+                ctx.data
+                    .insert(format!("{}_result", self.id), result.clone());
+                info!("✅ CircuitBreakerNode[{}]: Request completed", self.id);
+                Ok(result)
+            }
+            // The target did not fail; the execution was abandoned
+            Err(error) if error.is_cancelled() => Err(error),
+            Err(error) => {
+                breaker.record_failure().await;
+                warn!(
+                    "CircuitBreakerNode[{}]: Call failed, circuit is {}: {}",
+                    self.id,
+                    breaker.current_state().await.as_str(),
+                    error
+                );
+                Err(error)
+            }
+        }
     }
 }
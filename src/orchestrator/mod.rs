@@ -1,7 +1,7 @@
-use anyhow::Result;
+use crate::error::Result;
 use tracing::info;
 
-use crate::core::{Executor, Graph};
+use crate::core::{ExecutionResult, Executor, Graph};
 
 pub struct Orchestrator {
     executor: Executor,
@@ -14,15 +14,15 @@ impl Orchestrator {
     }
 
     /// Execute the graph using the internal executor
-    pub async fn execute(&mut self, graph: &mut Graph) -> Result<()> {
+    pub async fn execute(&mut self, graph: &mut Graph) -> Result<ExecutionResult> {
         info!("Orchestrator: Starting orchestration...");
-        self.executor.execute(graph).await?;
+        let result = self.executor.execute(graph).await?;
         info!("Orchestrator: Orchestration completed");
-        Ok(())
+        Ok(result)
     }
 
     /// Execute a graph using a default executor built from the graph definition
-    pub async fn execute_graph(graph: &mut Graph) -> Result<()> {
+    pub async fn execute_graph(graph: &mut Graph) -> Result<ExecutionResult> {
         info!("Orchestrator: Building executor from graph definition");
         let executor = Executor::from_graph_def(&graph.def)?;
         let mut orchestrator = Self::new(executor);
@@ -6,9 +6,8 @@ use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, info, warn};
 
-use crate::core::{Graph, GraphDef};
+use crate::core::{Executor, Graph, GraphDef};
 use crate::node::Node;
-use crate::rule::Rule;
 
 /// Configuration for parallel execution
 #[derive(Debug, Clone)]
@@ -37,23 +36,24 @@ pub struct ExecutionLayer {
 
 /// Parallel executor that identifies independent nodes and executes them concurrently
 pub struct ParallelExecutor {
-    nodes: HashMap<String, Box<dyn Node>>,
+    executor: Executor,
     _config: ParallelConfig,
 }
 
 impl ParallelExecutor {
     /// Create a new parallel executor
     pub fn new(config: ParallelConfig) -> Self {
+        let mut executor = Executor::new();
+        executor.set_max_concurrency(config.max_concurrent);
         Self {
-            nodes: HashMap::new(),
+            executor,
             _config: config,
         }
     }
 
     /// Register a node with the executor
     pub fn register_node(&mut self, node: Box<dyn Node>) {
-        let id = node.id().to_string();
-        self.nodes.insert(id, node);
+        self.executor.register_node(node);
     }
 
     /// Analyze graph and identify execution layers
@@ -150,121 +150,28 @@ impl ParallelExecutor {
         Ok(layers)
     }
 
-    /// Execute a single layer of nodes in parallel
-    async fn execute_layer(
-        &self,
-        layer: &ExecutionLayer,
-        graph: &mut Graph,
-    ) -> Result<Vec<String>> {
-        info!(
-            "ParallelExecutor: Executing layer {} with {} nodes",
-            layer.layer_index,
-            layer.node_ids.len()
-        );
-
-        let mut successful_nodes = Vec::new();
-
-        // Execute nodes in the layer
-        // Note: Currently executing sequentially within layer due to context sharing
-        // In a production system, you'd use proper synchronization (Arc<Mutex<Context>>)
-        // or message passing to enable true parallel execution
-        for node_id in &layer.node_ids {
-            // Check if node should be executed based on incoming edge rules
-            let should_execute = self.check_incoming_rules(node_id, graph);
-
-            if !should_execute {
-                info!("Skipping node '{}' due to failed rule", node_id);
-                continue;
-            }
-
-            if let Some(node) = self.nodes.get(node_id) {
-                info!("Executing node '{}'", node_id);
-
-                match node.run(&mut graph.context).await {
-                    Ok(_) => {
-                        info!("Node '{}' executed successfully", node_id);
-                        successful_nodes.push(node_id.clone());
-                    }
-                    Err(e) => {
-                        warn!("Node '{}' execution failed: {:?}", node_id, e);
-                    }
-                }
-            } else {
-                warn!("Node '{}' not found in executor", node_id);
-            }
-        }
-
-        info!(
-            "Layer {} completed: {}/{} nodes successful",
-            layer.layer_index,
-            successful_nodes.len(),
-            layer.node_ids.len()
-        );
-
-        Ok(successful_nodes)
-    }
-
-    /// Check if a node should execute based on incoming edge rules
-    fn check_incoming_rules(&self, node_id: &str, graph: &Graph) -> bool {
-        let incoming_edges: Vec<_> = graph.def.edges.iter().filter(|e| e.to == node_id).collect();
-
-        for edge in &incoming_edges {
-            if let Some(rule_id) = &edge.rule {
-                let rule = Rule::new(rule_id, "true");
-
-                match rule.evaluate(&graph.context.data) {
-                    Ok(result) => {
-                        if let serde_json::Value::Bool(false) = result {
-                            debug!(
-                                "Rule '{}' for edge {} -> {} evaluated to false",
-                                rule_id, edge.from, edge.to
-                            );
-                            return false;
-                        }
-                    }
-                    Err(e) => {
-                        warn!(
-                            "Rule '{}' evaluation failed: {}. Assuming true.",
-                            rule_id, e
-                        );
-                    }
-                }
-            }
-        }
-
-        true
-    }
-
-    /// Execute the entire graph with parallel execution per layer
+    /// Execute the entire graph, running the nodes of each layer concurrently
+    ///
+    /// Execution is delegated to the main [`Executor`], so caching, fallback
+    /// handling and edge conditions behave exactly as they do there.
     pub async fn execute(&self, graph: &mut Graph) -> Result<()> {
         info!("ParallelExecutor: Starting parallel graph execution");
 
-        // Identify execution layers
-        let layers = self.identify_layers(&graph.def)?;
-
-        if layers.is_empty() {
-            warn!("No execution layers found");
-            return Ok(());
-        }
-
-        info!("ParallelExecutor: Executing {} layers", layers.len());
-
-        let mut total_executed = 0;
-
-        // Execute each layer sequentially (but nodes within each layer run in parallel)
-        for layer in layers {
-            let successful_nodes = self.execute_layer(&layer, graph).await?;
-            total_executed += successful_nodes.len();
-        }
+        self.executor.execute(graph).await?;
 
         info!(
             "ParallelExecutor: Completed parallel execution. Total nodes executed: {}",
-            total_executed
+            self.executor.metrics().nodes_executed
         );
 
         Ok(())
     }
 
+    /// The underlying executor
+    pub fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
     /// Get parallel execution statistics
     pub fn get_parallelism_stats(&self, def: &GraphDef) -> Result<ParallelismStats> {
         let layers = self.identify_layers(def)?;
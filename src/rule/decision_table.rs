@@ -0,0 +1,826 @@
+//! Decision tables: rules written as rows of a table.
+//!
+//! Each row tests the input columns and, when all tests pass, produces the
+//! values in its output columns. Tables are written as CSV or YAML with the
+//! same column headers:
+//!
+//! - `out:<fact>` marks an output column
+//! - `priority` holds the row priority for the `priority` hit policy
+//! - any other header (optionally `in:<fact>`) is an input column; facts can
+//!   be paths into nested values such as `supplier.tier`
+//!
+//! ```text
+//! supplier_tier,order_qty,out:discount
+//! gold,>= 100,0.15
+//! gold,,0.10
+//! "silver, bronze",10..99,0.05
+//! -,-,0
+//! ```
+//!
+//! An input cell is empty or `-` to match anything, a comparison (`>= 100`,
+//! `!= "gold"`), an inclusive range (`10..99`), a comma separated list of
+//! alternatives, or a plain value to compare for equality. Output cells are
+//! plain values; an empty output cell leaves the fact unset.
+//!
+//! The [`HitPolicy`] decides what happens when several rows match.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::RuleError;
+use crate::core::path;
+
+/// How a decision table combines the rows that match
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HitPolicy {
+    /// The first matching row, in table order
+    #[default]
+    First,
+    /// At most one row may match; more than one is an error
+    Unique,
+    /// Every matching row; each output becomes an array of the values of the
+    /// matching rows, in table order
+    Collect,
+    /// The matching row with the highest `priority`; ties go to the earlier row
+    Priority,
+}
+
+impl fmt::Display for HitPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            HitPolicy::First => "first",
+            HitPolicy::Unique => "unique",
+            HitPolicy::Collect => "collect",
+            HitPolicy::Priority => "priority",
+        })
+    }
+}
+
+impl FromStr for HitPolicy {
+    type Err = RuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "first" => Ok(HitPolicy::First),
+            "unique" => Ok(HitPolicy::Unique),
+            "collect" => Ok(HitPolicy::Collect),
+            "priority" => Ok(HitPolicy::Priority),
+            other => Err(RuleError::InvalidExpression(format!(
+                "Unknown hit policy '{}' (expected first, unique, collect or priority)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Serialized form of a decision table, as written in YAML or inline in a
+/// graph definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecisionTableDef {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub hit_policy: HitPolicy,
+    /// Column headers, see the [module documentation](self)
+    pub columns: Vec<String>,
+    /// Cells of every row, in column order
+    pub rows: Vec<Vec<JsonValue>>,
+}
+
+/// Test applied to one input cell
+#[derive(Debug, Clone, PartialEq)]
+enum CellTest {
+    Any,
+    Compare(Comparison, JsonValue),
+    /// Inclusive range
+    Range(f64, f64),
+    OneOf(Vec<JsonValue>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    tests: Vec<CellTest>,
+    outputs: Vec<Option<JsonValue>>,
+    priority: i64,
+}
+
+/// A compiled decision table
+///
+/// ```
+/// use rust_logic_graph::rule::DecisionTable;
+/// use serde_json::json;
+/// use std::collections::HashMap;
+///
+/// let table = DecisionTable::from_csv(
+///     "discount",
+///     "tier,qty,out:discount\ngold,>= 100,0.15\ngold,,0.1\n",
+/// )
+/// .unwrap();
+///
+/// let facts = HashMap::from([
+///     ("tier".to_string(), json!("gold")),
+///     ("qty".to_string(), json!(20)),
+/// ]);
+/// assert_eq!(table.evaluate(&facts).unwrap(), json!({"discount": 0.1}));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecisionTable {
+    name: String,
+    hit_policy: HitPolicy,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    rows: Vec<Row>,
+    has_priority: bool,
+}
+
+impl DecisionTable {
+    /// Compile a table definition
+    pub fn from_def(def: &DecisionTableDef) -> Result<Self, RuleError> {
+        let name = def
+            .name
+            .clone()
+            .unwrap_or_else(|| "decision_table".to_string());
+        let invalid = |message: String| {
+            RuleError::InvalidExpression(format!("Decision table '{}': {}", name, message))
+        };
+
+        enum Column {
+            Input,
+            Output,
+            Priority,
+        }
+        let mut columns = Vec::new();
+        let (mut inputs, mut outputs) = (Vec::new(), Vec::new());
+        for header in &def.columns {
+            let header = header.trim();
+            if let Some(output) = header.strip_prefix("out:") {
+                outputs.push(output.trim().to_string());
+                columns.push(Column::Output);
+            } else if header.eq_ignore_ascii_case("priority") {
+                columns.push(Column::Priority);
+            } else {
+                let input = header.strip_prefix("in:").unwrap_or(header).trim();
+                if input.is_empty() {
+                    return Err(invalid("empty column header".to_string()));
+                }
+                inputs.push(input.to_string());
+                columns.push(Column::Input);
+            }
+        }
+        if outputs.is_empty() {
+            return Err(invalid(
+                "no output columns (mark them with 'out:')".to_string(),
+            ));
+        }
+        let has_priority = columns.iter().any(|c| matches!(c, Column::Priority));
+        if def.hit_policy == HitPolicy::Priority && !has_priority {
+            return Err(invalid(
+                "hit policy 'priority' needs a 'priority' column".to_string(),
+            ));
+        }
+
+        let mut rows = Vec::new();
+        for (index, cells) in def.rows.iter().enumerate() {
+            let row_error = |message: String| invalid(format!("row {}: {}", index + 1, message));
+            if cells.len() != columns.len() {
+                return Err(row_error(format!(
+                    "has {} cells, expected {}",
+                    cells.len(),
+                    columns.len()
+                )));
+            }
+            let mut row = Row {
+                tests: Vec::new(),
+                outputs: Vec::new(),
+                priority: 0,
+            };
+            for (cell, column) in cells.iter().zip(&columns) {
+                match column {
+                    Column::Input => row.tests.push(parse_test(cell).map_err(row_error)?),
+                    Column::Output => row.outputs.push(parse_output(cell)),
+                    Column::Priority => {
+                        row.priority = match parse_output(cell) {
+                            None => 0,
+                            Some(value) => value.as_i64().ok_or_else(|| {
+                                row_error(format!("priority '{}' is not an integer", value))
+                            })?,
+                        }
+                    }
+                }
+            }
+            rows.push(row);
+        }
+
+        Ok(Self {
+            name,
+            hit_policy: def.hit_policy,
+            inputs,
+            outputs,
+            rows,
+            has_priority,
+        })
+    }
+
+    /// Parse a YAML table definition
+    pub fn from_yaml(yaml: &str) -> Result<Self, RuleError> {
+        let def: DecisionTableDef = serde_yaml::from_str(yaml)
+            .map_err(|e| RuleError::Configuration(format!("Invalid decision table: {}", e)))?;
+        Self::from_def(&def)
+    }
+
+    /// Parse a CSV table whose first line holds the column headers; the hit
+    /// policy is `first` unless changed with [`with_hit_policy`](Self::with_hit_policy)
+    pub fn from_csv(name: impl Into<String>, csv: &str) -> Result<Self, RuleError> {
+        let mut lines = parse_csv(csv).into_iter();
+        let columns = lines.next().unwrap_or_default();
+        let rows = lines
+            .map(|cells| cells.into_iter().map(JsonValue::String).collect())
+            .collect();
+        Self::from_def(&DecisionTableDef {
+            name: Some(name.into()),
+            hit_policy: HitPolicy::First,
+            columns,
+            rows,
+        })
+    }
+
+    /// Load a `.csv` table, or a YAML table from any other file. Tables
+    /// without a name are named after the file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RuleError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RuleError::Eval(format!(
+                "Failed to read decision table '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if path.extension().is_some_and(|ext| ext == "csv") {
+            return Self::from_csv(name, &content);
+        }
+        let mut def: DecisionTableDef = serde_yaml::from_str(&content).map_err(|e| {
+            RuleError::Configuration(format!(
+                "Invalid decision table '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        def.name.get_or_insert(name);
+        Self::from_def(&def)
+    }
+
+    /// Use a different hit policy
+    pub fn with_hit_policy(mut self, hit_policy: HitPolicy) -> Result<Self, RuleError> {
+        if hit_policy == HitPolicy::Priority && !self.has_priority {
+            return Err(RuleError::InvalidExpression(format!(
+                "Decision table '{}': hit policy 'priority' needs a 'priority' column",
+                self.name
+            )));
+        }
+        self.hit_policy = hit_policy;
+        Ok(self)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn hit_policy(&self) -> HitPolicy {
+        self.hit_policy
+    }
+
+    /// Facts the table reads
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Facts the table sets
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Indices of the rows whose tests all pass, in table order
+    pub fn matching_rows(&self, facts: &HashMap<String, JsonValue>) -> Vec<usize> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                row.tests
+                    .iter()
+                    .zip(&self.inputs)
+                    .all(|(test, input)| test.matches(path::get(facts, input)))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Evaluate the table against `facts`.
+    ///
+    /// Returns an object with the output values of the selected row, or
+    /// `null` when no row matched. With the `collect` policy, every output is
+    /// an array (possibly empty) of the values of all matching rows.
+    pub fn evaluate(&self, facts: &HashMap<String, JsonValue>) -> Result<JsonValue, RuleError> {
+        let matching = self.matching_rows(facts);
+        let selected = match self.hit_policy {
+            HitPolicy::Collect => {
+                let mut result = Map::new();
+                for (column, output) in self.outputs.iter().enumerate() {
+                    let values = matching
+                        .iter()
+                        .filter_map(|&row| self.rows[row].outputs[column].clone())
+                        .collect();
+                    result.insert(output.clone(), JsonValue::Array(values));
+                }
+                return Ok(JsonValue::Object(result));
+            }
+            HitPolicy::First => matching.first().copied(),
+            HitPolicy::Unique => match matching.as_slice() {
+                [] => None,
+                [row] => Some(*row),
+                [first, second, ..] => {
+                    return Err(RuleError::Configuration(format!(
+                    "Decision table '{}': rows {} and {} both match, but the hit policy is unique",
+                    self.name,
+                    first + 1,
+                    second + 1
+                )))
+                }
+            },
+            HitPolicy::Priority => matching
+                .iter()
+                .copied()
+                .rev()
+                .max_by_key(|&row| self.rows[row].priority),
+        };
+
+        Ok(match selected {
+            None => JsonValue::Null,
+            Some(row) => JsonValue::Object(
+                self.outputs
+                    .iter()
+                    .zip(&self.rows[row].outputs)
+                    .filter_map(|(output, value)| Some((output.clone(), value.clone()?)))
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Compile the table to GRL rules, one per row, for loading into a
+    /// [`RuleEngine`](super::RuleEngine).
+    ///
+    /// Each row's rule excludes the rows that take precedence over it, so
+    /// `first`, `priority` and `unique` tables give the same result as
+    /// [`evaluate`](Self::evaluate); a `unique` table whose rows overlap
+    /// behaves like `first` instead of failing. `collect` tables can't be
+    /// expressed in GRL.
+    pub fn to_grl(&self) -> Result<String, RuleError> {
+        let error = |message: String| {
+            RuleError::Configuration(format!("Decision table '{}': {}", self.name, message))
+        };
+        if self.hit_policy == HitPolicy::Collect {
+            return Err(error(
+                "tables with the collect hit policy can't be compiled to GRL".to_string(),
+            ));
+        }
+
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        if self.hit_policy == HitPolicy::Priority {
+            order.sort_by_key(|&row| std::cmp::Reverse(self.rows[row].priority));
+        }
+
+        let mut grl = String::new();
+        let mut preceding: Vec<String> = Vec::new();
+        for (rank, &index) in order.iter().enumerate() {
+            let row = &self.rows[index];
+            let mut tests = Vec::new();
+            for (test, input) in row.tests.iter().zip(&self.inputs) {
+                if let Some(condition) = test.to_grl(input).map_err(&error)? {
+                    tests.push(condition);
+                }
+            }
+            let own = tests.join(" && ");
+
+            let mut conditions: Vec<String> = Vec::new();
+            if !own.is_empty() {
+                conditions.push(own.clone());
+            }
+            conditions.extend(
+                preceding
+                    .iter()
+                    .map(|condition| format!("!({})", condition)),
+            );
+            if conditions.is_empty() {
+                return Err(error(format!(
+                    "row {} matches everything and can only be compiled to GRL after other rows",
+                    index + 1
+                )));
+            }
+
+            let mut actions = String::new();
+            for (output, value) in self.outputs.iter().zip(&row.outputs) {
+                if let Some(value) = value {
+                    actions.push_str(&format!(
+                        "        {} = {};\n",
+                        output,
+                        grl_literal(value).map_err(&error)?
+                    ));
+                }
+            }
+
+            grl.push_str(&format!(
+                "rule \"{} row {}\" salience {} no-loop {{\n    when\n        {}\n    then\n{}}}\n\n",
+                self.name,
+                index + 1,
+                order.len() - rank,
+                conditions.join(" && "),
+                actions
+            ));
+
+            if own.is_empty() {
+                // Nothing after a catch-all row can match
+                break;
+            }
+            preceding.push(own);
+        }
+        Ok(grl)
+    }
+}
+
+impl CellTest {
+    fn matches(&self, actual: Option<&JsonValue>) -> bool {
+        let Some(actual) = actual else {
+            return *self == CellTest::Any;
+        };
+        match self {
+            CellTest::Any => true,
+            CellTest::Compare(comparison, expected) => compare(*comparison, actual, expected),
+            CellTest::Range(low, high) => actual.as_f64().is_some_and(|n| *low <= n && n <= *high),
+            CellTest::OneOf(options) => options
+                .iter()
+                .any(|option| compare(Comparison::Eq, actual, option)),
+        }
+    }
+
+    /// GRL condition for this test on `fact`; `None` when it matches anything
+    fn to_grl(&self, fact: &str) -> Result<Option<String>, String> {
+        Ok(match self {
+            CellTest::Any => None,
+            CellTest::Compare(comparison, value) => Some(format!(
+                "({} {} {})",
+                fact,
+                comparison.symbol(),
+                grl_literal(value)?
+            )),
+            CellTest::Range(low, high) => {
+                Some(format!("({} >= {}) && ({} <= {})", fact, low, fact, high))
+            }
+            CellTest::OneOf(options) => {
+                let options = options
+                    .iter()
+                    .map(|option| Ok(format!("({} == {})", fact, grl_literal(option)?)))
+                    .collect::<Result<Vec<_>, String>>()?;
+                Some(format!("({})", options.join(" || ")))
+            }
+        })
+    }
+}
+
+fn compare(comparison: Comparison, actual: &JsonValue, expected: &JsonValue) -> bool {
+    use std::cmp::Ordering;
+
+    let ordering = match (actual, expected) {
+        (JsonValue::Number(a), JsonValue::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (JsonValue::String(a), JsonValue::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match comparison {
+        Comparison::Eq => ordering.map_or(actual == expected, Ordering::is_eq),
+        Comparison::Ne => ordering.map_or(actual != expected, Ordering::is_ne),
+        Comparison::Lt => ordering.is_some_and(Ordering::is_lt),
+        Comparison::Le => ordering.is_some_and(Ordering::is_le),
+        Comparison::Gt => ordering.is_some_and(Ordering::is_gt),
+        Comparison::Ge => ordering.is_some_and(Ordering::is_ge),
+    }
+}
+
+fn parse_test(cell: &JsonValue) -> Result<CellTest, String> {
+    let text = match cell {
+        JsonValue::Null => return Ok(CellTest::Any),
+        JsonValue::String(text) => text.trim(),
+        other => return Ok(CellTest::Compare(Comparison::Eq, other.clone())),
+    };
+    if text.is_empty() || text == "-" {
+        return Ok(CellTest::Any);
+    }
+
+    for (symbol, comparison) in [
+        ("==", Comparison::Eq),
+        ("!=", Comparison::Ne),
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ] {
+        if let Some(rest) = text.strip_prefix(symbol) {
+            let value = parse_literal(rest.trim());
+            if !matches!(comparison, Comparison::Eq | Comparison::Ne) && !value.is_number() {
+                return Err(format!("'{}' compares with a non-number", text));
+            }
+            return Ok(CellTest::Compare(comparison, value));
+        }
+    }
+
+    if let Some((low, high)) = text.split_once("..") {
+        if let (Ok(low), Ok(high)) = (low.trim().parse(), high.trim().parse()) {
+            return Ok(CellTest::Range(low, high));
+        }
+    }
+
+    let options = split_list(text);
+    if options.len() > 1 {
+        return Ok(CellTest::OneOf(
+            options.iter().map(|option| parse_literal(option)).collect(),
+        ));
+    }
+    Ok(CellTest::Compare(Comparison::Eq, parse_literal(text)))
+}
+
+fn parse_output(cell: &JsonValue) -> Option<JsonValue> {
+    match cell {
+        JsonValue::Null => None,
+        JsonValue::String(text) if text.trim().is_empty() => None,
+        JsonValue::String(text) => Some(parse_literal(text.trim())),
+        other => Some(other.clone()),
+    }
+}
+
+/// A number, boolean, quoted string, or else the text itself
+fn parse_literal(text: &str) -> JsonValue {
+    for quote in ['"', '\''] {
+        if let Some(inner) = text
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return JsonValue::String(inner.to_string());
+        }
+    }
+    match text {
+        "true" => JsonValue::Bool(true),
+        "false" => JsonValue::Bool(false),
+        _ => serde_json::from_str::<serde_json::Number>(text)
+            .map(JsonValue::Number)
+            .unwrap_or_else(|_| JsonValue::String(text.to_string())),
+    }
+}
+
+/// Split on commas outside quotes
+fn split_list(text: &str) -> Vec<String> {
+    let mut items = vec![String::new()];
+    let mut quote = None;
+    for c in text.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (',', None) => {
+                items.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        items.last_mut().unwrap().push(c);
+    }
+    items.iter().map(|item| item.trim().to_string()).collect()
+}
+
+fn grl_literal(value: &JsonValue) -> Result<String, String> {
+    match value {
+        JsonValue::Number(n) => Ok(n.to_string()),
+        JsonValue::Bool(b) => Ok(b.to_string()),
+        JsonValue::String(s) if !s.contains(['"', '\\']) => Ok(format!("\"{}\"", s)),
+        other => Err(format!("value {} can't be written in GRL", other)),
+    }
+}
+
+/// Rows of a CSV document (RFC 4180: quoted cells may hold commas, line
+/// breaks and doubled quotes); blank lines are skipped
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                quoted = true;
+            }
+            ',' if !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                if row.iter().any(|cell| !cell.trim().is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            c => cell.push(c),
+        }
+    }
+    row.push(cell);
+    if row.iter().any(|cell| !cell.trim().is_empty()) {
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::RuleEngine;
+    use serde_json::json;
+
+    const DISCOUNTS: &str = "\
+supplier_tier,order_qty,out:discount,out:label
+gold,>= 100,0.15,gold bulk
+gold,,0.1,gold
+\"silver, bronze\",10..99,0.05,
+-,-,0,none
+";
+
+    fn facts(tier: &str, qty: i64) -> HashMap<String, JsonValue> {
+        HashMap::from([
+            ("supplier_tier".to_string(), json!(tier)),
+            ("order_qty".to_string(), json!(qty)),
+        ])
+    }
+
+    #[test]
+    fn test_first_hit() {
+        let table = DecisionTable::from_csv("discounts", DISCOUNTS).unwrap();
+        assert_eq!(table.inputs(), ["supplier_tier", "order_qty"]);
+        assert_eq!(table.outputs(), ["discount", "label"]);
+
+        assert_eq!(
+            table.evaluate(&facts("gold", 150)).unwrap(),
+            json!({"discount": 0.15, "label": "gold bulk"})
+        );
+        assert_eq!(
+            table.evaluate(&facts("bronze", 50)).unwrap(),
+            json!({"discount": 0.05})
+        );
+        assert_eq!(
+            table.evaluate(&facts("silver", 500)).unwrap(),
+            json!({"discount": 0, "label": "none"})
+        );
+    }
+
+    #[test]
+    fn test_unique_collect_and_priority() {
+        let table = DecisionTable::from_csv("discounts", DISCOUNTS).unwrap();
+
+        let unique = table.clone().with_hit_policy(HitPolicy::Unique).unwrap();
+        assert_eq!(
+            unique.evaluate(&facts("bronze", 50)).unwrap_err().to_string(),
+            "Configuration error: Decision table 'discounts': rows 3 and 4 both match, but the hit policy is unique"
+        );
+
+        let collect = table.with_hit_policy(HitPolicy::Collect).unwrap();
+        assert_eq!(
+            collect.evaluate(&facts("gold", 150)).unwrap(),
+            json!({"discount": [0.15, 0.1, 0], "label": ["gold bulk", "gold", "none"]})
+        );
+
+        let priority = DecisionTable::from_yaml(
+            r#"
+name: risk
+hit_policy: priority
+columns: [amount, country, "out:review", priority]
+rows:
+  - [">= 1000", "-", standard, 1]
+  - ["-", "XX, YY", manual, 10]
+  - [">= 1000", "XX", blocked, 10]
+"#,
+        )
+        .unwrap();
+        let order = |amount: i64, country: &str| {
+            HashMap::from([
+                ("amount".to_string(), json!(amount)),
+                ("country".to_string(), json!(country)),
+            ])
+        };
+        assert_eq!(
+            priority.evaluate(&order(5000, "XX")).unwrap(),
+            json!({"review": "manual"})
+        );
+        assert_eq!(
+            priority.evaluate(&order(5000, "DE")).unwrap(),
+            json!({"review": "standard"})
+        );
+        assert_eq!(priority.evaluate(&order(5, "DE")).unwrap(), json!(null));
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        let err = DecisionTable::from_csv("t", "a,b\n1,2\n").unwrap_err();
+        assert!(err.to_string().contains("no output columns"));
+
+        let err = DecisionTable::from_csv("t", "a,out:b\n1,2,3\n").unwrap_err();
+        assert!(err.to_string().contains("row 1: has 3 cells, expected 2"));
+
+        let err = DecisionTable::from_csv("t", "a,out:b\n> gold,2\n").unwrap_err();
+        assert!(err.to_string().contains("compares with a non-number"));
+
+        let table = DecisionTable::from_csv("t", "a,out:b\n1,2\n").unwrap();
+        assert!(table.with_hit_policy(HitPolicy::Priority).is_err());
+    }
+
+    #[test]
+    fn test_grl_matches_evaluate() {
+        let table = DecisionTable::from_csv("discounts", DISCOUNTS).unwrap();
+        let grl = table.to_grl().unwrap();
+
+        for (tier, qty) in [("gold", 150), ("gold", 5), ("bronze", 50), ("silver", 500)] {
+            let mut engine = RuleEngine::from_grl(&grl).unwrap();
+            let result = engine.evaluate(&facts(tier, qty)).unwrap();
+            let expected = table.evaluate(&facts(tier, qty)).unwrap();
+            for (output, value) in expected.as_object().unwrap() {
+                assert_eq!(
+                    result[output]
+                        .as_f64()
+                        .map(JsonValue::from)
+                        .unwrap_or(result[output].clone()),
+                    value.as_f64().map(JsonValue::from).unwrap_or(value.clone()),
+                    "{} for {} {}",
+                    output,
+                    tier,
+                    qty
+                );
+            }
+        }
+
+        let collect = table.with_hit_policy(HitPolicy::Collect).unwrap();
+        assert!(collect.to_grl().is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_quotes() {
+        assert_eq!(
+            parse_csv("a,\"b, c\",\"say \"\"hi\"\"\"\r\n\n1,2,3"),
+            vec![
+                vec![
+                    "a".to_string(),
+                    "b, c".to_string(),
+                    "say \"hi\"".to_string()
+                ],
+                vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            ]
+        );
+    }
+}
@@ -9,7 +9,9 @@ use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use tracing::debug;
 
-use super::{RuleError, RuleResult};
+use super::trace::{condition_matches, fact_changes, FiredRule, RuleTrace, UnfiredRule};
+use super::{DecisionTable, RuleError, RuleResult};
+use rust_rule_engine::engine::rule::Rule as GrlRule;
 
 /// Convenience wrapper around RustRuleEngine with JSON integration
 ///
@@ -66,6 +68,14 @@ impl RuleEngine {
         }
     }
 
+    /// Create a rule engine running the rules of `knowledge_base`, with fresh
+    /// execution state
+    pub fn from_knowledge_base(knowledge_base: KnowledgeBase) -> Self {
+        Self {
+            engine: RustRuleEngine::new(knowledge_base),
+        }
+    }
+
     /// Add rules from GRL syntax
     ///
     /// # Example
@@ -140,8 +150,18 @@ impl RuleEngine {
         Ok(())
     }
 
+    /// Add the rows of a decision table as GRL rules, see
+    /// [`DecisionTable::to_grl`]
+    pub fn add_decision_table(&mut self, table: &DecisionTable) -> Result<(), RuleError> {
+        self.add_grl_rule(&table.to_grl()?)
+    }
+
     /// Evaluate rules with JSON context (convenience method)
     ///
+    /// Context values are converted with [`json_to_fact`], nested objects and
+    /// arrays included. The result holds every fact after execution, converted
+    /// back with [`fact_to_json`].
+    ///
     /// For more control, use `inner()` or `inner_mut()` to access the
     /// underlying RustRuleEngine directly.
     ///
@@ -160,62 +180,75 @@ impl RuleEngine {
     /// let result = engine.evaluate(&context).unwrap();
     /// ```
     pub fn evaluate(&mut self, context: &HashMap<String, JsonValue>) -> RuleResult {
-        // Convert JSON context to Facts
-        let facts = Facts::new();
-
-        for (key, value) in context {
-            let rr_value = match value {
-                JsonValue::Bool(b) => Value::Boolean(*b),
-                JsonValue::Number(n) => {
-                    if let Some(f) = n.as_f64() {
-                        Value::Number(f)
-                    } else {
-                        continue;
-                    }
-                }
-                JsonValue::String(s) => Value::String(s.clone()),
-                _ => {
-                    debug!("Skipping unsupported value type for key: {}", key);
-                    continue;
-                }
-            };
-
-            facts.set(&key, rr_value);
-        }
+        let facts = to_facts(context);
 
-        // Execute rules
         match self.engine.execute(&facts) {
             Ok(_) => {
                 debug!("Rules executed successfully");
-
-                // Helper to convert Value to JsonValue
-                let convert_value = |val: &Value| -> Option<JsonValue> {
-                    match val {
-                        Value::Boolean(b) => Some(JsonValue::Bool(*b)),
-                        Value::Number(n) => Some(JsonValue::from(*n)),
-                        Value::String(s) => Some(JsonValue::String(s.clone())),
-                        Value::Integer(i) => Some(JsonValue::from(*i)),
-                        _ => None,
-                    }
-                };
-
-                // Get ALL facts from the engine after rule execution
-                // This captures all values set by rules (including Expression Evaluation results)
-                let all_facts = facts.get_all_facts();
-
-                let mut result = HashMap::new();
-                for (key, value) in all_facts {
-                    if let Some(json_value) = convert_value(&value) {
-                        result.insert(key, json_value);
-                    }
-                }
-
-                Ok(JsonValue::Object(result.into_iter().collect()))
+                Ok(facts_to_json(&facts))
             }
             Err(e) => Err(RuleError::Eval(format!("Rule execution failed: {}", e))),
         }
     }
 
+    /// Evaluate rules like [`RuleEngine::evaluate`], also returning a
+    /// [`RuleTrace`] of the rules that fired and the facts they changed
+    pub fn evaluate_traced(
+        &mut self,
+        context: &HashMap<String, JsonValue>,
+    ) -> Result<(JsonValue, RuleTrace), RuleError> {
+        let facts = to_facts(context);
+        let rules: HashMap<String, GrlRule> = self
+            .engine
+            .knowledge_base()
+            .get_rules()
+            .into_iter()
+            .map(|rule| (rule.name.clone(), rule))
+            .collect();
+
+        let mut fired = Vec::new();
+        let mut previous = facts.get_all_facts();
+        let outcome = self.engine.execute_with_callback(&facts, |name, facts| {
+            let current = facts.get_all_facts();
+            let changes = fact_changes(&previous, &current);
+            // Rules re-fire every cycle while their conditions hold; only
+            // list repeat firings that changed something
+            let repeat = fired.iter().any(|f: &FiredRule| f.rule == name);
+            if let (Some(rule), false) = (rules.get(name), repeat && changes.is_empty()) {
+                fired.push(FiredRule {
+                    rule: name.to_string(),
+                    salience: rule.salience,
+                    // Conditions matched the facts as they were before firing
+                    conditions: condition_matches(rule, &previous),
+                    changes,
+                });
+            }
+            previous = current;
+        });
+        let outcome =
+            outcome.map_err(|e| RuleError::Eval(format!("Rule execution failed: {}", e)))?;
+
+        let mut not_fired: Vec<UnfiredRule> = rules
+            .values()
+            .filter(|rule| !fired.iter().any(|f: &FiredRule| f.rule == rule.name))
+            .map(|rule| UnfiredRule {
+                rule: rule.name.clone(),
+                salience: rule.salience,
+                conditions: condition_matches(rule, &previous),
+            })
+            .collect();
+        not_fired.sort_by(|a, b| b.salience.cmp(&a.salience).then(a.rule.cmp(&b.rule)));
+
+        let trace = RuleTrace {
+            fired,
+            not_fired,
+            rules_evaluated: outcome.rules_evaluated,
+            rules_fired: outcome.rules_fired,
+            cycles: outcome.cycle_count,
+        };
+        Ok((facts_to_json(&facts), trace))
+    }
+
     /// Create a rule engine from GRL script
     ///
     /// # Example
@@ -258,6 +291,69 @@ impl RuleEngine {
     }
 }
 
+fn to_facts(context: &HashMap<String, JsonValue>) -> Facts {
+    let facts = Facts::new();
+    for (key, value) in context {
+        facts.set(key, json_to_fact(value));
+    }
+    facts
+}
+
+/// Every fact after execution, including the ones the rules set
+fn facts_to_json(facts: &Facts) -> JsonValue {
+    JsonValue::Object(
+        facts
+            .get_all_facts()
+            .iter()
+            .map(|(key, value)| (key.clone(), fact_to_json(value)))
+            .collect(),
+    )
+}
+
+/// Convert a JSON value into a rule engine value.
+///
+/// Integers stay integers, floats become numbers, and arrays and objects are
+/// converted recursively, so rules can read nested fields such as
+/// `order.customer.tier` and apply multifield operations (`order.items count`)
+/// to arrays.
+pub fn json_to_fact(value: &JsonValue) -> Value {
+    match value {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(b) => Value::Boolean(*b),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Number(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        JsonValue::String(s) => Value::String(s.clone()),
+        JsonValue::Array(items) => Value::Array(items.iter().map(json_to_fact).collect()),
+        JsonValue::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), json_to_fact(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Convert a rule engine value back into JSON; the inverse of [`json_to_fact`].
+///
+/// Unevaluated expressions are returned as their source text, and numbers
+/// that JSON cannot represent (NaN, infinity) become `null`.
+pub fn fact_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Null => JsonValue::Null,
+        Value::Boolean(b) => JsonValue::Bool(*b),
+        Value::Integer(i) => JsonValue::from(*i),
+        Value::Number(n) => JsonValue::from(*n),
+        Value::String(s) | Value::Expression(s) => JsonValue::String(s.clone()),
+        Value::Array(items) => JsonValue::Array(items.iter().map(fact_to_json).collect()),
+        Value::Object(map) => JsonValue::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), fact_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
 impl Default for RuleEngine {
     fn default() -> Self {
         Self::new()
@@ -374,6 +470,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nested_values_round_trip() {
+        let grl = r#"
+        rule "gold_order" {
+            when
+                order.customer.tier == "gold" && order.total > 100
+            then
+                discount = 0.2;
+        }
+        rule "multi_item" {
+            when
+                order.items count >= 2
+            then
+                bulk = true;
+        }
+        "#;
+        let mut engine = RuleEngine::from_grl(grl).unwrap();
+
+        let order = json!({
+            "customer": {"tier": "gold", "tags": ["vip", null]},
+            "items": [{"sku": "A", "qty": 3}, {"sku": "B", "qty": 1.5}],
+            "total": 150,
+        });
+        let context = HashMap::from([
+            ("order".to_string(), order.clone()),
+            ("note".to_string(), JsonValue::Null),
+        ]);
+
+        let result = engine.evaluate(&context).unwrap();
+        assert_eq!(result["discount"], json!(0.2));
+        assert_eq!(result["bulk"], json!(true));
+        assert_eq!(result["order"], order);
+        assert_eq!(result["note"], JsonValue::Null);
+    }
+
+    #[test]
+    fn test_evaluate_traced() {
+        let grl = r#"
+        rule "needs_reorder" salience 100 {
+            when
+                stock < 10
+            then
+                reorder = true;
+                shortage = 10 - stock;
+        }
+        rule "expedite" salience 50 {
+            when
+                reorder == true && supplier == "slow"
+            then
+                expedite = true;
+        }
+        rule "discontinued" salience 10 {
+            when
+                discontinued == true
+            then
+                reorder = false;
+        }
+        "#;
+        let mut engine = RuleEngine::from_grl(grl).unwrap();
+        let context = HashMap::from([
+            ("stock".to_string(), json!(4)),
+            ("supplier".to_string(), json!("slow")),
+            ("discontinued".to_string(), json!(false)),
+        ]);
+
+        let (facts, trace) = engine.evaluate_traced(&context).unwrap();
+        assert_eq!(facts["expedite"], json!(true));
+
+        let fired: Vec<&str> = trace.fired.iter().map(|f| f.rule.as_str()).collect();
+        assert_eq!(fired, ["needs_reorder", "expedite"]);
+        assert_eq!(trace.fired[0].salience, 100);
+        assert!(trace.rules_fired >= 2);
+
+        let condition = &trace.fired[0].conditions[0];
+        assert_eq!(
+            (condition.field.as_str(), condition.operator.as_str()),
+            ("stock", "<")
+        );
+        assert_eq!(
+            (condition.expected.clone(), condition.actual.clone()),
+            (json!(10), json!(4))
+        );
+
+        let reorder = trace.fired[0]
+            .changes
+            .iter()
+            .find(|c| c.fact == "reorder")
+            .unwrap();
+        assert_eq!(
+            (reorder.before.clone(), reorder.after.clone()),
+            (None, Some(json!(true)))
+        );
+
+        assert_eq!(trace.not_fired.len(), 1);
+        assert_eq!(trace.not_fired[0].rule, "discontinued");
+        assert_eq!(trace.not_fired[0].conditions[0].actual, json!(false));
+
+        let report = trace.to_string();
+        assert!(report.contains("2 of 3 rules fired"));
+        assert!(report.contains("not fired \"discontinued\""));
+    }
+
     #[test]
     fn test_direct_engine_access() {
         let engine = RuleEngine::new();
@@ -0,0 +1,667 @@
+//! Condition expression language used by [`Rule`](super::Rule).
+//!
+//! Expressions are tokenized and parsed into an [`Expr`] tree once, then
+//! evaluated against context data. Supported syntax, from lowest to highest
+//! precedence:
+//!
+//! | Operators                              | Operands               |
+//! |----------------------------------------|------------------------|
+//! | `\|\|`                                 | booleans               |
+//! | `&&`                                   | booleans               |
+//! | `==`, `!=`                             | any                    |
+//! | `<`, `<=`, `>`, `>=`, `in`, `contains` | numbers, strings, arrays, objects |
+//! | `+`, `-`                               | numbers (`+` also joins strings) |
+//! | `*`, `/`, `%`                          | numbers                |
+//! | `!`, unary `-`                         | boolean / number       |
+//!
+//! Operands are literals (`42`, `1.5`, `"text"`, `'text'`, `true`, `false`,
+//! `null`, `[1, 2]`), context paths (`order.items[0].qty`), parenthesized
+//! expressions and the functions `len`, `lower`, `upper`, `trim`,
+//! `starts_with` and `ends_with`.
+
+use serde_json::{Number, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use super::{RuleError, RuleResult};
+use crate::core::path;
+
+/// Unary operators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+/// Binary operators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    Contains,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::Eq | BinaryOp::Ne => 3,
+            BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge
+            | BinaryOp::In
+            | BinaryOp::Contains => 4,
+            BinaryOp::Add | BinaryOp::Sub => 5,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 6,
+        }
+    }
+}
+
+/// Built-in functions with their arity
+const FUNCTIONS: &[(&str, usize)] = &[
+    ("len", 1),
+    ("lower", 1),
+    ("upper", 1),
+    ("trim", 1),
+    ("starts_with", 2),
+    ("ends_with", 2),
+];
+
+/// Parsed condition expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    /// Context key or path
+    Var(String),
+    Array(Vec<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+impl Expr {
+    /// Parse `source`, returning [`RuleError::InvalidExpression`] on any
+    /// syntax error
+    pub fn parse(source: &str) -> Result<Expr, RuleError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            source,
+            tokens,
+            pos: 0,
+        };
+        let expr = parser.expression(0)?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some((_, at)) => Err(parser.error("unexpected token", *at)),
+        }
+    }
+
+    /// Evaluate against context data
+    pub fn eval(&self, context: &HashMap<String, Value>) -> RuleResult {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Var(name) => path::get(context, name)
+                .cloned()
+                .ok_or_else(|| RuleError::MissingVariable(name.clone())),
+            Expr::Array(items) => items
+                .iter()
+                .map(|item| item.eval(context))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            Expr::Unary(op, operand) => {
+                let value = operand.eval(context)?;
+                match (op, &value) {
+                    (UnaryOp::Not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+                    (UnaryOp::Neg, Value::Number(_)) => {
+                        arithmetic(BinaryOp::Sub, &Value::from(0), &value)
+                    }
+                    (UnaryOp::Not, _) => Err(type_mismatch("'!' expects a boolean", &value)),
+                    (UnaryOp::Neg, _) => Err(type_mismatch("'-' expects a number", &value)),
+                }
+            }
+            Expr::Binary(BinaryOp::And, left, right) => Ok(Value::Bool(
+                as_bool(&left.eval(context)?, "&&")? && { as_bool(&right.eval(context)?, "&&")? },
+            )),
+            Expr::Binary(BinaryOp::Or, left, right) => Ok(Value::Bool(
+                as_bool(&left.eval(context)?, "||")? || { as_bool(&right.eval(context)?, "||")? },
+            )),
+            Expr::Binary(op, left, right) => {
+                let left = left.eval(context)?;
+                let right = right.eval(context)?;
+                binary(*op, &left, &right)
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.eval(context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(name, &args)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(Value),
+    Ident(String),
+    Op(BinaryOp),
+    Not,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+fn invalid(source: &str, message: &str, at: usize) -> RuleError {
+    RuleError::InvalidExpression(format!("{} at position {} in '{}'", message, at, source))
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, RuleError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        let two_char_op = match two.as_str() {
+            "&&" => Some(Token::Op(BinaryOp::And)),
+            "||" => Some(Token::Op(BinaryOp::Or)),
+            "==" => Some(Token::Op(BinaryOp::Eq)),
+            "!=" => Some(Token::Op(BinaryOp::Ne)),
+            "<=" => Some(Token::Op(BinaryOp::Le)),
+            ">=" => Some(Token::Op(BinaryOp::Ge)),
+            _ => None,
+        };
+        if let Some(token) = two_char_op {
+            tokens.push((token, start));
+            i += 2;
+            continue;
+        }
+
+        let token = match c {
+            '<' => Token::Op(BinaryOp::Lt),
+            '>' => Token::Op(BinaryOp::Gt),
+            '+' => Token::Op(BinaryOp::Add),
+            '-' => Token::Op(BinaryOp::Sub),
+            '*' => Token::Op(BinaryOp::Mul),
+            '/' => Token::Op(BinaryOp::Div),
+            '%' => Token::Op(BinaryOp::Rem),
+            '!' => Token::Not,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '"' | '\'' => {
+                let (text, end) = string_literal(source, &chars, i)?;
+                i = end;
+                tokens.push((Token::Literal(Value::String(text)), start));
+                continue;
+            }
+            c if c.is_ascii_digit() => {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = number_literal(&text)
+                    .ok_or_else(|| invalid(source, "invalid number", start))?;
+                tokens.push((Token::Literal(Value::Number(number)), start));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                i = identifier_end(&chars, i);
+                let text: String = chars[start..i].iter().collect();
+                let token = match text.as_str() {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "null" => Token::Literal(Value::Null),
+                    "in" => Token::Op(BinaryOp::In),
+                    "contains" => Token::Op(BinaryOp::Contains),
+                    _ => Token::Ident(text),
+                };
+                tokens.push((token, start));
+                continue;
+            }
+            _ => return Err(invalid(source, &format!("unexpected character '{}'", c), i)),
+        };
+        tokens.push((token, start));
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// End of an identifier or path such as `order.items[0].qty` starting at `i`
+fn identifier_end(chars: &[char], mut i: usize) -> usize {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    loop {
+        while i < chars.len() && is_ident(chars[i]) {
+            i += 1;
+        }
+        match chars.get(i) {
+            Some('.') if chars.get(i + 1).is_some_and(|&c| is_ident(c)) => i += 1,
+            Some('[') => {
+                let digits = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                if digits > 0 && chars.get(i + 1 + digits) == Some(&']') {
+                    i += digits + 2;
+                } else {
+                    return i;
+                }
+            }
+            _ => return i,
+        }
+    }
+}
+
+fn string_literal(
+    source: &str,
+    chars: &[char],
+    start: usize,
+) -> Result<(String, usize), RuleError> {
+    let quote = chars[start];
+    let mut text = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        match chars[i] {
+            c if c == quote => return Ok((text, i + 1)),
+            '\\' => {
+                let escaped = chars
+                    .get(i + 1)
+                    .ok_or_else(|| invalid(source, "unterminated string", start))?;
+                text.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => *other,
+                });
+                i += 2;
+            }
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Err(invalid(source, "unterminated string", start))
+}
+
+fn number_literal(text: &str) -> Option<Number> {
+    if let Ok(n) = text.parse::<i64>() {
+        return Some(n.into());
+    }
+    text.parse::<f64>().ok().and_then(Number::from_f64)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn end(&self) -> usize {
+        self.source.chars().count()
+    }
+
+    fn error(&self, message: &str, at: usize) -> RuleError {
+        invalid(self.source, message, at)
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<(), RuleError> {
+        match self.next() {
+            Some((token, _)) if token == expected => Ok(()),
+            Some((_, at)) => Err(self.error(&format!("expected {}", what), at)),
+            None => Err(self.error(&format!("expected {}", what), self.end())),
+        }
+    }
+
+    /// Precedence climbing over binary operators binding tighter than `min`
+    fn expression(&mut self, min: u8) -> Result<Expr, RuleError> {
+        let mut left = self.unary()?;
+
+        while let Some((Token::Op(op), _)) = self.peek() {
+            let op = *op;
+            if op.precedence() <= min {
+                break;
+            }
+            self.pos += 1;
+            let right = self.expression(op.precedence())?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, RuleError> {
+        match self.peek() {
+            Some((Token::Not, _)) => {
+                self.pos += 1;
+                Ok(Expr::Unary(UnaryOp::Not, Box::new(self.unary()?)))
+            }
+            Some((Token::Op(BinaryOp::Sub), _)) => {
+                self.pos += 1;
+                Ok(Expr::Unary(UnaryOp::Neg, Box::new(self.unary()?)))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, RuleError> {
+        let Some((token, at)) = self.next() else {
+            return Err(self.error("unexpected end of expression", self.end()));
+        };
+
+        match token {
+            Token::Literal(value) => Ok(Expr::Literal(value)),
+            Token::LParen => {
+                let expr = self.expression(0)?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(expr)
+            }
+            Token::LBracket => {
+                let items = self.list(Token::RBracket, "']'")?;
+                Ok(Expr::Array(items))
+            }
+            Token::Ident(name) => {
+                if !matches!(self.peek(), Some((Token::LParen, _))) {
+                    return Ok(Expr::Var(name));
+                }
+                self.pos += 1;
+                let args = self.list(Token::RParen, "')'")?;
+                match FUNCTIONS.iter().find(|(f, _)| *f == name) {
+                    Some((_, arity)) if *arity == args.len() => Ok(Expr::Call(name, args)),
+                    Some((_, arity)) => Err(self.error(
+                        &format!("function '{}' takes {} argument(s)", name, arity),
+                        at,
+                    )),
+                    None => Err(self.error(&format!("unknown function '{}'", name), at)),
+                }
+            }
+            _ => Err(self.error("expected a value", at)),
+        }
+    }
+
+    /// Comma-separated expressions up to the closing token
+    fn list(&mut self, close: Token, what: &str) -> Result<Vec<Expr>, RuleError> {
+        let mut items = Vec::new();
+        if matches!(self.peek(), Some((token, _)) if *token == close) {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            items.push(self.expression(0)?);
+            match self.next() {
+                Some((Token::Comma, _)) => continue,
+                Some((token, _)) if token == close => return Ok(items),
+                Some((_, at)) => return Err(self.error(&format!("expected ',' or {}", what), at)),
+                None => return Err(self.error(&format!("expected {}", what), self.end())),
+            }
+        }
+    }
+}
+
+fn type_mismatch(message: &str, value: &Value) -> RuleError {
+    RuleError::TypeMismatch(format!("{}, got {}", message, value))
+}
+
+fn as_bool(value: &Value, op: &str) -> Result<bool, RuleError> {
+    value
+        .as_bool()
+        .ok_or_else(|| type_mismatch(&format!("'{}' expects booleans", op), value))
+}
+
+/// Equality that treats `1` and `1.0` as equal
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => match (l.as_i64(), r.as_i64()) {
+            (Some(l), Some(r)) => l == r,
+            _ => l.as_f64() == r.as_f64(),
+        },
+        _ => left == right,
+    }
+}
+
+fn compare(left: &Value, right: &Value) -> Result<Ordering, RuleError> {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l
+            .as_f64()
+            .zip(r.as_f64())
+            .and_then(|(l, r)| l.partial_cmp(&r))
+            .ok_or_else(|| RuleError::TypeMismatch("numbers are not comparable".to_string())),
+        (Value::String(l), Value::String(r)) => Ok(l.cmp(r)),
+        _ => Err(RuleError::TypeMismatch(format!(
+            "cannot compare {} with {}",
+            left, right
+        ))),
+    }
+}
+
+/// Whether `collection` holds `item`: array element, substring or object key
+fn contains(collection: &Value, item: &Value) -> Result<bool, RuleError> {
+    match (collection, item) {
+        (Value::Array(items), _) => Ok(items.iter().any(|v| values_equal(v, item))),
+        (Value::String(s), Value::String(sub)) => Ok(s.contains(sub.as_str())),
+        (Value::Object(map), Value::String(key)) => Ok(map.contains_key(key)),
+        _ => Err(RuleError::TypeMismatch(format!(
+            "cannot look for {} in {}",
+            item, collection
+        ))),
+    }
+}
+
+fn binary(op: BinaryOp, left: &Value, right: &Value) -> RuleResult {
+    let result = match op {
+        BinaryOp::Eq => values_equal(left, right),
+        BinaryOp::Ne => !values_equal(left, right),
+        BinaryOp::Lt => compare(left, right)? == Ordering::Less,
+        BinaryOp::Le => compare(left, right)? != Ordering::Greater,
+        BinaryOp::Gt => compare(left, right)? == Ordering::Greater,
+        BinaryOp::Ge => compare(left, right)? != Ordering::Less,
+        BinaryOp::In => contains(right, left)?,
+        BinaryOp::Contains => contains(left, right)?,
+        BinaryOp::Add => {
+            if let (Value::String(l), Value::String(r)) = (left, right) {
+                return Ok(Value::String(format!("{}{}", l, r)));
+            }
+            return arithmetic(op, left, right);
+        }
+        _ => return arithmetic(op, left, right),
+    };
+    Ok(Value::Bool(result))
+}
+
+fn arithmetic(op: BinaryOp, left: &Value, right: &Value) -> RuleResult {
+    let (Value::Number(l), Value::Number(r)) = (left, right) else {
+        return Err(RuleError::TypeMismatch(format!(
+            "arithmetic needs numbers, got {} and {}",
+            left, right
+        )));
+    };
+
+    // Stay in integers where possible so `2 + 2 == 4` keeps integer results
+    if let (Some(l), Some(r)) = (l.as_i64(), r.as_i64()) {
+        let exact = match op {
+            BinaryOp::Add => l.checked_add(r),
+            BinaryOp::Sub => l.checked_sub(r),
+            BinaryOp::Mul => l.checked_mul(r),
+            BinaryOp::Rem if r != 0 => l.checked_rem(r),
+            _ => None,
+        };
+        if let Some(n) = exact {
+            return Ok(Value::from(n));
+        }
+    }
+
+    let (l, r) = (
+        l.as_f64().unwrap_or(f64::NAN),
+        r.as_f64().unwrap_or(f64::NAN),
+    );
+    if matches!(op, BinaryOp::Div | BinaryOp::Rem) && r == 0.0 {
+        return Err(RuleError::Eval("division by zero".to_string()));
+    }
+    let result = match op {
+        BinaryOp::Add => l + r,
+        BinaryOp::Sub => l - r,
+        BinaryOp::Mul => l * r,
+        BinaryOp::Div => l / r,
+        BinaryOp::Rem => l % r,
+        _ => unreachable!("not an arithmetic operator"),
+    };
+    Number::from_f64(result)
+        .map(Value::Number)
+        .ok_or_else(|| RuleError::Eval(format!("arithmetic result {} is not a number", result)))
+}
+
+fn call(name: &str, args: &[Value]) -> RuleResult {
+    let string_arg = |i: usize| {
+        args[i]
+            .as_str()
+            .ok_or_else(|| type_mismatch(&format!("{}() expects strings", name), &args[i]))
+    };
+
+    match name {
+        "len" => match &args[0] {
+            Value::String(s) => Ok(Value::from(s.chars().count())),
+            Value::Array(items) => Ok(Value::from(items.len())),
+            Value::Object(map) => Ok(Value::from(map.len())),
+            other => Err(type_mismatch(
+                "len() expects a string, array or object",
+                other,
+            )),
+        },
+        "lower" => Ok(Value::from(string_arg(0)?.to_lowercase())),
+        "upper" => Ok(Value::from(string_arg(0)?.to_uppercase())),
+        "trim" => Ok(Value::from(string_arg(0)?.trim())),
+        "starts_with" => Ok(Value::from(string_arg(0)?.starts_with(string_arg(1)?))),
+        "ends_with" => Ok(Value::from(string_arg(0)?.ends_with(string_arg(1)?))),
+        _ => Err(RuleError::Eval(format!("unknown function '{}'", name))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eval(source: &str, context: &Value) -> RuleResult {
+        let context: HashMap<String, Value> =
+            serde_json::from_value(context.clone()).expect("context must be an object");
+        Expr::parse(source)?.eval(&context)
+    }
+
+    #[test]
+    fn test_precedence_and_parentheses() {
+        let ctx = json!({"a": 2, "b": 1});
+        assert_eq!(eval("a > 1 && b < 2", &ctx).unwrap(), json!(true));
+        assert_eq!(eval("1 + 2 * 3", &ctx).unwrap(), json!(7));
+        assert_eq!(eval("(1 + 2) * 3", &ctx).unwrap(), json!(9));
+        assert_eq!(
+            eval("a == 1 || b == 1 && a == 3", &ctx).unwrap(),
+            json!(false)
+        );
+        assert_eq!(eval("!(a > 1) || -a < 0", &ctx).unwrap(), json!(true));
+        assert_eq!(eval("a / 4 == 0.5", &ctx).unwrap(), json!(true));
+        assert_eq!(eval("7 % 4 - 1", &ctx).unwrap(), json!(2));
+    }
+
+    #[test]
+    fn test_membership_and_functions() {
+        let ctx = json!({
+            "status": "Active",
+            "tags": ["vip", "eu"],
+            "order": {"items": [{"sku": "A-1"}]}
+        });
+        assert_eq!(
+            eval("lower(status) in ['active', 'trial']", &ctx).unwrap(),
+            json!(true)
+        );
+        assert_eq!(eval("tags contains 'vip'", &ctx).unwrap(), json!(true));
+        assert_eq!(eval("'ct' in status", &ctx).unwrap(), json!(true));
+        assert_eq!(eval("len(tags) == 2", &ctx).unwrap(), json!(true));
+        assert_eq!(
+            eval("starts_with(order.items[0].sku, \"A-\")", &ctx).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            eval("'pre' + trim('  fix ')", &ctx).unwrap(),
+            json!("prefix")
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        for source in [
+            "a >",
+            "a > 1 &&",
+            "(a > 1",
+            "a = 1",
+            "a > 1 b",
+            "unknown(a)",
+            "len(a, b)",
+            "'open",
+            "",
+        ] {
+            assert!(
+                matches!(Expr::parse(source), Err(RuleError::InvalidExpression(_))),
+                "'{}' should be rejected",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        let ctx = json!({"name": "x", "n": 1});
+        assert!(matches!(
+            eval("missing > 1", &ctx),
+            Err(RuleError::MissingVariable(_))
+        ));
+        assert!(matches!(
+            eval("name > 1", &ctx),
+            Err(RuleError::TypeMismatch(_))
+        ));
+        assert!(matches!(
+            eval("n && true", &ctx),
+            Err(RuleError::TypeMismatch(_))
+        ));
+        assert!(matches!(eval("n / 0", &ctx), Err(RuleError::Eval(_))));
+    }
+}
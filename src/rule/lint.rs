@@ -0,0 +1,580 @@
+//! Static analysis of GRL rule sets.
+//!
+//! [`RuleLinter`] looks for mistakes that parse fine but can't be intended:
+//!
+//! - rules that can never fire because their conditions contradict each
+//!   other (`stock > 10 && stock < 5`)
+//! - rules with equal salience that set the same fact to different values
+//!   and can both fire for the same facts
+//! - facts that rules read but that nothing provides, when the linter is told
+//!   which facts are available
+//!
+//! Conditions are only compared when they test a fact against a literal or
+//! against another fact; function calls, arithmetic and pattern matching are
+//! assumed to be satisfiable.
+
+use rust_rule_engine::engine::rule::{ConditionExpression, ConditionGroup, Rule as GrlRule};
+use rust_rule_engine::types::{ActionType, LogicalOperator, Operator};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+
+use super::engine::{GRLParser, Value};
+use super::RuleError;
+
+/// Above this many alternatives a condition is not analysed
+const MAX_ALTERNATIVES: usize = 64;
+
+/// Kind of problem found by [`RuleLinter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// A rule whose conditions can never all hold
+    Unreachable,
+    /// Two rules of equal salience that set a fact to different values
+    Conflict,
+    /// A fact read by a rule that no rule, node or input provides
+    UnknownFact,
+}
+
+/// A problem found in a rule set
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleLint {
+    pub kind: LintKind,
+    /// Rules involved, in the order they appear in the source
+    pub rules: Vec<String>,
+    /// Fact involved, for conflicts and unknown facts
+    pub fact: Option<String>,
+    pub message: String,
+}
+
+impl RuleLint {
+    /// Whether the lint is certainly a mistake. Unknown facts may still be
+    /// supplied at run time, so they are only warnings.
+    pub fn is_error(&self) -> bool {
+        self.kind != LintKind::UnknownFact
+    }
+}
+
+impl fmt::Display for RuleLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Finds contradictory, conflicting and unknown-fact rules in GRL
+///
+/// ```
+/// use rust_logic_graph::rule::lint::RuleLinter;
+///
+/// let lints = RuleLinter::new()
+///     .provided_facts(["stock"])
+///     .lint(r#"
+///         rule "Never" { when stock > 10 && stock < 5 then reorder = true; }
+///     "#)
+///     .unwrap();
+/// assert_eq!(lints.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RuleLinter {
+    provided: Option<HashSet<String>>,
+}
+
+impl RuleLinter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Facts available to the rules besides those the rules set themselves.
+    /// Unknown facts are only reported once this has been called.
+    pub fn provided_facts<I, S>(mut self, facts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.provided
+            .get_or_insert_with(HashSet::new)
+            .extend(facts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Parse and lint GRL source
+    pub fn lint(&self, grl: &str) -> Result<Vec<RuleLint>, RuleError> {
+        let rules = GRLParser::parse_rules(grl)
+            .map_err(|e| RuleError::Eval(format!("Failed to parse GRL: {}", e)))?;
+        Ok(self.lint_rules(&rules))
+    }
+
+    /// Lint already parsed rules
+    pub fn lint_rules(&self, rules: &[GrlRule]) -> Vec<RuleLint> {
+        let alternatives: Vec<Option<Vec<Conjunction>>> = rules
+            .iter()
+            .map(|rule| alternatives(&rule.conditions, false))
+            .collect();
+        let mut lints = Vec::new();
+
+        let mut reachable = vec![true; rules.len()];
+        for (i, rule) in rules.iter().enumerate() {
+            if let Some(alts) = &alternatives[i] {
+                if alts.iter().all(|conj| contradicts(conj.iter())) {
+                    reachable[i] = false;
+                    lints.push(RuleLint {
+                        kind: LintKind::Unreachable,
+                        rules: vec![rule.name.clone()],
+                        fact: None,
+                        message: format!(
+                            "Rule \"{}\" can never fire: its conditions contradict each other",
+                            rule.name
+                        ),
+                    });
+                }
+            }
+        }
+
+        for i in 0..rules.len() {
+            for j in i + 1..rules.len() {
+                let (a, b) = (&rules[i], &rules[j]);
+                if !reachable[i]
+                    || !reachable[j]
+                    || a.salience != b.salience
+                    || a.agenda_group != b.agenda_group
+                    || (a.activation_group.is_some() && a.activation_group == b.activation_group)
+                    || exclusive(&alternatives[i], &alternatives[j])
+                {
+                    continue;
+                }
+                let writes_b = writes(b);
+                for (fact, value_a) in writes(a) {
+                    match writes_b.get(fact) {
+                        Some(value_b) if value_a != *value_b => lints.push(RuleLint {
+                            kind: LintKind::Conflict,
+                            rules: vec![a.name.clone(), b.name.clone()],
+                            fact: Some(fact.to_string()),
+                            message: format!(
+                                "Rules \"{}\" and \"{}\" (salience {}) can both fire and set '{}' to different values ({} vs {})",
+                                a.name,
+                                b.name,
+                                a.salience,
+                                fact,
+                                display_value(value_a),
+                                display_value(value_b)
+                            ),
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        if let Some(provided) = &self.provided {
+            let set: HashSet<&str> = rules
+                .iter()
+                .flat_map(|rule| writes(rule).into_keys())
+                .collect();
+            let mut reported = HashSet::new();
+            for rule in rules {
+                for fact in reads(rule) {
+                    if !is_provided(&fact, provided, &set) && reported.insert(fact.clone()) {
+                        lints.push(RuleLint {
+                            kind: LintKind::UnknownFact,
+                            rules: vec![rule.name.clone()],
+                            message: format!(
+                                "Rule \"{}\" reads '{}', which no rule, node or input provides",
+                                rule.name, fact
+                            ),
+                            fact: Some(fact),
+                        });
+                    }
+                }
+            }
+        }
+
+        lints
+    }
+}
+
+/// Facts set by any of `rules`, so they can be provided to the linters of
+/// other rule sets in the same graph
+pub fn written_facts(rules: &[GrlRule]) -> BTreeSet<String> {
+    rules
+        .iter()
+        .flat_map(|rule| writes(rule).into_keys().map(str::to_string))
+        .collect()
+}
+
+/// A single comparison of a fact against a literal or another fact
+#[derive(Debug, Clone)]
+struct Atom {
+    field: String,
+    operator: Operator,
+    operand: Operand,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    /// Another fact; comparisons against the same fact are checked as if it
+    /// were the number 0
+    Fact(String),
+}
+
+/// Atoms that must all hold
+type Conjunction = Vec<Atom>;
+
+/// The condition as alternatives (any one of which makes it hold), or `None`
+/// when it is too large to analyse
+fn alternatives(group: &ConditionGroup, negated: bool) -> Option<Vec<Conjunction>> {
+    match group {
+        ConditionGroup::Single(condition) => {
+            let ConditionExpression::Field(field) = &condition.expression else {
+                return Some(vec![vec![]]);
+            };
+            let operator = if negated {
+                negate(&condition.operator)
+            } else {
+                Some(condition.operator.clone())
+            };
+            let atom = operator
+                .zip(operand(&condition.value))
+                .map(|(operator, operand)| Atom {
+                    field: field.clone(),
+                    operator,
+                    operand,
+                });
+            Some(vec![atom.into_iter().collect()])
+        }
+        ConditionGroup::Compound {
+            left,
+            operator,
+            right,
+        } => {
+            let left = alternatives(left, negated)?;
+            let right = alternatives(right, negated)?;
+            // De Morgan: a negated AND is an OR of the negations, and vice versa
+            let and = matches!(
+                (operator, negated),
+                (LogicalOperator::And, false) | (LogicalOperator::Or, true)
+            );
+            let combined: Vec<Conjunction> = if and {
+                left.iter()
+                    .flat_map(|l| right.iter().map(move |r| [l.clone(), r.clone()].concat()))
+                    .collect()
+            } else {
+                left.into_iter().chain(right).collect()
+            };
+            (combined.len() <= MAX_ALTERNATIVES).then_some(combined)
+        }
+        ConditionGroup::Not(inner) => alternatives(inner, !negated),
+        _ => Some(vec![vec![]]),
+    }
+}
+
+fn negate(operator: &Operator) -> Option<Operator> {
+    Some(match operator {
+        Operator::Equal => Operator::NotEqual,
+        Operator::NotEqual => Operator::Equal,
+        Operator::GreaterThan => Operator::LessThanOrEqual,
+        Operator::GreaterThanOrEqual => Operator::LessThan,
+        Operator::LessThan => Operator::GreaterThanOrEqual,
+        Operator::LessThanOrEqual => Operator::GreaterThan,
+        _ => return None,
+    })
+}
+
+fn operand(value: &Value) -> Option<Operand> {
+    match value {
+        Value::Integer(i) => Some(Operand::Number(*i as f64)),
+        Value::Number(n) => Some(Operand::Number(*n)),
+        Value::String(s) => Some(Operand::Text(s.clone())),
+        Value::Boolean(b) => Some(Operand::Bool(*b)),
+        Value::Expression(e) if is_identifier(e) => Some(Operand::Fact(e.clone())),
+        _ => None,
+    }
+}
+
+/// What the atoms on one field (against one other fact, if any) allow
+#[derive(Default)]
+struct Bounds {
+    lower: Option<(f64, bool)>,
+    upper: Option<(f64, bool)>,
+    equal: Option<Operand>,
+    not_equal: Vec<Operand>,
+}
+
+/// Whether the atoms can never all hold together
+fn contradicts<'a>(atoms: impl Iterator<Item = &'a Atom>) -> bool {
+    let mut bounds: BTreeMap<(&str, Option<&str>), Bounds> = BTreeMap::new();
+    for atom in atoms {
+        let (reference, operand) = match &atom.operand {
+            Operand::Fact(other) => (Some(other.as_str()), Operand::Number(0.0)),
+            operand => (None, operand.clone()),
+        };
+        let b = bounds.entry((atom.field.as_str(), reference)).or_default();
+        match (&atom.operator, &operand) {
+            (Operator::Equal, _) => {
+                if b.equal.as_ref().is_some_and(|equal| *equal != operand) {
+                    return true;
+                }
+                b.equal = Some(operand);
+            }
+            (Operator::NotEqual, _) => b.not_equal.push(operand),
+            (Operator::GreaterThan, Operand::Number(n)) => tighten(&mut b.lower, *n, false, true),
+            (Operator::GreaterThanOrEqual, Operand::Number(n)) => {
+                tighten(&mut b.lower, *n, true, true)
+            }
+            (Operator::LessThan, Operand::Number(n)) => tighten(&mut b.upper, *n, false, false),
+            (Operator::LessThanOrEqual, Operand::Number(n)) => {
+                tighten(&mut b.upper, *n, true, false)
+            }
+            _ => {}
+        }
+    }
+
+    bounds.values().any(|b| {
+        if let Some(equal) = &b.equal {
+            if b.not_equal.contains(equal) {
+                return true;
+            }
+            if let Operand::Number(n) = equal {
+                let above = b
+                    .lower
+                    .is_some_and(|(low, incl)| *n < low || (*n == low && !incl));
+                let below = b
+                    .upper
+                    .is_some_and(|(up, incl)| *n > up || (*n == up && !incl));
+                return above || below;
+            }
+        }
+        match (b.lower, b.upper) {
+            (Some((low, low_incl)), Some((up, up_incl))) => {
+                low > up || (low == up && !(low_incl && up_incl))
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Narrow a lower (`is_lower`) or upper bound to `value`
+fn tighten(bound: &mut Option<(f64, bool)>, value: f64, inclusive: bool, is_lower: bool) {
+    let tighter = match *bound {
+        None => true,
+        Some((current, current_incl)) => {
+            if is_lower {
+                value > current || (value == current && current_incl && !inclusive)
+            } else {
+                value < current || (value == current && current_incl && !inclusive)
+            }
+        }
+    };
+    if tighter {
+        *bound = Some((value, inclusive));
+    }
+}
+
+/// Whether no facts can satisfy both conditions
+fn exclusive(a: &Option<Vec<Conjunction>>, b: &Option<Vec<Conjunction>>) -> bool {
+    let (Some(a), Some(b)) = (a, b) else {
+        return false;
+    };
+    a.iter()
+        .all(|a| b.iter().all(|b| contradicts(a.iter().chain(b.iter()))))
+}
+
+/// Facts the rule sets, with the value it sets them to
+fn writes(rule: &GrlRule) -> BTreeMap<&str, &Value> {
+    rule.actions
+        .iter()
+        .filter_map(|action| match action {
+            ActionType::Set { field, value } => Some((field.as_str(), value)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Facts the rule's conditions and actions read
+fn reads(rule: &GrlRule) -> Vec<String> {
+    let mut facts = Vec::new();
+    collect_reads(&rule.conditions, &mut facts);
+    for action in &rule.actions {
+        if let ActionType::Set {
+            value: Value::Expression(expr),
+            ..
+        } = action
+        {
+            facts.extend(identifiers(expr));
+        }
+    }
+    let mut seen = HashSet::new();
+    facts.retain(|fact| seen.insert(fact.clone()));
+    facts
+}
+
+fn collect_reads(group: &ConditionGroup, out: &mut Vec<String>) {
+    match group {
+        ConditionGroup::Single(condition) => {
+            match &condition.expression {
+                ConditionExpression::Field(field) => out.push(field.clone()),
+                ConditionExpression::MultiField { field, .. } => out.push(field.clone()),
+                ConditionExpression::FunctionCall { args, .. }
+                | ConditionExpression::Test { args, .. } => {
+                    out.extend(args.iter().filter(|arg| is_identifier(arg)).cloned())
+                }
+            }
+            if let Value::Expression(expr) = &condition.value {
+                out.extend(identifiers(expr));
+            }
+        }
+        ConditionGroup::Compound { left, right, .. } => {
+            collect_reads(left, out);
+            collect_reads(right, out);
+        }
+        ConditionGroup::Not(inner)
+        | ConditionGroup::Exists(inner)
+        | ConditionGroup::Forall(inner) => collect_reads(inner, out),
+        _ => {}
+    }
+}
+
+/// Fact names in an arithmetic expression such as `qty * unit_price`
+fn identifiers(expr: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut rest = expr;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        let after = &rest[start..];
+        let end = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+            .unwrap_or(after.len());
+        let word = after[..end].trim_end_matches('.');
+        let is_call = after[end..].trim_start().starts_with('(');
+        let preceded_by_digit = rest[..start].ends_with(|c: char| c.is_ascii_digit());
+        if !is_call && !preceded_by_digit && !matches!(word, "true" | "false" | "null") {
+            found.push(word.to_string());
+        }
+        rest = &after[end..];
+    }
+    found
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        && !matches!(text, "true" | "false" | "null")
+}
+
+/// A fact counts as provided when it, one of its parents or one of its
+/// fields is provided
+fn is_provided(fact: &str, provided: &HashSet<String>, set: &HashSet<&str>) -> bool {
+    let known = |name: &str| provided.contains(name) || set.contains(name);
+    let mut path = fact;
+    loop {
+        if known(path) {
+            return true;
+        }
+        match path.rfind('.') {
+            Some(dot) => path = &path[..dot],
+            None => break,
+        }
+    }
+    let prefix = format!("{}.", fact);
+    provided
+        .iter()
+        .map(String::as_str)
+        .chain(set.iter().copied())
+        .any(|name| name.starts_with(&prefix))
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{}\"", s),
+        Value::Expression(e) => e.clone(),
+        Value::Integer(i) => i.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(grl: &str) -> Vec<RuleLint> {
+        RuleLinter::new().lint(grl).unwrap()
+    }
+
+    #[test]
+    fn test_contradictory_conditions() {
+        let lints = lint(
+            r#"
+            rule "Range" { when stock > 10 && stock < 5 then a = 1; }
+            rule "Equal" { when tier == "gold" && tier == "silver" then a = 2; }
+            rule "NotEqual" { when !(flag != true) && flag == false then a = 3; }
+            rule "Either" { when (stock > 10 && stock < 5) || stock == 7 then a = 4; }
+            rule "Edge" { when stock >= 5 && stock <= 5 then b = 1; }
+            rule "Facts" { when qty < limit && qty >= limit then c = 1; }
+            "#,
+        );
+        let unreachable: Vec<_> = lints
+            .iter()
+            .filter(|lint| lint.kind == LintKind::Unreachable)
+            .map(|lint| lint.rules[0].as_str())
+            .collect();
+        assert_eq!(unreachable, vec!["Range", "Equal", "NotEqual", "Facts"]);
+    }
+
+    #[test]
+    fn test_conflicting_writes() {
+        let lints = lint(
+            r#"
+            rule "Gold" salience 10 { when spend > 1000 then tier = "gold"; }
+            rule "Silver" salience 10 { when visits > 5 then tier = "silver"; }
+            rule "Bronze" salience 5 { when visits > 1 then tier = "bronze"; }
+            rule "Low" salience 10 { when spend <= 1000 then tier = "basic"; }
+            rule "Same" salience 10 { when visits > 100 then tier = "gold"; }
+            "#,
+        );
+        let conflicts: Vec<_> = lints
+            .iter()
+            .filter(|lint| lint.kind == LintKind::Conflict)
+            .map(|lint| lint.rules.join("/"))
+            .collect();
+        // Gold and Low are exclusive, Gold and Same agree, Bronze has a
+        // different salience
+        assert_eq!(
+            conflicts,
+            vec!["Gold/Silver", "Silver/Low", "Silver/Same", "Low/Same"]
+        );
+        assert!(lints
+            .iter()
+            .all(|lint| lint.fact.as_deref() == Some("tier")));
+    }
+
+    #[test]
+    fn test_unknown_facts() {
+        let grl = r#"
+            rule "Total" { when qty > 0 && customer.tier == "gold" then total = qty * unit_price; }
+            rule "Ship" { when total > 100 then ship = true; }
+        "#;
+        assert!(lint(grl).is_empty());
+
+        let lints = RuleLinter::new()
+            .provided_facts(["qty", "customer"])
+            .lint(grl)
+            .unwrap();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].kind, LintKind::UnknownFact);
+        assert_eq!(lints[0].fact.as_deref(), Some("unit_price"));
+        assert!(!lints[0].is_error());
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(
+            identifiers("demand_lead_time - order.qty * 1.2e3 + round(x)"),
+            vec!["demand_lead_time", "order.qty", "x"]
+        );
+    }
+}
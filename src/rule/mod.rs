@@ -1,16 +1,27 @@
+pub mod decision_table;
 mod engine;
+pub mod expr;
+pub mod lint;
+mod rule_set;
+pub mod testing;
+pub mod trace;
 
 // Re-export the convenience wrapper
-pub use engine::RuleEngine;
+pub use decision_table::{DecisionTable, DecisionTableDef, HitPolicy};
+pub use engine::{fact_to_json, json_to_fact, RuleEngine};
+pub use expr::Expr;
+pub use rule_set::{RuleSet, RuleSetManager, RuleSetVersion, DEFAULT_MAX_HISTORY};
+pub use trace::RuleTrace;
 
 // Re-export rust-rule-engine types for advanced usage
 pub use engine::{EngineConfig, Facts, GRLParser, KnowledgeBase, RustRuleEngine, Value};
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Error, Serialize, Deserialize)]
 pub enum RuleError {
     #[error("Rule evaluation failed: {0}")]
     Eval(String),
@@ -23,168 +34,103 @@ pub enum RuleError {
 
     #[error("Invalid expression: {0}")]
     InvalidExpression(String),
+
+    /// The node ran past its `timeout_ms` or the execution deadline and was
+    /// cancelled; running it again may succeed
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// The execution running the node was cancelled
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
+    /// The graph, node or rule set is set up wrongly (an unknown node or
+    /// rule set, a missing declared output, ...); every attempt fails the
+    /// same way
+    #[error("Configuration error: {0}")]
+    Configuration(String),
+}
+
+impl RuleError {
+    /// Whether this is a [`RuleError::Timeout`]
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, RuleError::Timeout(_))
+    }
+
+    /// Whether this is a [`RuleError::Cancelled`]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, RuleError::Cancelled(_))
+    }
+
+    /// Whether running the node again may succeed. Invalid expressions, type
+    /// mismatches, missing variables and configuration errors fail the same
+    /// way on every attempt.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, RuleError::Eval(_) | RuleError::Timeout(_))
+    }
 }
 
 pub type RuleResult = Result<JsonValue, RuleError>;
 
-/// Simple rule implementation (backward compatible)
-/// For advanced features, use RuleEngine directly
+/// Condition rule evaluated with the built-in expression language (see
+/// [`Expr`] for the syntax). For GRL rule sets, use [`RuleEngine`].
+///
+/// The condition is parsed once, when the rule is created.
 #[derive(Debug, Clone)]
 pub struct Rule {
     pub id: String,
     pub condition: String,
+    expr: Result<Expr, String>,
 }
 
 impl Rule {
+    /// Create a rule; a condition that fails to parse makes every
+    /// evaluation return [`RuleError::InvalidExpression`]
     pub fn new(id: impl Into<String>, condition: impl Into<String>) -> Self {
+        let condition = condition.into();
+        let expr = Expr::parse(&condition).map_err(|e| match e {
+            RuleError::InvalidExpression(message) => message,
+            other => other.to_string(),
+        });
         Self {
             id: id.into(),
-            condition: condition.into(),
+            condition,
+            expr,
         }
     }
 
-    /// Evaluate the rule against provided data context
-    pub fn evaluate(&self, context: &HashMap<String, JsonValue>) -> RuleResult {
-        let condition = self.condition.trim();
-
-        // Handle simple boolean literals
-        if condition == "true" {
-            return Ok(JsonValue::Bool(true));
-        }
-        if condition == "false" {
-            return Ok(JsonValue::Bool(false));
-        }
-
-        // Handle variable lookup (e.g., "user_active")
-        if !condition.contains(' ') && !condition.contains(['>', '<', '=', '!']) {
-            return context
-                .get(condition)
-                .cloned()
-                .ok_or_else(|| RuleError::MissingVariable(condition.to_string()));
-        }
-
-        // Handle comparisons (e.g., "age > 18", "status == active")
-        if let Some(result) = self.evaluate_comparison(condition, context) {
-            return result;
-        }
-
-        // Handle logical operations (e.g., "active && verified")
-        if condition.contains("&&") || condition.contains("||") {
-            return self.evaluate_logical(condition, context);
-        }
-
-        // Default to true if we can't parse (permissive)
-        Ok(JsonValue::Bool(true))
-    }
-
-    fn evaluate_comparison(
-        &self,
-        expr: &str,
-        context: &HashMap<String, JsonValue>,
-    ) -> Option<RuleResult> {
-        for op in ["==", "!=", ">=", "<=", ">", "<"] {
-            if let Some((left, right)) = expr.split_once(op) {
-                let left = left.trim();
-                let right = right.trim();
-
-                let left_val = self.get_value(left, context).ok()?;
-                let right_val = self.get_value(right, context).ok()?;
-
-                let result = match op {
-                    "==" => left_val == right_val,
-                    "!=" => left_val != right_val,
-                    ">" => self.compare_values(&left_val, &right_val, std::cmp::Ordering::Greater),
-                    "<" => self.compare_values(&left_val, &right_val, std::cmp::Ordering::Less),
-                    ">=" => {
-                        self.compare_values(&left_val, &right_val, std::cmp::Ordering::Greater)
-                            || left_val == right_val
-                    }
-                    "<=" => {
-                        self.compare_values(&left_val, &right_val, std::cmp::Ordering::Less)
-                            || left_val == right_val
-                    }
-                    _ => false,
-                };
-
-                return Some(Ok(JsonValue::Bool(result)));
-            }
-        }
-        None
+    /// Create a rule, rejecting conditions that fail to parse
+    pub fn compile(id: impl Into<String>, condition: impl Into<String>) -> Result<Self, RuleError> {
+        let condition = condition.into();
+        let expr = Expr::parse(&condition)?;
+        Ok(Self {
+            id: id.into(),
+            condition,
+            expr: Ok(expr),
+        })
     }
 
-    fn evaluate_logical(&self, expr: &str, context: &HashMap<String, JsonValue>) -> RuleResult {
-        if let Some((left, right)) = expr.split_once("&&") {
-            let left_result = Rule::new("temp", left.trim()).evaluate(context)?;
-            let right_result = Rule::new("temp", right.trim()).evaluate(context)?;
-
-            if let (JsonValue::Bool(l), JsonValue::Bool(r)) = (left_result, right_result) {
-                return Ok(JsonValue::Bool(l && r));
-            }
-        }
-
-        if let Some((left, right)) = expr.split_once("||") {
-            let left_result = Rule::new("temp", left.trim()).evaluate(context)?;
-            let right_result = Rule::new("temp", right.trim()).evaluate(context)?;
-
-            if let (JsonValue::Bool(l), JsonValue::Bool(r)) = (left_result, right_result) {
-                return Ok(JsonValue::Bool(l || r));
-            }
-        }
-
-        Err(RuleError::InvalidExpression(expr.to_string()))
+    /// Parsed condition
+    pub fn expr(&self) -> Result<&Expr, RuleError> {
+        self.expr
+            .as_ref()
+            .map_err(|e| RuleError::InvalidExpression(e.clone()))
     }
 
-    fn get_value(&self, s: &str, context: &HashMap<String, JsonValue>) -> RuleResult {
-        // Try to parse as number
-        if let Ok(num) = s.parse::<i64>() {
-            return Ok(JsonValue::Number(num.into()));
-        }
-
-        // Try to parse as float
-        if let Ok(num) = s.parse::<f64>() {
-            if let Some(n) = serde_json::Number::from_f64(num) {
-                return Ok(JsonValue::Number(n));
-            }
-        }
-
-        // Try to parse as boolean
-        if s == "true" {
-            return Ok(JsonValue::Bool(true));
-        }
-        if s == "false" {
-            return Ok(JsonValue::Bool(false));
-        }
-
-        // Try string literal (quoted)
-        if s.starts_with('"') && s.ends_with('"') {
-            return Ok(JsonValue::String(s[1..s.len() - 1].to_string()));
-        }
-
-        // Otherwise, look up in context
-        context
-            .get(s)
-            .cloned()
-            .ok_or_else(|| RuleError::MissingVariable(s.to_string()))
+    /// Evaluate the rule against provided data context
+    pub fn evaluate(&self, context: &HashMap<String, JsonValue>) -> RuleResult {
+        self.expr()?.eval(context)
     }
 
-    fn compare_values(
-        &self,
-        left: &JsonValue,
-        right: &JsonValue,
-        ordering: std::cmp::Ordering,
-    ) -> bool {
-        match (left, right) {
-            (JsonValue::Number(l), JsonValue::Number(r)) => {
-                if let (Some(l), Some(r)) = (l.as_f64(), r.as_f64()) {
-                    return l.partial_cmp(&r) == Some(ordering);
-                }
-            }
-            (JsonValue::String(l), JsonValue::String(r)) => {
-                return l.cmp(r) == ordering;
-            }
-            _ => {}
+    /// Evaluate the rule as a condition, requiring a boolean result
+    pub fn evaluate_bool(&self, context: &HashMap<String, JsonValue>) -> Result<bool, RuleError> {
+        match self.evaluate(context)? {
+            JsonValue::Bool(b) => Ok(b),
+            other => Err(RuleError::TypeMismatch(format!(
+                "condition '{}' evaluated to {} (expected a boolean)",
+                self.condition, other
+            ))),
         }
-        false
     }
 }
 
@@ -220,4 +166,18 @@ mod tests {
         let rule = Rule::new("r1", "active && verified");
         assert_eq!(rule.evaluate(&context).unwrap(), JsonValue::Bool(true));
     }
+
+    #[test]
+    fn test_nested_path() {
+        let rule = Rule::new("r1", "inventory_result.items[0].available_qty < 10");
+        let mut context = HashMap::new();
+        context.insert(
+            "inventory_result".to_string(),
+            serde_json::json!({"items": [{"available_qty": 4}]}),
+        );
+        assert_eq!(rule.evaluate(&context).unwrap(), JsonValue::Bool(true));
+
+        let rule = Rule::new("r2", "inventory_result.items[0].available_qty");
+        assert_eq!(rule.evaluate(&context).unwrap(), serde_json::json!(4));
+    }
 }
@@ -0,0 +1,432 @@
+//! Named GRL rule sets that can be replaced while graphs are running.
+//!
+//! A [`RuleSetManager`] keeps every loaded version of each rule set. Loading
+//! new rule text compiles it first and only then swaps it in, so a broken
+//! edit never replaces working rules. An executor (see
+//! [`Executor::set_rule_sets`]) resolves each rule set to its active version
+//! the first time a run uses it and keeps that version for the rest of the
+//! run; the versions used are reported in the run's metrics.
+//!
+//! [`Executor::set_rule_sets`]: crate::core::Executor::set_rule_sets
+
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+use super::{RuleEngine, RuleError, RuleResult, RuleTrace};
+
+/// Default number of versions kept per rule set
+pub const DEFAULT_MAX_HISTORY: usize = 20;
+
+/// One compiled version of a named rule set
+pub struct RuleSet {
+    name: String,
+    version: u64,
+    source: String,
+    loaded_at: DateTime<Utc>,
+    rule_count: usize,
+    engine: Mutex<RuleEngine>,
+}
+
+impl RuleSet {
+    /// Compile `source`; the version number is assigned when it is stored
+    fn compile(name: &str, source: String) -> Result<Self, RuleError> {
+        let engine = RuleEngine::from_grl(&source)?;
+        let rule_count = engine.inner().knowledge_base().rule_count();
+        if rule_count == 0 {
+            return Err(RuleError::InvalidExpression(format!(
+                "GRL for rule set '{}' does not define any rules",
+                name
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            version: 0,
+            source,
+            loaded_at: Utc::now(),
+            rule_count,
+            engine: Mutex::new(engine),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// GRL text this version was compiled from
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn loaded_at(&self) -> DateTime<Utc> {
+        self.loaded_at
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rule_count
+    }
+
+    /// Run the rules against `context`; see [`RuleEngine::evaluate`]
+    pub fn evaluate(&self, context: &HashMap<String, Value>) -> RuleResult {
+        self.engine.lock().evaluate(context)
+    }
+
+    /// Run the rules and trace them; see [`RuleEngine::evaluate_traced`]
+    pub fn evaluate_traced(
+        &self,
+        context: &HashMap<String, Value>,
+    ) -> Result<(Value, RuleTrace), RuleError> {
+        self.engine.lock().evaluate_traced(context)
+    }
+}
+
+impl std::fmt::Debug for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleSet")
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("loaded_at", &self.loaded_at)
+            .field("rule_count", &self.rule_count)
+            .finish()
+    }
+}
+
+/// Entry of a rule set's version history
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSetVersion {
+    pub version: u64,
+    pub loaded_at: DateTime<Utc>,
+    pub rule_count: usize,
+    pub active: bool,
+}
+
+#[derive(Default)]
+struct History {
+    versions: Vec<Arc<RuleSet>>,
+    active: usize,
+    last_version: u64,
+}
+
+/// Registry of named, versioned GRL rule sets.
+///
+/// Share it between executors with an `Arc`; every method takes `&self`.
+///
+/// ```no_run
+/// use rust_logic_graph::rule::RuleSetManager;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), rust_logic_graph::RuleError> {
+/// let rule_sets = Arc::new(RuleSetManager::new());
+/// rule_sets.load_file("purchasing", "rules/purchasing_rules.grl")?;
+///
+/// // Pick up edits to the file without restarting
+/// rule_sets.watch("purchasing", "rules/purchasing_rules.grl", Duration::from_secs(5));
+///
+/// // A bad change can be reverted to the previous version
+/// rule_sets.rollback("purchasing")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RuleSetManager {
+    sets: RwLock<HashMap<String, History>>,
+    max_history: usize,
+}
+
+impl RuleSetManager {
+    pub fn new() -> Self {
+        Self::with_max_history(DEFAULT_MAX_HISTORY)
+    }
+
+    /// Keep at most `max_history` versions of each rule set (at least 1)
+    pub fn with_max_history(max_history: usize) -> Self {
+        Self {
+            sets: RwLock::new(HashMap::new()),
+            max_history: max_history.max(1),
+        }
+    }
+
+    /// Compile `grl` and make it the active version of `name`, returning the
+    /// new version number. On error the active version is left unchanged.
+    pub fn load(&self, name: &str, grl: impl Into<String>) -> Result<u64, RuleError> {
+        let mut rule_set = RuleSet::compile(name, grl.into())?;
+
+        let mut sets = self.sets.write();
+        let history = sets.entry(name.to_string()).or_default();
+        let version = history.last_version + 1;
+        rule_set.version = version;
+
+        history.versions.push(Arc::new(rule_set));
+        history.last_version = version;
+        if history.versions.len() > self.max_history {
+            history.versions.remove(0);
+        }
+        history.active = history.versions.len() - 1;
+
+        info!("Loaded rule set '{}' version {}", name, version);
+        Ok(version)
+    }
+
+    /// Load the GRL file at `path` as a new version of `name`
+    pub fn load_file(&self, name: &str, path: impl AsRef<Path>) -> Result<u64, RuleError> {
+        let path = path.as_ref();
+        let grl = std::fs::read_to_string(path).map_err(|e| {
+            RuleError::Eval(format!(
+                "Failed to read GRL file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        self.load(name, grl)
+    }
+
+    /// Active version of `name`, if it has been loaded
+    pub fn active(&self, name: &str) -> Option<Arc<RuleSet>> {
+        let sets = self.sets.read();
+        let history = sets.get(name)?;
+        history.versions.get(history.active).cloned()
+    }
+
+    /// A specific version of `name`, if it is still in the history
+    pub fn get(&self, name: &str, version: u64) -> Option<Arc<RuleSet>> {
+        let sets = self.sets.read();
+        sets.get(name)?
+            .versions
+            .iter()
+            .find(|set| set.version == version)
+            .cloned()
+    }
+
+    /// Names of all loaded rule sets
+    pub fn names(&self) -> Vec<String> {
+        self.sets.read().keys().cloned().collect()
+    }
+
+    /// Version history of `name`, oldest first
+    pub fn versions(&self, name: &str) -> Vec<RuleSetVersion> {
+        let sets = self.sets.read();
+        let Some(history) = sets.get(name) else {
+            return Vec::new();
+        };
+        history
+            .versions
+            .iter()
+            .enumerate()
+            .map(|(i, set)| RuleSetVersion {
+                version: set.version,
+                loaded_at: set.loaded_at,
+                rule_count: set.rule_count,
+                active: i == history.active,
+            })
+            .collect()
+    }
+
+    /// Make `version` the active version of `name`
+    pub fn activate(&self, name: &str, version: u64) -> Result<(), RuleError> {
+        let mut sets = self.sets.write();
+        let history = sets.get_mut(name).ok_or_else(|| unknown_rule_set(name))?;
+        let index = history
+            .versions
+            .iter()
+            .position(|set| set.version == version)
+            .ok_or_else(|| {
+                RuleError::Configuration(format!(
+                    "Rule set '{}' has no version {} in its history",
+                    name, version
+                ))
+            })?;
+        history.active = index;
+
+        info!("Activated rule set '{}' version {}", name, version);
+        Ok(())
+    }
+
+    /// Activate the version loaded before the active one, returning its number
+    pub fn rollback(&self, name: &str) -> Result<u64, RuleError> {
+        let version = {
+            let sets = self.sets.read();
+            let history = sets.get(name).ok_or_else(|| unknown_rule_set(name))?;
+            if history.active == 0 {
+                return Err(RuleError::Configuration(format!(
+                    "Rule set '{}' has no earlier version to roll back to",
+                    name
+                )));
+            }
+            history.versions[history.active - 1].version
+        };
+        self.activate(name, version)?;
+        Ok(version)
+    }
+
+    /// Reload `name` from `path` whenever the file's modification time
+    /// changes, checking every `interval`.
+    ///
+    /// Changes that fail to compile are logged and ignored, keeping the
+    /// active version. Abort the returned handle to stop watching.
+    pub fn watch(
+        self: &Arc<Self>,
+        name: impl Into<String>,
+        path: impl Into<PathBuf>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        let name = name.into();
+        let path = path.into();
+
+        tokio::spawn(async move {
+            let mut last_modified = modified(&path);
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+                let current = modified(&path);
+                if current.is_none() || current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+
+                match manager.load_file(&name, &path) {
+                    Ok(version) => info!(
+                        "Reloaded rule set '{}' from {} as version {}",
+                        name,
+                        path.display(),
+                        version
+                    ),
+                    Err(e) => warn!(
+                        "Ignoring change to {} for rule set '{}': {}",
+                        path.display(),
+                        name,
+                        e
+                    ),
+                }
+            }
+        })
+    }
+}
+
+impl Default for RuleSetManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for RuleSetManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sets = self.sets.read();
+        let mut active: Vec<(&String, u64)> = sets
+            .iter()
+            .filter_map(|(name, h)| h.versions.get(h.active).map(|set| (name, set.version)))
+            .collect();
+        active.sort();
+        f.debug_struct("RuleSetManager")
+            .field("active", &active)
+            .field("max_history", &self.max_history)
+            .finish()
+    }
+}
+
+fn unknown_rule_set(name: &str) -> RuleError {
+    RuleError::Configuration(format!("Rule set '{}' has not been loaded", name))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn threshold_rules(threshold: u32) -> String {
+        format!(
+            r#"
+            rule "Reorder" {{
+                when
+                    stock < {}
+                then
+                    reorder = true;
+            }}
+            "#,
+            threshold
+        )
+    }
+
+    fn reorders(manager: &RuleSetManager, stock: u32) -> bool {
+        let data = HashMap::from([("stock".to_string(), json!(stock))]);
+        let facts = manager
+            .active("purchasing")
+            .unwrap()
+            .evaluate(&data)
+            .unwrap();
+        facts.get("reorder") == Some(&json!(true))
+    }
+
+    #[test]
+    fn test_load_swaps_active_version() {
+        let manager = RuleSetManager::new();
+        assert_eq!(manager.load("purchasing", threshold_rules(10)).unwrap(), 1);
+        assert!(!reorders(&manager, 20));
+
+        assert_eq!(manager.load("purchasing", threshold_rules(50)).unwrap(), 2);
+        assert!(reorders(&manager, 20));
+
+        let versions = manager.versions("purchasing");
+        assert_eq!(versions.len(), 2);
+        assert!(versions[1].active && !versions[0].active);
+    }
+
+    #[test]
+    fn test_invalid_rules_keep_active_version() {
+        let manager = RuleSetManager::new();
+        manager.load("purchasing", threshold_rules(10)).unwrap();
+
+        assert!(manager.load("purchasing", "rule {").is_err());
+        assert!(manager.load("purchasing", "// nothing").is_err());
+        assert_eq!(manager.active("purchasing").unwrap().version(), 1);
+    }
+
+    #[test]
+    fn test_rollback_and_activate() {
+        let manager = RuleSetManager::new();
+        manager.load("purchasing", threshold_rules(10)).unwrap();
+        manager.load("purchasing", threshold_rules(50)).unwrap();
+
+        assert_eq!(manager.rollback("purchasing").unwrap(), 1);
+        assert!(!reorders(&manager, 20));
+        assert!(manager.rollback("purchasing").is_err());
+
+        manager.activate("purchasing", 2).unwrap();
+        assert!(reorders(&manager, 20));
+        assert!(manager.activate("purchasing", 7).is_err());
+        assert!(manager.rollback("unknown").is_err());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let manager = RuleSetManager::with_max_history(2);
+        for threshold in [10, 20, 30] {
+            manager
+                .load("purchasing", threshold_rules(threshold))
+                .unwrap();
+        }
+
+        let versions: Vec<u64> = manager
+            .versions("purchasing")
+            .iter()
+            .map(|v| v.version)
+            .collect();
+        assert_eq!(versions, [2, 3]);
+        assert!(manager.get("purchasing", 1).is_none());
+    }
+}
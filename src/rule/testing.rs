@@ -0,0 +1,316 @@
+//! Fixture-driven tests for GRL rule files.
+//!
+//! A fixture lists test cases, each with input facts and what should come out
+//! of the rules: expected fact values (`expect`, dotted paths allowed) and
+//! rules that must or must not fire. Fixtures are YAML or JSON:
+//!
+//! ```yaml
+//! rules: purchasing_rules.grl   # relative to the fixture file
+//! tests:
+//!   - name: reorders when stock is short
+//!     facts: { available_qty: 20, demand_lead_time: 50 }
+//!     expect: { need_reorder: true, shortage: 30 }
+//!     fired: ["Check if product needs reordering"]
+//!     not_fired: ["Sufficient inventory"]
+//! ```
+//!
+//! The same runner backs `rlg test-rules` and can be called from `cargo test`:
+//!
+//! ```no_run
+//! use rust_logic_graph::rule::testing::RuleTestSuite;
+//!
+//! RuleTestSuite::from_file("rules/purchasing.test.yaml")
+//!     .unwrap()
+//!     .run()
+//!     .unwrap()
+//!     .assert_passed();
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::{RuleEngine, RuleError, RuleTrace};
+use crate::core::path;
+
+/// A set of test cases for one GRL rule file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleTestSuite {
+    /// GRL file under test; relative paths are resolved against the fixture
+    /// file when loaded with [`RuleTestSuite::from_file`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<PathBuf>,
+    pub tests: Vec<RuleTestCase>,
+}
+
+/// Input facts and the expected outcome of running the rules on them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleTestCase {
+    pub name: String,
+    #[serde(default)]
+    pub facts: HashMap<String, JsonValue>,
+    /// Expected fact values after the rules ran, keyed by fact path
+    #[serde(default)]
+    pub expect: HashMap<String, JsonValue>,
+    /// Rules that must fire
+    #[serde(default)]
+    pub fired: Vec<String>,
+    /// Rules that must not fire
+    #[serde(default)]
+    pub not_fired: Vec<String>,
+}
+
+/// Outcome of one test case
+#[derive(Debug, Clone)]
+pub struct RuleTestResult {
+    pub name: String,
+    /// Why the case failed; empty when it passed
+    pub failures: Vec<String>,
+    pub trace: RuleTrace,
+}
+
+/// Outcome of a whole suite, in test case order
+#[derive(Debug, Clone, Default)]
+pub struct RuleTestReport {
+    pub results: Vec<RuleTestResult>,
+}
+
+impl RuleTestSuite {
+    /// Parse a YAML fixture
+    pub fn from_yaml(yaml: &str) -> Result<Self, RuleError> {
+        serde_yaml::from_str(yaml)
+            .map_err(|e| RuleError::Eval(format!("Invalid rule test fixture: {}", e)))
+    }
+
+    /// Parse a JSON fixture
+    pub fn from_json(json: &str) -> Result<Self, RuleError> {
+        serde_json::from_str(json)
+            .map_err(|e| RuleError::Eval(format!("Invalid rule test fixture: {}", e)))
+    }
+
+    /// Load a fixture file; `.json` files are read as JSON, anything else as
+    /// YAML
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RuleError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RuleError::Eval(format!(
+                "Failed to read rule test fixture '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut suite = if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&content)?
+        } else {
+            Self::from_yaml(&content)?
+        };
+        if let (Some(rules), Some(dir)) = (&suite.rules, path.parent()) {
+            suite.rules = Some(dir.join(rules));
+        }
+        Ok(suite)
+    }
+
+    /// Run the suite against the GRL file named in `rules`
+    pub fn run(&self) -> Result<RuleTestReport, RuleError> {
+        let rules = self.rules.as_ref().ok_or_else(|| {
+            RuleError::Eval("Rule test fixture does not name a GRL file in 'rules'".to_string())
+        })?;
+        let grl = std::fs::read_to_string(rules).map_err(|e| {
+            RuleError::Eval(format!(
+                "Failed to read GRL file '{}': {}",
+                rules.display(),
+                e
+            ))
+        })?;
+        self.run_grl(&grl)
+    }
+
+    /// Run the suite against GRL source; fails only if the rules do not
+    /// compile, test failures are reported in the returned report
+    pub fn run_grl(&self, grl: &str) -> Result<RuleTestReport, RuleError> {
+        let engine = RuleEngine::from_grl(grl)?;
+        if engine.inner().knowledge_base().rule_count() == 0 {
+            return Err(RuleError::InvalidExpression(
+                "GRL under test does not define any rules".to_string(),
+            ));
+        }
+        let results = self
+            .tests
+            .iter()
+            .map(|case| case.run(grl))
+            .collect::<Result<_, _>>()?;
+        Ok(RuleTestReport { results })
+    }
+}
+
+impl RuleTestCase {
+    /// Run this case against GRL source, on a fresh engine
+    pub fn run(&self, grl: &str) -> Result<RuleTestResult, RuleError> {
+        let mut engine = RuleEngine::from_grl(grl)?;
+        let (facts, trace) = engine.evaluate_traced(&self.facts)?;
+        let facts: HashMap<String, JsonValue> = match facts {
+            JsonValue::Object(map) => map.into_iter().collect(),
+            _ => HashMap::new(),
+        };
+
+        let mut expected: Vec<_> = self.expect.iter().collect();
+        expected.sort_by(|a, b| a.0.cmp(b.0));
+        let mut failures = Vec::new();
+        for (fact, value) in expected {
+            match path::get(&facts, fact) {
+                Some(actual) if values_match(value, actual) => {}
+                Some(actual) => {
+                    failures.push(format!("expected {} = {}, got {}", fact, value, actual))
+                }
+                None => failures.push(format!("expected {} = {}, but it is not set", fact, value)),
+            }
+        }
+        for rule in &self.fired {
+            if !trace.fired(rule) {
+                failures.push(format!(
+                    "expected rule \"{}\" to fire, but it did not",
+                    rule
+                ));
+            }
+        }
+        for rule in &self.not_fired {
+            if trace.fired(rule) {
+                failures.push(format!(
+                    "expected rule \"{}\" not to fire, but it did",
+                    rule
+                ));
+            }
+        }
+
+        Ok(RuleTestResult {
+            name: self.name.clone(),
+            failures,
+            trace,
+        })
+    }
+}
+
+impl RuleTestResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl RuleTestReport {
+    /// Whether every test case passed
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(RuleTestResult::passed)
+    }
+
+    /// Test cases that failed
+    pub fn failures(&self) -> impl Iterator<Item = &RuleTestResult> {
+        self.results.iter().filter(|result| !result.passed())
+    }
+
+    /// Panic with the report if any test case failed, for use in `cargo test`
+    pub fn assert_passed(&self) {
+        assert!(self.passed(), "rule tests failed:\n{}", self);
+    }
+}
+
+impl fmt::Display for RuleTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            if result.passed() {
+                writeln!(f, "ok      {}", result.name)?;
+            } else {
+                writeln!(f, "FAILED  {}", result.name)?;
+                for failure in &result.failures {
+                    writeln!(f, "        {}", failure)?;
+                }
+            }
+        }
+        let failed = self.failures().count();
+        writeln!(
+            f,
+            "{} tests, {} passed, {} failed",
+            self.results.len(),
+            self.results.len() - failed,
+            failed
+        )
+    }
+}
+
+/// Compare an expected value with an actual one; numbers match by value, so
+/// an expected `30` matches a rule result of `30.0`
+fn values_match(expected: &JsonValue, actual: &JsonValue) -> bool {
+    match (expected, actual) {
+        (JsonValue::Number(a), JsonValue::Number(b)) => a.as_f64() == b.as_f64(),
+        (JsonValue::Array(a), JsonValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_match(a, b))
+        }
+        (JsonValue::Object(a), JsonValue::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| values_match(a, b)))
+        }
+        _ => expected == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const GRL: &str = r#"
+        rule "Reorder" salience 10 no-loop {
+            when
+                stock < 10
+            then
+                reorder = true;
+                shortage = 10 - stock;
+        }
+    "#;
+
+    #[test]
+    fn test_passing_and_failing_cases() {
+        let suite = RuleTestSuite::from_yaml(
+            r#"
+tests:
+  - name: reorders when low
+    facts: { stock: 4 }
+    expect: { reorder: true, shortage: 6 }
+    fired: [Reorder]
+  - name: wrong expectation
+    facts: { stock: 40 }
+    expect: { reorder: true }
+    fired: [Reorder]
+"#,
+        )
+        .unwrap();
+
+        let report = suite.run_grl(GRL).unwrap();
+        assert!(!report.passed());
+        assert!(report.results[0].passed());
+        assert_eq!(
+            report.results[1].failures,
+            vec![
+                "expected reorder = true, but it is not set".to_string(),
+                "expected rule \"Reorder\" to fire, but it did not".to_string(),
+            ]
+        );
+        assert!(report.to_string().contains("2 tests, 1 passed, 1 failed"));
+    }
+
+    #[test]
+    fn test_values_match_numbers_by_value() {
+        assert!(values_match(&json!(30), &json!(30.0)));
+        assert!(values_match(&json!({"a": [1]}), &json!({"a": [1.0]})));
+        assert!(!values_match(&json!(30), &json!("30")));
+    }
+
+    #[test]
+    fn test_invalid_grl_fails_suite() {
+        let suite = RuleTestSuite::from_json(r#"{"tests": [{"name": "any"}]}"#).unwrap();
+        assert!(suite.run_grl("rule {").is_err());
+        assert!(suite.run().is_err());
+    }
+}
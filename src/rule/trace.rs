@@ -0,0 +1,239 @@
+//! Explainability traces for GRL rule evaluation.
+//!
+//! [`RuleEngine::evaluate_traced`](super::RuleEngine::evaluate_traced)
+//! records which rules fired, in firing order, the conditions they matched
+//! with the values the facts had at that point, and every fact each rule
+//! changed. Rules that never fired are listed with the values that kept them
+//! from matching.
+//!
+//! The engine keeps re-firing rules whose conditions still hold until a cycle
+//! changes nothing (or `max_cycles` is reached); a repeat firing is only
+//! listed when it changed facts, but every firing counts towards
+//! [`RuleTrace::rules_fired`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::engine::{fact_to_json, Value};
+use rust_rule_engine::engine::rule::{ConditionExpression, ConditionGroup, Rule as GrlRule};
+use rust_rule_engine::types::Operator;
+
+/// Trace of one rule engine evaluation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleTrace {
+    /// Rules that fired, in firing order
+    pub fired: Vec<FiredRule>,
+    /// Rules that never fired, with their conditions against the final facts
+    pub not_fired: Vec<UnfiredRule>,
+    /// Number of rule condition evaluations
+    pub rules_evaluated: usize,
+    /// Number of times any rule fired, repeats included
+    pub rules_fired: usize,
+    /// Number of engine cycles
+    pub cycles: usize,
+}
+
+/// A rule that fired
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiredRule {
+    pub rule: String,
+    pub salience: i32,
+    /// Conditions with the fact values they were matched against
+    pub conditions: Vec<ConditionMatch>,
+    /// Facts the rule's actions set or changed
+    pub changes: Vec<FactChange>,
+}
+
+/// A rule that did not fire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnfiredRule {
+    pub rule: String,
+    pub salience: i32,
+    pub conditions: Vec<ConditionMatch>,
+}
+
+/// One condition of a rule and the value its field had when evaluated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionMatch {
+    /// Field, function call or expression the condition tests
+    pub field: String,
+    pub operator: String,
+    pub expected: JsonValue,
+    /// Value of `field`; `null` when it is missing or not a plain field
+    pub actual: JsonValue,
+}
+
+/// A fact's value before and after a rule fired; `None` when absent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactChange {
+    pub fact: String,
+    pub before: Option<JsonValue>,
+    pub after: Option<JsonValue>,
+}
+
+impl RuleTrace {
+    /// Whether the rule named `rule` fired
+    pub fn fired(&self, rule: &str) -> bool {
+        self.fired.iter().any(|fired| fired.rule == rule)
+    }
+}
+
+impl fmt::Display for RuleTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} rules fired ({} firings, {} evaluations in {} cycles)",
+            self.fired.len(),
+            self.fired.len() + self.not_fired.len(),
+            self.rules_fired,
+            self.rules_evaluated,
+            self.cycles
+        )?;
+        for (i, fired) in self.fired.iter().enumerate() {
+            writeln!(
+                f,
+                "{}. fired \"{}\" (salience {})",
+                i + 1,
+                fired.rule,
+                fired.salience
+            )?;
+            for condition in &fired.conditions {
+                writeln!(f, "     when {}", condition)?;
+            }
+            for change in &fired.changes {
+                writeln!(
+                    f,
+                    "     set {}: {} -> {}",
+                    change.fact,
+                    display_optional(&change.before),
+                    display_optional(&change.after)
+                )?;
+            }
+        }
+        for unfired in &self.not_fired {
+            writeln!(
+                f,
+                "-  not fired \"{}\" (salience {})",
+                unfired.rule, unfired.salience
+            )?;
+            for condition in &unfired.conditions {
+                writeln!(f, "     when {}", condition)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ConditionMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} (actual: {})",
+            self.field, self.operator, self.expected, self.actual
+        )
+    }
+}
+
+fn display_optional(value: &Option<JsonValue>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "(unset)".to_string(), |v| v.to_string())
+}
+
+/// Facts as seen by the engine at one point of an evaluation
+pub(crate) type FactSnapshot = HashMap<String, Value>;
+
+/// Conditions of `rule`, resolved against `facts`
+pub(crate) fn condition_matches(rule: &GrlRule, facts: &FactSnapshot) -> Vec<ConditionMatch> {
+    let mut matches = Vec::new();
+    collect_conditions(&rule.conditions, facts, &mut matches);
+    matches
+}
+
+fn collect_conditions(group: &ConditionGroup, facts: &FactSnapshot, out: &mut Vec<ConditionMatch>) {
+    match group {
+        ConditionGroup::Single(condition) => {
+            let (field, actual) = match &condition.expression {
+                ConditionExpression::Field(field) => (field.clone(), lookup(facts, field)),
+                ConditionExpression::FunctionCall { name, args }
+                | ConditionExpression::Test { name, args } => {
+                    (format!("{}({})", name, args.join(", ")), JsonValue::Null)
+                }
+                ConditionExpression::MultiField {
+                    field, operation, ..
+                } => (format!("{} {}", field, operation), JsonValue::Null),
+            };
+            out.push(ConditionMatch {
+                field,
+                operator: operator_symbol(&condition.operator).to_string(),
+                expected: fact_to_json(&condition.value),
+                actual,
+            });
+        }
+        ConditionGroup::Compound { left, right, .. } => {
+            collect_conditions(left, facts, out);
+            collect_conditions(right, facts, out);
+        }
+        ConditionGroup::Not(inner)
+        | ConditionGroup::Exists(inner)
+        | ConditionGroup::Forall(inner) => collect_conditions(inner, facts, out),
+        #[allow(unreachable_patterns)]
+        _ => {}
+    }
+}
+
+/// Value of `field`, read through nested objects like the engine does
+fn lookup(facts: &FactSnapshot, field: &str) -> JsonValue {
+    if let Some(value) = facts.get(field) {
+        return fact_to_json(value);
+    }
+    let mut parts = field.split('.');
+    let Some(mut current) = parts.next().and_then(|root| facts.get(root)) else {
+        return JsonValue::Null;
+    };
+    for part in parts {
+        match current {
+            Value::Object(map) => match map.get(part) {
+                Some(value) => current = value,
+                None => return JsonValue::Null,
+            },
+            _ => return JsonValue::Null,
+        }
+    }
+    fact_to_json(current)
+}
+
+/// Facts that differ between two snapshots, in fact name order
+pub(crate) fn fact_changes(before: &FactSnapshot, after: &FactSnapshot) -> Vec<FactChange> {
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| before.get(*name) != after.get(*name))
+        .map(|name| FactChange {
+            fact: name.clone(),
+            before: before.get(name).map(fact_to_json),
+            after: after.get(name).map(fact_to_json),
+        })
+        .collect()
+}
+
+fn operator_symbol(operator: &Operator) -> &'static str {
+    match operator {
+        Operator::Equal => "==",
+        Operator::NotEqual => "!=",
+        Operator::GreaterThan => ">",
+        Operator::GreaterThanOrEqual => ">=",
+        Operator::LessThan => "<",
+        Operator::LessThanOrEqual => "<=",
+        Operator::Contains => "contains",
+        Operator::NotContains => "not_contains",
+        Operator::StartsWith => "startsWith",
+        Operator::EndsWith => "endsWith",
+        Operator::Matches => "matches",
+        Operator::In => "in",
+    }
+}
@@ -0,0 +1,145 @@
+/// Integration tests for cancelling running graph executions
+use rust_logic_graph::{CancellationToken, Graph, GraphIO};
+use serde_json::json;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::executor;
+
+/// Cancel `cancel` after `ms` milliseconds
+fn cancel_after(cancel: &CancellationToken, ms: u64) {
+    let cancel = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        cancel.cancel();
+    });
+}
+
+#[tokio::test]
+async fn test_cancel_drops_running_node_and_stops_scheduling() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  fast:
+    node_type: RuleNode
+    condition: "true"
+  query:
+    node_type: SlowNode
+    settings:
+      sleep_ms: 5000
+  report:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: fast
+    to: query
+  - from: query
+    to: report
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, _) = executor(&graph);
+    let cancel = CancellationToken::new();
+    cancel_after(&cancel, 30);
+
+    let start = Instant::now();
+    let error = executor
+        .execute_with_cancellation(&mut graph, cancel)
+        .await
+        .unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    assert!(error.is_cancelled());
+    assert_eq!(error.code, "E013");
+
+    // The dropped node's writes are discarded; nothing after it ran
+    assert!(graph.context.get("fast_result").is_some());
+    assert!(graph.context.get("query_started").is_none());
+    assert!(graph.context.get("report_result").is_none());
+
+    let metrics = error.metrics().unwrap();
+    assert_eq!(metrics.nodes_executed, 1);
+    assert_eq!(metrics.nodes_cancelled, 1);
+    assert_eq!(metrics.nodes_failed, 0);
+    let query = metrics
+        .node_stats
+        .iter()
+        .find(|s| s.node_id == "query")
+        .unwrap();
+    assert!(query.cancelled && !query.success);
+}
+
+#[tokio::test]
+async fn test_cancel_runs_finally_block_but_not_catch_or_retries() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  query:
+    node_type: SlowNode
+    settings:
+      sleep_ms: 5000
+  retry:
+    node_type: RetryNode
+    target_node: query
+    max_retries: 3
+    initial_delay_ms: 1
+  recover:
+    node_type: RuleNode
+    condition: "true"
+  release:
+    node_type: RuleNode
+    condition: "true"
+  guarded:
+    node_type: TryCatchNode
+    try_node: retry
+    catch_node: recover
+    finally_node: release
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, calls) = executor(&graph);
+    let cancel = CancellationToken::new();
+    cancel_after(&cancel, 30);
+
+    let error = executor
+        .execute_with_cancellation(&mut graph, cancel)
+        .await
+        .unwrap_err();
+    assert!(error.is_cancelled());
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(graph.context.get("release_result").is_some());
+    assert!(graph.context.get("recover_result").is_none());
+    assert!(graph.context.get("guarded_result").is_none());
+}
+
+#[tokio::test]
+async fn test_uncancelled_token_runs_to_completion() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  query:
+    node_type: SlowNode
+    settings:
+      sleep_ms: 1
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, _) = executor(&graph);
+
+    executor
+        .execute_with_cancellation(&mut graph, CancellationToken::new())
+        .await
+        .unwrap();
+    assert_eq!(graph.context.get("query_result"), Some(&json!("done")));
+    assert_eq!(executor.metrics().nodes_cancelled, 0);
+}
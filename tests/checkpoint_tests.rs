@@ -0,0 +1,161 @@
+/// Integration tests for checkpointing executions and resuming them after a
+/// crash
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rust_logic_graph::distributed::{ContextStore, InMemoryStore};
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphDef, GraphIO, Node, NodeConfig, NodeRegistry, NodeType,
+    RuleResult,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Logs every call; with `settings.crash` it panics (after a short delay)
+/// while the shared `crash` flag is set, like a process dying mid-call
+struct StepNode {
+    id: String,
+    crashes: bool,
+    crash: Arc<AtomicBool>,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Node for StepNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("StepNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        self.calls.lock().push(self.id.clone());
+        if self.crashes && self.crash.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            panic!("process crashed while running '{}'", self.id);
+        }
+        let result = json!(format!("{} done", self.id));
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
+
+const PIPELINE_YAML: &str = r#"
+nodes:
+  llm:
+    node_type: StepNode
+  fast:
+    node_type: StepNode
+  summarize:
+    node_type: StepNode
+    settings:
+      crash: true
+edges:
+  - from: llm
+    to: fast
+  - from: llm
+    to: summarize
+"#;
+
+fn executor(
+    def: &GraphDef,
+    store: Arc<InMemoryStore>,
+    crash: bool,
+    calls: Arc<Mutex<Vec<String>>>,
+) -> Executor {
+    let crash = Arc::new(AtomicBool::new(crash));
+    let mut registry = NodeRegistry::default();
+    registry.register("StepNode", move |id: &str, config: &NodeConfig| {
+        let crashes = config
+            .settings
+            .as_ref()
+            .and_then(|s| s.get("crash"))
+            .and_then(|c| c.as_bool())
+            .unwrap_or(false);
+        Ok(Box::new(StepNode {
+            id: id.to_string(),
+            crashes,
+            crash: crash.clone(),
+            calls: calls.clone(),
+        }) as Box<dyn Node>)
+    });
+
+    let mut executor = Executor::from_graph_def_with_registry(def, Arc::new(registry)).unwrap();
+    executor.set_context_store(store);
+    executor
+}
+
+#[tokio::test]
+async fn test_resume_skips_nodes_completed_before_crash() {
+    let def = GraphIO::from_yaml(PIPELINE_YAML).unwrap();
+    let store = Arc::new(InMemoryStore::new());
+    let calls = Arc::new(Mutex::new(Vec::new()));
+
+    // First attempt dies while 'summarize' runs
+    let crashing = executor(&def, store.clone(), true, calls.clone());
+    let mut graph = Graph::new(def.clone());
+    graph.context.set("ticket", json!("T-1"));
+    let attempt =
+        tokio::spawn(async move { crashing.execute_checkpointed(&mut graph, "ticket-1").await });
+    assert!(attempt.await.unwrap_err().is_panic());
+
+    let resumer = executor(&def, store.clone(), false, calls.clone());
+    let checkpoint = resumer.checkpoint("ticket-1").await.unwrap().unwrap();
+    assert_eq!(checkpoint.completed, ["llm", "fast"]);
+    assert_eq!(checkpoint.pending, ["summarize"]);
+    assert!(!checkpoint.finished);
+    assert_eq!(
+        checkpoint.context.get("fast_result"),
+        Some(&json!("fast done"))
+    );
+
+    // Resuming in a "new process" only runs what did not complete
+    calls.lock().clear();
+    let mut graph = Graph::new(def);
+    resumer
+        .execute_checkpointed(&mut graph, "ticket-1")
+        .await
+        .unwrap();
+
+    assert_eq!(*calls.lock(), ["summarize"]);
+    assert_eq!(graph.context.get("ticket"), Some(&json!("T-1")));
+    assert_eq!(graph.context.get("llm_result"), Some(&json!("llm done")));
+    assert_eq!(
+        graph.context.get("summarize_result"),
+        Some(&json!("summarize done"))
+    );
+    assert_eq!(resumer.metrics().nodes_restored, 2);
+    assert_eq!(resumer.metrics().nodes_executed, 1);
+
+    let checkpoint = resumer.checkpoint("ticket-1").await.unwrap().unwrap();
+    assert!(checkpoint.finished);
+    assert_eq!(checkpoint.completed, ["llm", "fast", "summarize"]);
+    assert!(store.exists("checkpoint:ticket-1").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_checkpointing_requires_context_store() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  a:
+    node_type: RuleNode
+    condition: "true"
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+
+    let error = executor
+        .execute_checkpointed(&mut graph, "run-1")
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("requires a context store"));
+    assert!(executor.checkpoint("run-1").await.unwrap().is_none());
+}
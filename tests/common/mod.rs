@@ -0,0 +1,93 @@
+//! Test nodes and executors shared by the integration tests
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use rust_logic_graph::{
+    Context, Executor, Graph, Node, NodeConfig, NodeRegistry, NodeType, RuleError, RuleResult,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Marks itself started, then sleeps `settings.sleep_ms` on its first call
+/// (a hung query) and returns right away on later calls
+pub struct SlowNode {
+    id: String,
+    sleep_ms: u64,
+    node_calls: AtomicUsize,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Node for SlowNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("SlowNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        ctx.set(format!("{}_started", self.id), json!(true));
+        if self.node_calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(self.sleep_ms)).await;
+        }
+        ctx.set(format!("{}_result", self.id), json!("done"));
+        Ok(json!("done"))
+    }
+}
+
+/// Always fails with "connection refused", like a lookup against an
+/// unreachable database
+pub struct FailingNode {
+    id: String,
+}
+
+#[async_trait]
+impl Node for FailingNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("FailingNode".to_string())
+    }
+
+    async fn run(&self, _ctx: &mut Context) -> RuleResult {
+        Err(RuleError::Eval("connection refused".to_string()))
+    }
+}
+
+/// Integer setting `name` of a node, or 0
+pub fn setting(config: &NodeConfig, name: &str) -> u64 {
+    config
+        .settings
+        .as_ref()
+        .and_then(|s| s.get(name))
+        .and_then(|s| s.as_u64())
+        .unwrap_or(0)
+}
+
+/// Executor for `graph` with SlowNode and FailingNode registered, and the
+/// number of SlowNode invocations it makes
+pub fn executor(graph: &Graph) -> (Executor, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut registry = NodeRegistry::default();
+    let counter = calls.clone();
+    registry.register("SlowNode", move |id: &str, config: &NodeConfig| {
+        Ok(Box::new(SlowNode {
+            id: id.to_string(),
+            sleep_ms: setting(config, "sleep_ms"),
+            node_calls: AtomicUsize::new(0),
+            calls: counter.clone(),
+        }) as Box<dyn Node>)
+    });
+    registry.register("FailingNode", |id: &str, _config: &NodeConfig| {
+        Ok(Box::new(FailingNode { id: id.to_string() }) as Box<dyn Node>)
+    });
+    let executor = Executor::from_graph_def_with_registry(&graph.def, Arc::new(registry)).unwrap();
+    (executor, calls)
+}
@@ -0,0 +1,190 @@
+/// Integration tests for concurrent node execution in the Executor
+use async_trait::async_trait;
+use rust_logic_graph::node::NodeType;
+use rust_logic_graph::{Context, Edge, Executor, Graph, GraphDef, Node, NodeConfig, RuleResult};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Sleeps, then writes its result and a value under a shared key
+struct SlowNode {
+    id: String,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Node for SlowNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("SlowNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let input = ctx.get("input").cloned().unwrap_or(json!(null));
+        ctx.set("last_writer", json!(self.id));
+        ctx.set(format!("{}_result", self.id), input.clone());
+        Ok(input)
+    }
+}
+
+/// Records whether `worker_0` had finished when it started
+struct ObserverNode {
+    id: String,
+}
+
+#[async_trait]
+impl Node for ObserverNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("ObserverNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let seen = json!(ctx.get("worker_0_result").is_some());
+        ctx.set(format!("{}_result", self.id), seen.clone());
+        Ok(seen)
+    }
+}
+
+/// `fan_out` independent slow nodes feeding a single `join` node
+fn fan_out_graph(fan_out: usize) -> (Executor, GraphDef, Arc<AtomicUsize>) {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    let mut executor = Executor::new();
+    let mut nodes = HashMap::new();
+    let mut edges = Vec::new();
+
+    for i in 0..fan_out {
+        let id = format!("worker_{}", i);
+        executor.register_node(Box::new(SlowNode {
+            id: id.clone(),
+            in_flight: in_flight.clone(),
+            max_in_flight: max_in_flight.clone(),
+        }));
+        nodes.insert(id.clone(), NodeConfig::rule_node("true"));
+        edges.push(Edge::new(id, "join"));
+    }
+    executor.register_node(Box::new(rust_logic_graph::RuleNode::new("join", "true")));
+    nodes.insert("join".to_string(), NodeConfig::rule_node("true"));
+
+    (executor, GraphDef::new(nodes, edges), max_in_flight)
+}
+
+#[tokio::test]
+async fn test_independent_nodes_run_concurrently() {
+    let (executor, def, max_in_flight) = fan_out_graph(4);
+    let mut graph = Graph::new(def);
+
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 4);
+    assert_eq!(executor.metrics().nodes_executed, 5);
+    assert_eq!(graph.context.get("join_result"), Some(&json!(true)));
+}
+
+#[tokio::test]
+async fn test_max_concurrency_limits_in_flight_nodes() {
+    let (mut executor, def, max_in_flight) = fan_out_graph(4);
+    executor.set_max_concurrency(2);
+    let mut graph = Graph::new(def);
+
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    assert_eq!(executor.metrics().nodes_executed, 5);
+}
+
+#[tokio::test]
+async fn test_concurrent_writes_merge_deterministically() {
+    for _ in 0..5 {
+        let (executor, def, _) = fan_out_graph(3);
+        let mut graph = Graph::new(def);
+        graph.context.set("input", json!(7));
+
+        executor.execute(&mut graph).await.unwrap();
+
+        // Every node's results survive, and the last node id wins on conflicts
+        for i in 0..3 {
+            assert_eq!(
+                graph.context.get(&format!("worker_{}_result", i)),
+                Some(&json!(7))
+            );
+        }
+        assert_eq!(graph.context.get("last_writer"), Some(&json!("worker_2")));
+        let order: Vec<_> = executor
+            .metrics()
+            .node_stats
+            .iter()
+            .map(|s| s.node_id.clone())
+            .collect();
+        assert_eq!(order.len(), 4);
+        assert_eq!(order[3], "join");
+    }
+}
+
+#[tokio::test]
+async fn test_nodes_start_without_waiting_for_unrelated_nodes() {
+    // worker_0 is slow; fast -> observer does not depend on it
+    let (mut executor, _, _) = fan_out_graph(1);
+    executor.register_node(Box::new(rust_logic_graph::RuleNode::new("fast", "true")));
+    executor.register_node(Box::new(ObserverNode {
+        id: "observer".to_string(),
+    }));
+    let nodes = ["worker_0", "fast", "observer"]
+        .into_iter()
+        .map(|id| (id.to_string(), NodeConfig::rule_node("true")))
+        .collect();
+    let mut graph = Graph::new(GraphDef::new(nodes, vec![Edge::new("fast", "observer")]));
+
+    executor.execute(&mut graph).await.unwrap();
+
+    // observer ran while worker_0 was still sleeping
+    assert_eq!(graph.context.get("observer_result"), Some(&json!(false)));
+    assert!(graph.context.get("worker_0_result").is_some());
+    let order: Vec<_> = executor
+        .metrics()
+        .node_stats
+        .iter()
+        .map(|s| s.node_id.clone())
+        .collect();
+    assert_eq!(order, ["fast", "observer", "worker_0"]);
+}
+
+#[tokio::test]
+async fn test_shared_executor_runs_graphs_concurrently() {
+    let (executor, def, _) = fan_out_graph(2);
+    let executor = Arc::new(executor);
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let executor = executor.clone();
+            let def = def.clone();
+            tokio::spawn(async move {
+                let mut graph = Graph::new(def);
+                graph.context.set("input", json!(i));
+                executor.execute(&mut graph).await.unwrap();
+                graph
+            })
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        let graph = handle.await.unwrap();
+        assert_eq!(graph.context.get("worker_0_result"), Some(&json!(i)));
+        assert_eq!(graph.context.get("worker_1_result"), Some(&json!(i)));
+    }
+}
@@ -1,6 +1,24 @@
 /// Integration tests for DBNode params feature
-use rust_logic_graph::{Executor, Graph, GraphDef, NodeConfig};
+use async_trait::async_trait;
+use rust_logic_graph::{DBNode, DatabaseExecutor, Executor, Graph, GraphDef, NodeConfig};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Records the params of every query it runs
+#[derive(Default)]
+struct RecordingExecutor {
+    params: Mutex<Vec<Vec<String>>>,
+}
+
+#[async_trait]
+impl DatabaseExecutor for RecordingExecutor {
+    async fn execute(&self, _query: &str, params: &[&str]) -> Result<Value, String> {
+        let params = params.iter().map(|p| p.to_string()).collect();
+        self.params.lock().unwrap().push(params);
+        Ok(serde_json::json!({"rows": []}))
+    }
+}
 
 #[tokio::test]
 async fn test_db_node_with_single_param() {
@@ -15,16 +33,13 @@ async fn test_db_node_with_single_param() {
         ),
     );
 
-    let def = GraphDef {
-        nodes,
-        edges: vec![],
-    };
+    let def = GraphDef::new(nodes, vec![]);
     let mut graph = Graph::new(def);
 
     // Set the parameter in context
     graph.context.set("item_id", serde_json::json!("ID-123"));
 
-    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
     let result = executor.execute(&mut graph).await;
 
     assert!(result.is_ok());
@@ -44,17 +59,14 @@ async fn test_db_node_with_multiple_params() {
         ),
     );
 
-    let def = GraphDef {
-        nodes,
-        edges: vec![],
-    };
+    let def = GraphDef::new(nodes, vec![]);
     let mut graph = Graph::new(def);
 
     // Set parameters in context
     graph.context.set("user_id", serde_json::json!("USER-456"));
     graph.context.set("status", serde_json::json!("active"));
 
-    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
     let result = executor.execute(&mut graph).await;
 
     assert!(result.is_ok());
@@ -71,13 +83,10 @@ async fn test_db_node_without_params() {
         NodeConfig::db_node("SELECT * FROM table"),
     );
 
-    let def = GraphDef {
-        nodes,
-        edges: vec![],
-    };
+    let def = GraphDef::new(nodes, vec![]);
     let mut graph = Graph::new(def);
 
-    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
     let result = executor.execute(&mut graph).await;
 
     assert!(result.is_ok());
@@ -97,14 +106,11 @@ async fn test_db_node_with_missing_context_param() {
         ),
     );
 
-    let def = GraphDef {
-        nodes,
-        edges: vec![],
-    };
+    let def = GraphDef::new(nodes, vec![]);
     let mut graph = Graph::new(def);
 
     // Don't set the parameter - should still execute but with empty params
-    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
     let result = executor.execute(&mut graph).await;
 
     assert!(result.is_ok());
@@ -126,10 +132,7 @@ async fn test_db_node_with_different_value_types() {
         ),
     );
 
-    let def = GraphDef {
-        nodes,
-        edges: vec![],
-    };
+    let def = GraphDef::new(nodes, vec![]);
     let mut graph = Graph::new(def);
 
     // Test with different JSON value types
@@ -139,12 +142,46 @@ async fn test_db_node_with_different_value_types() {
     graph.context.set("price", serde_json::json!(99.99));
     graph.context.set("is_active", serde_json::json!(true));
 
-    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
     let result = executor.execute(&mut graph).await;
 
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_db_node_with_nested_path_params() {
+    let mut nodes = HashMap::new();
+    nodes.insert(
+        "db_query".to_string(),
+        NodeConfig::db_node("SELECT * FROM orders WHERE customer_id = $1 AND sku = $2"),
+    );
+
+    let def = GraphDef::new(nodes, vec![]);
+    let mut graph = Graph::new(def);
+    graph.context.set(
+        "customer_result",
+        serde_json::json!({"profile": {"id": "C-9"}, "cart": [{"sku": "SKU-1"}]}),
+    );
+
+    let recorder = Arc::new(RecordingExecutor::default());
+    let mut executor = Executor::new();
+    executor.register_node(Box::new(DBNode::with_executor_and_params(
+        "db_query",
+        "SELECT * FROM orders WHERE customer_id = $1 AND sku = $2",
+        recorder.clone(),
+        vec![
+            "customer_result.profile.id".to_string(),
+            "customer_result.cart[0].sku".to_string(),
+        ],
+    )));
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(
+        *recorder.params.lock().unwrap(),
+        vec![vec!["C-9".to_string(), "SKU-1".to_string()]]
+    );
+}
+
 #[test]
 fn test_json_serialization_with_params() {
     let config = NodeConfig::db_node_with_params(
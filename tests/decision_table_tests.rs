@@ -0,0 +1,109 @@
+/// Integration tests for decision tables
+use rust_logic_graph::rule::{DecisionTable, HitPolicy};
+use rust_logic_graph::{Executor, Graph, GraphDef, GraphIO, NodeType, RuleEngine};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_decision_table_node_from_csv() {
+    let yaml = r#"
+nodes:
+  discount:
+    node_type: DecisionTableNode
+    table_file: examples/supplier_discounts.csv
+  approve:
+    node_type: RuleNode
+    condition: "discount >= 0.1"
+edges:
+  - { from: discount, to: approve }
+"#;
+    let mut graph = Graph::new(GraphIO::from_yaml(yaml).unwrap());
+    graph.context.set("supplier_tier", json!("gold"));
+    graph.context.set("order_qty", json!(800));
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(executor.metrics().nodes_failed, 0);
+    assert_eq!(graph.context.get("discount"), Some(&json!(0.15)));
+    assert_eq!(
+        graph.context.get("discount_result"),
+        Some(&json!({"discount": 0.15, "discount_reason": "gold volume"}))
+    );
+    assert_eq!(graph.context.get("approve_result"), Some(&json!(true)));
+}
+
+#[tokio::test]
+async fn test_inline_collect_table() {
+    let yaml = r#"
+nodes:
+  checks:
+    node_type: DecisionTableNode
+    table:
+      hit_policy: collect
+      columns: [order.amount, order.country, "out:flags"]
+      rows:
+        - [">= 1000", "-", large]
+        - ["-", "XX, YY", high_risk_country]
+        - ["< 0", "-", negative]
+edges: []
+"#;
+    let mut graph = Graph::new(GraphIO::from_yaml(yaml).unwrap());
+    graph
+        .context
+        .set("order", json!({"amount": 2500, "country": "XX"}));
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(
+        graph.context.get("flags"),
+        Some(&json!(["large", "high_risk_country"]))
+    );
+}
+
+#[test]
+fn test_csv_table_in_rule_engine() {
+    let table = DecisionTable::from_file("examples/supplier_discounts.csv").unwrap();
+    assert_eq!(table.name(), "supplier_discounts");
+    assert_eq!(table.hit_policy(), HitPolicy::First);
+
+    let mut engine = RuleEngine::new();
+    engine.add_decision_table(&table).unwrap();
+    let facts = HashMap::from([
+        ("supplier_tier".to_string(), json!("bronze")),
+        ("order_qty".to_string(), json!(600)),
+    ]);
+    let result = engine.evaluate(&facts).unwrap();
+    assert_eq!(result["discount"], json!(0.05));
+    assert_eq!(result["discount_reason"], json!("volume"));
+}
+
+#[test]
+fn test_invalid_table_rejected_when_building() {
+    let yaml = r#"
+nodes:
+  discount:
+    node_type: DecisionTableNode
+    hit_policy: priority
+    table:
+      columns: [tier, "out:discount"]
+      rows: [[gold, 0.1]]
+edges: []
+"#;
+    let def = GraphIO::from_yaml(yaml).unwrap();
+    let err = Executor::from_graph_def(&def).err().unwrap();
+    assert!(err.to_string().contains("needs a 'priority' column"));
+}
+
+#[test]
+fn test_decision_table_from_node_types_needs_a_table() {
+    let nodes = HashMap::from([("discount".to_string(), NodeType::DecisionTableNode)]);
+    let def = GraphDef::from_node_types(nodes, vec![]);
+    assert_eq!(def.nodes["discount"].node_type, NodeType::DecisionTableNode);
+
+    let err = Executor::from_graph_def(&def).err().unwrap();
+    assert!(err
+        .to_string()
+        .contains("requires either 'table' or 'table_file'"));
+}
@@ -0,0 +1,156 @@
+/// Integration tests for conditional edges and dead-path elimination
+use rust_logic_graph::{Executor, Graph, GraphIO};
+use serde_json::json;
+
+const LOAN_YAML: &str = r#"
+nodes:
+  score:
+    node_type: RuleNode
+    condition: "true"
+  approve:
+    node_type: RuleNode
+    condition: "true"
+  review:
+    node_type: RuleNode
+    condition: "true"
+  review_audit:
+    node_type: RuleNode
+    condition: "true"
+  notify:
+    node_type: RuleNode
+    condition: "true"
+rules:
+  good_credit: "credit_score >= 700"
+  needs_review:
+    grl: |
+      rule "ManualReview" {
+        when
+          credit_score < 700
+        then
+          passed = true;
+      }
+edges:
+  - from: score
+    to: approve
+    rule: good_credit
+  - from: score
+    to: review
+    rule: needs_review
+  - from: review
+    to: review_audit
+  - from: approve
+    to: notify
+  - from: review_audit
+    to: notify
+"#;
+
+async fn run_loan(credit_score: i64) -> (Graph, Executor) {
+    let mut graph = Graph::new(GraphIO::from_yaml(LOAN_YAML).unwrap());
+    graph.context.set("credit_score", json!(credit_score));
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+    (graph, executor)
+}
+
+#[tokio::test]
+async fn test_false_condition_skips_branch_transitively() {
+    let (graph, executor) = run_loan(750).await;
+
+    assert!(graph.context.get("approve_result").is_some());
+    assert!(graph.context.get("review_result").is_none());
+    // review_audit's only input was skipped, so it is skipped too
+    assert!(graph.context.get("review_audit_result").is_none());
+    // notify still has a live input through approve
+    assert!(graph.context.get("notify_result").is_some());
+    assert_eq!(executor.metrics().nodes_skipped, 2);
+}
+
+#[tokio::test]
+async fn test_grl_named_rule_selects_branch() {
+    let (graph, executor) = run_loan(600).await;
+
+    assert!(graph.context.get("approve_result").is_none());
+    assert!(graph.context.get("review_result").is_some());
+    assert!(graph.context.get("review_audit_result").is_some());
+    assert!(graph.context.get("notify_result").is_some());
+    assert_eq!(executor.metrics().nodes_skipped, 1);
+}
+
+#[tokio::test]
+async fn test_inline_edge_condition() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  start:
+    node_type: RuleNode
+    condition: "true"
+  large:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: start
+    to: large
+    rule: "amount > 1000"
+"#,
+        )
+        .unwrap(),
+    );
+    graph.context.set("amount", json!(10));
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert!(graph.context.get("large_result").is_none());
+    assert_eq!(executor.metrics().nodes_skipped, 1);
+}
+
+#[test]
+fn test_invalid_grl_rule_fails_validation() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  a:
+    node_type: RuleNode
+rules:
+  broken:
+    grl: "rule {"
+edges: []
+"#,
+    )
+    .unwrap();
+
+    let err = def.validate().unwrap_err();
+    assert!(err.to_string().contains("broken"));
+}
+
+#[test]
+fn test_undefined_rule_name_fails_validation() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  a: { node_type: RuleNode, condition: "true" }
+  b: { node_type: RuleNode, condition: "true" }
+edges:
+  - { from: a, to: b, rule: r1 }
+"#,
+    )
+    .unwrap();
+
+    let err = def.validate().unwrap_err();
+    assert!(err.message.contains("references rule 'r1'"));
+}
+
+#[test]
+fn test_example_graphs_validate() {
+    for file in [
+        "examples/simple_flow.yaml",
+        "examples/advanced_flow.yaml",
+        "examples/grl_graph_flow.yaml",
+    ] {
+        let def = GraphIO::load_from_yaml_file(file).unwrap();
+        def.validate()
+            .unwrap_or_else(|e| panic!("{} is invalid: {}", file, e));
+    }
+}
@@ -0,0 +1,198 @@
+/// Integration tests for execution events and listeners
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rust_logic_graph::{
+    AsyncExecutionListener, CacheConfig, CacheManager, ExecutionEvent, ExecutionListener,
+    ExecutionStatus, Executor, Graph, GraphIO, RuleResult,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+const GRAPH_YAML: &str = r#"
+name: orders
+nodes:
+  lookup:
+    node_type: FailingNode
+  score:
+    node_type: RuleNode
+    condition: "true"
+  review:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: score
+    to: review
+    rule: "amount > 1000"
+"#;
+
+fn executor(yaml: &str) -> (Graph, Executor) {
+    let mut graph = Graph::new(GraphIO::from_yaml(yaml).unwrap());
+    graph.context.set("amount", json!(50));
+    let (executor, _) = common::executor(&graph);
+    (graph, executor)
+}
+
+/// Short description of an event, e.g. `"finished:score"`
+fn describe(event: &ExecutionEvent) -> String {
+    match event {
+        ExecutionEvent::GraphStarted { node_count, .. } => format!("graph_started:{}", node_count),
+        ExecutionEvent::NodeScheduled { node_id } => format!("scheduled:{}", node_id),
+        ExecutionEvent::NodeStarted { node_id } => format!("started:{}", node_id),
+        ExecutionEvent::NodeFinished { node_id, .. } => format!("finished:{}", node_id),
+        ExecutionEvent::NodeSkipped { node_id, .. } => format!("skipped:{}", node_id),
+        ExecutionEvent::CacheHit { node_id } => format!("cache_hit:{}", node_id),
+        ExecutionEvent::FallbackApplied { node_id, .. } => format!("fallback:{}", node_id),
+        ExecutionEvent::GraphFinished { .. } => "graph_finished".to_string(),
+    }
+}
+
+#[derive(Default)]
+struct EventLog {
+    events: Mutex<Vec<ExecutionEvent>>,
+}
+
+impl ExecutionListener for EventLog {
+    fn on_event(&self, event: &ExecutionEvent) {
+        self.events.lock().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_listener_sees_node_lifecycle_in_order() {
+    let (mut graph, mut executor) = executor(GRAPH_YAML);
+    let log = Arc::new(EventLog::default());
+    executor.add_listener(log.clone());
+
+    executor.execute(&mut graph).await.unwrap();
+
+    let events = log.events.lock();
+    let described: Vec<String> = events.iter().map(describe).collect();
+    assert_eq!(described.first().unwrap(), "graph_started:3");
+    assert_eq!(described.last().unwrap(), "graph_finished");
+
+    let position = |name: &str| described.iter().position(|d| d == name).unwrap();
+    assert!(position("scheduled:score") < position("started:score"));
+    assert!(position("started:score") < position("finished:score"));
+    assert!(position("started:lookup") < position("fallback:lookup"));
+    assert!(position("fallback:lookup") < position("finished:lookup"));
+    assert!(position("finished:score") < position("skipped:review"));
+    assert!(!described.contains(&"started:review".to_string()));
+
+    match events.last().unwrap() {
+        ExecutionEvent::GraphFinished {
+            graph,
+            status,
+            error,
+            ..
+        } => {
+            assert_eq!(graph.as_deref(), Some("orders"));
+            assert_eq!(*status, Some(ExecutionStatus::CompletedWithFailures));
+            assert!(error.is_none());
+        }
+        other => panic!("unexpected event {:?}", other),
+    }
+}
+
+/// Counts node completions and cache hits through the per-event callbacks
+#[derive(Default)]
+struct Counters {
+    finished: AtomicUsize,
+    failed: AtomicUsize,
+    cache_hits: AtomicUsize,
+}
+
+impl ExecutionListener for Counters {
+    fn on_node_finished(&self, _node_id: &str, result: &RuleResult, _duration: Duration) {
+        self.finished.fetch_add(1, Ordering::SeqCst);
+        if result.is_err() {
+            self.failed.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn on_cache_hit(&self, _node_id: &str) {
+        self.cache_hits.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn test_callbacks_report_cache_hits() {
+    let (mut graph, mut executor) = executor(GRAPH_YAML);
+    executor.set_cache(CacheManager::new(CacheConfig::default()).await.unwrap());
+    let counters = Arc::new(Counters::default());
+    executor.add_listener(counters.clone());
+
+    executor.execute(&mut graph).await.unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    // The failed lookup is not cached and fails again on the second run
+    assert_eq!(counters.finished.load(Ordering::SeqCst), 4);
+    assert_eq!(counters.failed.load(Ordering::SeqCst), 2);
+    assert_eq!(counters.cache_hits.load(Ordering::SeqCst), 1);
+}
+
+/// Writes events somewhere slow, e.g. an audit table
+#[derive(Default)]
+struct AuditLog {
+    events: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl AsyncExecutionListener for AuditLog {
+    async fn on_event(&self, event: ExecutionEvent) {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        self.events.lock().push(describe(&event));
+    }
+}
+
+#[tokio::test]
+async fn test_async_listener_is_awaited() {
+    let yaml = format!("failure_policy: fail_fast\n{}", GRAPH_YAML);
+    let (mut graph, mut executor) = executor(&yaml);
+    let audit = Arc::new(AuditLog::default());
+    executor.add_async_listener(audit.clone());
+
+    executor.execute(&mut graph).await.unwrap_err();
+
+    let events = audit.events.lock();
+    assert!(events.contains(&"finished:lookup".to_string()));
+    assert!(!events.contains(&"fallback:lookup".to_string()));
+    assert_eq!(events.last().unwrap(), "graph_finished");
+}
+
+#[tokio::test]
+async fn test_broadcast_subscribers_receive_events() {
+    let (mut graph, executor) = executor(GRAPH_YAML);
+    let executor = Arc::new(executor);
+    let mut events = executor.subscribe();
+
+    let running = executor.clone();
+    let run = tokio::spawn(async move { running.execute(&mut graph).await.map(|r| r.status) });
+
+    let mut received = Vec::new();
+    loop {
+        let event = events.recv().await.unwrap();
+        let finished = matches!(event, ExecutionEvent::GraphFinished { .. });
+        received.push(describe(&event));
+        if finished {
+            break;
+        }
+    }
+
+    assert_eq!(
+        run.await.unwrap().unwrap(),
+        ExecutionStatus::CompletedWithFailures
+    );
+    assert_eq!(received[0], "graph_started:3");
+    assert!(received.contains(&"skipped:review".to_string()));
+    assert_eq!(
+        received
+            .iter()
+            .filter(|e| e.starts_with("finished:"))
+            .count(),
+        2
+    );
+}
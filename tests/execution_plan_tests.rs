@@ -0,0 +1,135 @@
+/// Integration tests for compiling a graph once and running it many times
+use rust_logic_graph::{Context, Edge, Executor, GraphDef, GraphIO, NodeConfig};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const PRICING_YAML: &str = r#"
+nodes:
+  load:
+    node_type: RuleNode
+    condition: "true"
+  discount:
+    node_type: RuleNode
+    condition: "true"
+  premium:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: load
+    to: discount
+    rule: "quantity >= 10"
+  - from: load
+    to: premium
+    rule: "quantity >= 100"
+"#;
+
+#[tokio::test]
+async fn test_plan_reused_across_runs() {
+    let def = GraphIO::from_yaml(PRICING_YAML).unwrap();
+    let executor = Executor::from_graph_def(&def).unwrap();
+    let plan = executor.compile(&def).unwrap();
+
+    assert_eq!(
+        plan.layers(),
+        vec![vec!["load"], vec!["discount", "premium"]]
+    );
+
+    for (quantity, discounted, premium) in [(5, false, false), (50, true, false), (500, true, true)]
+    {
+        let mut ctx = Context::new();
+        ctx.set("quantity", json!(quantity));
+        executor.execute_plan(&plan, &mut ctx).await.unwrap();
+
+        assert_eq!(ctx.get("discount_result").is_some(), discounted);
+        assert_eq!(ctx.get("premium_result").is_some(), premium);
+        assert_eq!(
+            executor.metrics().nodes_skipped,
+            usize::from(!discounted) + usize::from(!premium)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_plan_shared_between_tasks() {
+    let def = GraphIO::from_yaml(PRICING_YAML).unwrap();
+    let executor = Arc::new(Executor::from_graph_def(&def).unwrap());
+    let plan = Arc::new(executor.compile(&def).unwrap());
+
+    let handles: Vec<_> = (0..16)
+        .map(|i| {
+            let executor = executor.clone();
+            let plan = plan.clone();
+            tokio::spawn(async move {
+                let mut ctx = Context::new();
+                ctx.set("quantity", json!(i * 10));
+                executor.execute_plan(&plan, &mut ctx).await.unwrap();
+                ctx
+            })
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        let ctx = handle.await.unwrap();
+        assert_eq!(ctx.get("discount_result").is_some(), i >= 1);
+        assert_eq!(ctx.get("premium_result").is_some(), i >= 10);
+    }
+}
+
+#[test]
+fn test_compile_rejects_invalid_graphs() {
+    let nodes: HashMap<_, _> = ["a", "b"]
+        .iter()
+        .map(|id| (id.to_string(), NodeConfig::rule_node("true")))
+        .collect();
+    let executor = Executor::new();
+
+    let cyclic = GraphDef::new(
+        nodes.clone(),
+        vec![Edge::new("a", "b"), Edge::new("b", "a")],
+    );
+    let err = executor.compile(&cyclic).unwrap_err();
+    assert!(err.to_string().contains("Cycle detected"));
+
+    let dangling = GraphDef::new(nodes.clone(), vec![Edge::new("a", "missing")]);
+    assert!(executor.compile(&dangling).is_err());
+
+    let bad_rule = GraphDef::new(nodes, vec![Edge::new("a", "b").with_rule("quantity >")]);
+    let err = executor.compile(&bad_rule).unwrap_err();
+    assert!(err.to_string().contains("Invalid condition on edge a -> b"));
+}
+
+#[test]
+fn test_invalid_node_condition_rejected() {
+    let yaml = PRICING_YAML.replacen("condition: \"true\"", "condition: \"(quantity > 1\"", 1);
+    let def = GraphIO::from_yaml(&yaml).unwrap();
+
+    let err = Executor::from_graph_def(&def).err().unwrap();
+    assert!(err.to_string().contains("invalid condition"));
+}
+
+#[test]
+fn test_compile_errors_are_structured() {
+    let nodes: HashMap<_, _> = ["a", "b"]
+        .iter()
+        .map(|id| (id.to_string(), NodeConfig::rule_node("true")))
+        .collect();
+    let executor = Executor::new();
+
+    let cyclic =
+        GraphDef::new(nodes, vec![Edge::new("a", "b"), Edge::new("b", "a")]).with_name("pricing");
+    let err = executor.compile(&cyclic).unwrap_err();
+    assert_eq!(err.code, "E006");
+    assert!(err.is_permanent());
+    assert_eq!(err.context.graph_name.as_deref(), Some("pricing"));
+
+    let yaml = PRICING_YAML.replacen("condition: \"true\"", "condition: \"(quantity > 1\"", 1);
+    let err = Executor::from_graph_def(&GraphIO::from_yaml(&yaml).unwrap())
+        .err()
+        .unwrap();
+    assert_eq!(err.code, "E004");
+    assert_eq!(err.context.node_id.as_deref(), Some("load"));
+
+    let err = GraphIO::from_yaml("nodes: [").unwrap_err();
+    assert_eq!(err.code, "E007");
+}
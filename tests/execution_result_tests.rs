@@ -0,0 +1,140 @@
+/// Integration tests for the structured results of graph executions
+use rust_logic_graph::{ExecutionStatus, Executor, Graph, GraphIO, NodeStatus, RuleError};
+use serde_json::json;
+
+mod common;
+use common::executor;
+
+const ROUTING_YAML: &str = r#"
+name: routing
+nodes:
+  score:
+    node_type: RuleNode
+    condition: "true"
+  approve:
+    node_type: RuleNode
+    condition: "true"
+  review:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: score
+    to: approve
+    rule: "credit_score >= 700"
+  - from: score
+    to: review
+    rule: "credit_score < 700"
+outputs:
+  approved: { node: approve }
+  reviewed: { node: review }
+  score: { context: credit_score }
+"#;
+
+fn failing_graph() -> (Graph, Executor) {
+    let graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  lookup:
+    node_type: FailingNode
+  report:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: lookup
+    to: report
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, _) = executor(&graph);
+    (graph, executor)
+}
+
+#[tokio::test]
+async fn test_result_reports_outputs_and_node_statuses() {
+    let mut graph = Graph::new(GraphIO::from_yaml(ROUTING_YAML).unwrap());
+    graph.context.set("credit_score", json!(750));
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+
+    let result = executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(result.status, ExecutionStatus::Completed);
+    assert!(result.is_success());
+    assert_eq!(result.output("score"), Some(&json!(750)));
+    assert_eq!(
+        result.output("approved"),
+        graph.context.get("approve_result")
+    );
+    assert_eq!(result.output("reviewed"), None);
+    assert_eq!(result.outputs.len(), 2);
+
+    assert_eq!(result.node("score"), Some(&NodeStatus::Succeeded));
+    assert_eq!(result.node("approve"), Some(&NodeStatus::Succeeded));
+    assert!(matches!(
+        result.node("review"),
+        Some(NodeStatus::Skipped { reason }) if reason.contains("incoming edges")
+    ));
+    assert_eq!(result.execution_order, vec!["score", "approve"]);
+    assert_eq!(result.metrics.nodes_executed, 2);
+    assert_eq!(result.metrics.nodes_skipped, 1);
+}
+
+#[tokio::test]
+async fn test_result_serializes_to_json() {
+    let mut graph = Graph::new(GraphIO::from_yaml(ROUTING_YAML).unwrap());
+    graph.context.set("credit_score", json!(600));
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+
+    let result = executor.execute(&mut graph).await.unwrap();
+    let value = serde_json::to_value(&result).unwrap();
+
+    assert_eq!(value["status"], "completed");
+    assert_eq!(value["outputs"]["score"], 600);
+    assert_eq!(value["nodes"]["review"]["status"], "succeeded");
+    assert_eq!(value["nodes"]["approve"]["status"], "skipped");
+    assert_eq!(value["execution_order"], json!(["score", "review"]));
+    assert_eq!(value["metrics"]["nodes_executed"], 2);
+}
+
+#[tokio::test]
+async fn test_tolerated_failures_are_reported() {
+    let (mut graph, executor) = failing_graph();
+
+    let result = executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(result.status, ExecutionStatus::CompletedWithFailures);
+    assert!(!result.is_success());
+    assert_eq!(
+        result.node("lookup"),
+        Some(&NodeStatus::Fallback {
+            error: RuleError::Eval("connection refused".to_string())
+        })
+    );
+    assert_eq!(result.node("report"), Some(&NodeStatus::Succeeded));
+    let failures: Vec<_> = result.failures().map(|(id, _)| id).collect();
+    assert_eq!(failures, vec!["lookup"]);
+}
+
+#[tokio::test]
+async fn test_failure_without_fallback_result() {
+    let (mut graph, mut executor) = failing_graph();
+    executor.set_fallback_handler(|_, _| None);
+
+    let result = executor.execute(&mut graph).await.unwrap();
+
+    assert!(matches!(
+        result.node("lookup"),
+        Some(NodeStatus::Failed { .. })
+    ));
+    assert!(graph.context.get("lookup_result").is_none());
+}
+
+#[tokio::test]
+async fn test_outputs_must_reference_existing_nodes() {
+    let yaml = ROUTING_YAML.replace("{ node: approve }", "{ node: approval }");
+    let error = GraphIO::from_yaml(&yaml).unwrap().validate().unwrap_err();
+
+    assert_eq!(error.code, "E006");
+    assert!(error.message.contains("Graph output 'approved'"));
+}
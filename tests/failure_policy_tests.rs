@@ -0,0 +1,162 @@
+/// Integration tests for graph failure policies and required/optional nodes
+use rust_logic_graph::{
+    CacheConfig, CacheManager, Context, FailurePolicy, Graph, GraphIO, NodeConfig, RuleError,
+};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+mod common;
+use common::executor;
+
+/// `lookup` fails; `audit` runs alongside it and `report` after it
+fn graph(header: &str, lookup: &str) -> Graph {
+    let yaml = format!(
+        r#"
+{}
+nodes:
+  lookup:
+    node_type: FailingNode
+{}
+  audit:
+    node_type: RuleNode
+    condition: "true"
+  report:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: lookup
+    to: report
+"#,
+        header, lookup
+    );
+    Graph::new(GraphIO::from_yaml(&yaml).unwrap())
+}
+
+#[tokio::test]
+async fn test_continue_policy_reports_failures() {
+    let mut graph = graph("", "");
+    assert_eq!(graph.def.failure_policy, FailurePolicy::Continue);
+    let (executor, _) = executor(&graph);
+
+    executor.execute(&mut graph).await.unwrap();
+
+    assert!(graph.context.get("report_result").is_some());
+    let metrics = executor.metrics();
+    assert_eq!(metrics.nodes_failed, 1);
+    let failures: Vec<_> = metrics.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, "lookup");
+    assert!(matches!(failures[0].1, RuleError::Eval(m) if m == "connection refused"));
+}
+
+#[tokio::test]
+async fn test_fail_fast_aborts_on_first_failure() {
+    let mut graph = graph("name: checkout\nfailure_policy: fail_fast", "");
+    let (executor, _) = executor(&graph);
+
+    let error = executor.execute(&mut graph).await.unwrap_err();
+    assert_eq!(error.code, "E001");
+    assert!(error.message.contains("Required node 'lookup' failed"));
+    assert_eq!(error.context.node_id.as_deref(), Some("lookup"));
+    assert_eq!(error.context.graph_name.as_deref(), Some("checkout"));
+    let failures: Vec<_> = error.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, "lookup");
+    assert!(matches!(failures[0].1, RuleError::Eval(m) if m == "connection refused"));
+
+    // `audit` started first and finished before `lookup` failed; later nodes
+    // never start, and the failed node gets no fallback result
+    assert!(graph.context.get("audit_result").is_some());
+    assert!(graph.context.get("report_result").is_none());
+    assert!(graph.context.get("lookup_result").is_none());
+    assert_eq!(error.metrics().unwrap().nodes_failed, 1);
+}
+
+#[tokio::test]
+async fn test_fail_fast_cancels_running_nodes() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+failure_policy: fail_fast
+nodes:
+  lookup:
+    node_type: FailingNode
+  fetch:
+    node_type: SlowNode
+    settings:
+      sleep_ms: 5000
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, calls) = executor(&graph);
+
+    let start = Instant::now();
+    let error = executor.execute(&mut graph).await.unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    assert_eq!(error.context.node_id.as_deref(), Some("lookup"));
+    assert!(!error.is_cancelled());
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    // `fetch` started first; it is dropped and its writes are discarded
+    assert!(graph.context.get("fetch_started").is_none());
+    let metrics = error.metrics().unwrap();
+    assert_eq!(metrics.nodes_cancelled, 1);
+    assert_eq!(error.failures().count(), 1);
+}
+
+fn fallback(node_id: &str, ctx: &mut Context) -> Option<Value> {
+    let value = json!({"fallback": true});
+    ctx.set(format!("{}_result", node_id), value.clone());
+    Some(value)
+}
+
+#[tokio::test]
+async fn test_failed_nodes_are_not_cached() {
+    let (mut executor, _) = executor(&graph("", ""));
+    executor.set_cache(CacheManager::new(CacheConfig::default()).await.unwrap());
+    executor.set_fallback_handler(fallback);
+
+    for _ in 0..2 {
+        let mut graph = graph("", "");
+        executor.execute(&mut graph).await.unwrap();
+
+        assert_eq!(
+            graph.context.get("lookup_result"),
+            Some(&json!({"fallback": true}))
+        );
+        let metrics = executor.metrics();
+        assert_eq!(metrics.nodes_failed, 1);
+        // `audit` and `report` come from the cache on the second run
+        assert!(!metrics
+            .node_stats
+            .iter()
+            .any(|stats| stats.node_id == "lookup" && stats.cache_hit));
+    }
+    assert_eq!(executor.metrics().cache_hits, 2);
+}
+
+#[tokio::test]
+async fn test_optional_node_is_tolerated_under_fail_fast() {
+    let mut graph = graph("failure_policy: fail_fast", "    required: false");
+    let (executor, _) = executor(&graph);
+
+    executor.execute(&mut graph).await.unwrap();
+    assert!(graph.context.get("report_result").is_some());
+    assert_eq!(executor.metrics().failures().count(), 1);
+}
+
+#[tokio::test]
+async fn test_required_node_aborts_under_continue() {
+    let mut graph = graph("", "    required: true");
+    let (executor, _) = executor(&graph);
+
+    let error = executor.execute(&mut graph).await.unwrap_err();
+    assert_eq!(error.context.node_id.as_deref(), Some("lookup"));
+    assert!(graph.context.get("report_result").is_none());
+
+    // The YAML flag and the builder agree
+    let config = NodeConfig::custom("FailingNode", json!({})).with_required(true);
+    assert_eq!(config.required, graph.def.nodes["lookup"].required);
+}
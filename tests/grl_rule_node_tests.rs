@@ -0,0 +1,118 @@
+/// Integration tests for RuleNodes running GRL rule sets
+use rust_logic_graph::{CacheConfig, CacheManager, Executor, Graph, GraphDef, GraphIO, NodeConfig};
+use serde_json::json;
+use std::collections::HashMap;
+
+const DISCOUNT_YAML: &str = r#"
+nodes:
+  pricing:
+    node_type: RuleNode
+    grl: |
+      rule "BulkDiscount" salience 10 {
+        when
+          quantity >= 100
+        then
+          discount = 0.15;
+          tier = "bulk";
+      }
+      rule "NoDiscount" salience 10 {
+        when
+          quantity < 100
+        then
+          discount = 0.0;
+          tier = "standard";
+      }
+  approve:
+    node_type: RuleNode
+    condition: "tier == \"bulk\" && discount > 0.1"
+edges:
+  - from: pricing
+    to: approve
+"#;
+
+#[tokio::test]
+async fn test_inline_grl_writes_facts_to_context() {
+    let def = GraphIO::from_yaml(DISCOUNT_YAML).unwrap();
+    let mut graph = Graph::new(def);
+    graph.context.set("quantity", json!(250));
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(executor.metrics().nodes_failed, 0);
+    assert_eq!(graph.context.get("tier"), Some(&json!("bulk")));
+    assert_eq!(graph.context.get("discount"), Some(&json!(0.15)));
+    // Only new or changed facts make up the node's result
+    assert_eq!(
+        graph.context.get("pricing_result"),
+        Some(&json!({"discount": 0.15, "tier": "bulk"}))
+    );
+    assert_eq!(graph.context.get("quantity"), Some(&json!(250)));
+    assert_eq!(graph.context.get("approve_result"), Some(&json!(true)));
+}
+
+#[tokio::test]
+async fn test_cache_hit_restores_facts() {
+    let def = GraphIO::from_yaml(DISCOUNT_YAML).unwrap();
+    let mut executor = Executor::from_graph_def(&def).unwrap();
+    executor.set_cache(CacheManager::new(CacheConfig::default()).await.unwrap());
+
+    for run in 0..2 {
+        let mut graph = Graph::new(def.clone());
+        graph.context.set("quantity", json!(250));
+        let result = executor.execute(&mut graph).await.unwrap();
+
+        assert_eq!(executor.metrics().cache_hits, run * 2);
+        assert_eq!(graph.context.get("tier"), Some(&json!("bulk")));
+        assert_eq!(graph.context.get("discount"), Some(&json!(0.15)));
+        assert_eq!(graph.context.get("approve_result"), Some(&json!(true)));
+        assert_eq!(result.metrics.nodes_failed, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_grl_file_rule_node() {
+    let nodes = HashMap::from([(
+        "reorder".to_string(),
+        NodeConfig::grl_rule_file("examples/purchasing_rules.grl"),
+    )]);
+    let mut graph = Graph::new(GraphDef::new(nodes, vec![]));
+    for (key, value) in [
+        ("avg_daily_demand", json!(10.0)),
+        ("lead_time_days", json!(5.0)),
+        ("demand_lead_time", json!(50.0)),
+        ("available_qty", json!(20.0)),
+        ("trend", json!("stable")),
+        ("moq", json!(10.0)),
+    ] {
+        graph.context.set(key, value);
+    }
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(executor.metrics().nodes_failed, 0);
+    assert_eq!(graph.context.get("need_reorder"), Some(&json!(true)));
+    assert_eq!(graph.context.get("shortage"), Some(&json!(30.0)));
+}
+
+#[test]
+fn test_invalid_grl_rejected_when_building() {
+    let missing = HashMap::from([(
+        "rules".to_string(),
+        NodeConfig::grl_rule_file("does/not/exist.grl"),
+    )]);
+    let err = Executor::from_graph_def(&GraphDef::new(missing, vec![]))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("failed to read GRL file"));
+
+    let empty = HashMap::from([(
+        "rules".to_string(),
+        NodeConfig::grl_rule_node("// no rules here"),
+    )]);
+    let err = Executor::from_graph_def(&GraphDef::new(empty, vec![]))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("does not define any rules"));
+}
@@ -0,0 +1,123 @@
+/// Integration tests for building executors through the NodeRegistry
+use async_trait::async_trait;
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleResult,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+struct GreetingNode {
+    id: String,
+    greeting: String,
+}
+
+#[async_trait]
+impl Node for GreetingNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("GreetingNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let name = ctx
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("world")
+            .to_string();
+        let result = json!(format!("{}, {}!", self.greeting, name));
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
+
+fn registry_with_greeting() -> Arc<NodeRegistry> {
+    let mut registry = NodeRegistry::default();
+    registry.register("GreetingNode", |id: &str, config: &NodeConfig| {
+        let greeting = config
+            .settings
+            .as_ref()
+            .and_then(|s| s.get("greeting"))
+            .and_then(|g| g.as_str())
+            .unwrap_or("Hello")
+            .to_string();
+        Ok(Box::new(GreetingNode {
+            id: id.to_string(),
+            greeting,
+        }) as Box<dyn Node>)
+    });
+    Arc::new(registry)
+}
+
+#[tokio::test]
+async fn test_custom_node_type_from_yaml() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  greet:
+    node_type: GreetingNode
+    settings:
+      greeting: Hi
+  check:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: greet
+    to: check
+"#,
+    )
+    .unwrap();
+
+    let mut graph = Graph::new(def);
+    graph.context.set("name", json!("Alice"));
+
+    let executor =
+        Executor::from_graph_def_with_registry(&graph.def, registry_with_greeting()).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(
+        graph.context.get("greet_result"),
+        Some(&json!("Hi, Alice!"))
+    );
+    assert_eq!(executor.metrics().nodes_executed, 2);
+}
+
+#[test]
+fn test_unregistered_node_type_is_rejected() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  greet:
+    node_type: GreetingNode
+edges: []
+"#,
+    )
+    .unwrap();
+
+    let err = Executor::from_graph_def(&def).err().unwrap();
+    assert!(err.to_string().contains("GreetingNode"));
+}
+
+#[test]
+fn test_advanced_nodes_are_not_placeholders() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  risky:
+    node_type: DBNode
+    query: "SELECT 1"
+  guarded:
+    node_type: RetryNode
+    target_node: risky
+    max_retries: 2
+edges: []
+"#,
+    )
+    .unwrap();
+
+    let registry = NodeRegistry::default();
+    let node = registry.create("guarded", &def.nodes["guarded"]).unwrap();
+    assert_eq!(node.node_type(), NodeType::RetryNode);
+}
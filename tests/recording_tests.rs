@@ -0,0 +1,232 @@
+/// Integration tests for execution recordings and replaying external nodes
+use async_trait::async_trait;
+use rust_logic_graph::core::{ExecutionRecording, Replay};
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleError,
+    RuleResult,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const CUSTOMERS_YAML: &str = r#"
+nodes:
+  customers:
+    node_type: DBNode
+    query: "SELECT * FROM customers"
+  bulk:
+    node_type: RuleNode
+    condition: "true"
+  small:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: customers
+    to: bulk
+    rule: "customers_result.count > 5"
+  - from: customers
+    to: small
+    rule: "customers_result.count <= 5"
+"#;
+
+#[tokio::test]
+async fn test_recording_captures_nodes_and_edge_decisions() {
+    let mut graph = Graph::new(GraphIO::from_yaml(CUSTOMERS_YAML).unwrap());
+    graph.context.set("region", json!("EU"));
+    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.set_recording(true);
+    executor.execute(&mut graph).await.unwrap();
+
+    let recording = executor.last_recording().unwrap();
+    assert_eq!(recording.initial_context.get("region"), Some(&json!("EU")));
+    assert_eq!(recording.final_context, graph.context.data);
+    assert_eq!(recording.edge_taken("customers", "bulk"), Some(false));
+    assert_eq!(recording.edge_taken("customers", "small"), Some(true));
+    assert_eq!(recording.skipped, vec!["bulk".to_string()]);
+
+    let ids: Vec<&str> = recording.nodes.iter().map(|n| n.node_id.as_str()).collect();
+    assert_eq!(ids, ["customers", "small"]);
+    let customers = &recording.nodes[0];
+    assert_eq!(customers.node_type, NodeType::DBNode);
+    assert_eq!(customers.context.get("region"), Some(&json!("EU")));
+    assert_eq!(customers.output["customers_result"]["count"], json!(2));
+    assert!(customers.error.is_none());
+    assert!(!customers.replayed);
+}
+
+#[tokio::test]
+async fn test_replay_answers_db_nodes_from_recording() {
+    let mut graph = Graph::new(GraphIO::from_yaml(CUSTOMERS_YAML).unwrap());
+    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.set_recording(true);
+    executor.execute(&mut graph).await.unwrap();
+
+    // Pretend the database returned more rows when the run was recorded
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("run.json");
+    let mut recording = executor.last_recording().unwrap();
+    let record = &mut recording.nodes[0];
+    record.output.insert(
+        "customers_result".to_string(),
+        json!({"rows": [], "count": 10}),
+    );
+    record.result = Some(json!({"rows": [], "count": 10}));
+    recording.save(&path).unwrap();
+
+    let mut replayed = Graph::new(graph.def.clone());
+    executor.set_replay(ExecutionRecording::load(&path).unwrap());
+    executor.execute(&mut replayed).await.unwrap();
+
+    assert_eq!(
+        replayed.context.get("customers_result"),
+        Some(&json!({"rows": [], "count": 10}))
+    );
+    assert!(replayed.context.get("bulk_result").is_some());
+    assert!(replayed.context.get("small_result").is_none());
+    assert!(executor.last_recording().unwrap().nodes[0].replayed);
+}
+
+/// Returns how many times it has been called, failing the first
+/// `settings.failures` calls
+struct QuoteNode {
+    id: String,
+    node_type: NodeType,
+    failures: usize,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Node for QuoteNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        self.node_type.clone()
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if call <= self.failures {
+            return Err(RuleError::Eval(format!(
+                "quote service unavailable ({})",
+                call
+            )));
+        }
+        let result = json!({"price": 100 + call});
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
+
+/// Executor whose `QuoteNode` and `DBNode` nodes are quote services
+/// sharing the call counter `calls`
+fn quote_executor(yaml: &str, calls: Arc<AtomicUsize>) -> (Graph, Executor) {
+    let mut registry = NodeRegistry::default();
+    for type_name in ["QuoteNode", "DBNode"] {
+        let calls = calls.clone();
+        registry.register(type_name, move |id: &str, config: &NodeConfig| {
+            let failures = config
+                .settings
+                .as_ref()
+                .and_then(|s| s.get("failures"))
+                .and_then(|f| f.as_u64())
+                .unwrap_or(0) as usize;
+            Ok(Box::new(QuoteNode {
+                id: id.to_string(),
+                node_type: config.node_type.clone(),
+                failures,
+                calls: calls.clone(),
+            }) as Box<dyn Node>)
+        });
+    }
+
+    let graph = Graph::new(GraphIO::from_yaml(yaml).unwrap());
+    let executor = Executor::from_graph_def_with_registry(&graph.def, Arc::new(registry)).unwrap();
+    (graph, executor)
+}
+
+#[tokio::test]
+async fn test_replay_wrapped_custom_node_in_invocation_order() {
+    let yaml = r#"
+nodes:
+  quote:
+    node_type: QuoteNode
+    settings:
+      failures: 1
+  retry:
+    node_type: RetryNode
+    target_node: quote
+    max_retries: 3
+    initial_delay_ms: 1
+edges: []
+"#;
+    let (mut graph, mut executor) = quote_executor(yaml, Arc::new(AtomicUsize::new(0)));
+    executor.set_recording(true);
+    executor.execute(&mut graph).await.unwrap();
+
+    let recording = executor.last_recording().unwrap();
+    let quotes: Vec<_> = recording.node_records("quote").collect();
+    assert_eq!(quotes.len(), 2);
+    assert!(matches!(&quotes[0].error, Some(RuleError::Eval(_))));
+    assert_eq!(quotes[1].result, Some(json!({"price": 102})));
+    // Wrapped invocations are listed before their wrapper
+    assert_eq!(recording.nodes.last().unwrap().node_id, "retry");
+
+    let bytes = recording.to_msgpack().unwrap();
+    let recording = ExecutionRecording::from_msgpack(&bytes).unwrap();
+
+    // Replay against a fresh service: it is never called
+    let replay_calls = Arc::new(AtomicUsize::new(0));
+    let (mut replayed, mut replayer) = quote_executor(yaml, replay_calls.clone());
+    replayer.set_replay(Replay::new(recording).node("quote"));
+    replayer.execute(&mut replayed).await.unwrap();
+
+    assert_eq!(replay_calls.load(Ordering::SeqCst), 0);
+    assert_eq!(
+        replayed.context.get("retry_result"),
+        graph.context.get("retry_result")
+    );
+}
+
+#[tokio::test]
+async fn test_replay_answers_subgraphs_without_running_their_db_nodes() {
+    let yaml = r#"
+nodes:
+  pricing:
+    node_type: SubgraphNode
+    subgraph:
+      nodes:
+        quote:
+          node_type: DBNode
+          query: "SELECT price FROM quotes"
+      edges: []
+    output_mapping:
+      quote_result: price
+  approve:
+    node_type: RuleNode
+    condition: "price.price > 100"
+edges:
+  - from: pricing
+    to: approve
+"#;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (mut graph, mut executor) = quote_executor(yaml, calls.clone());
+    executor.set_recording(true);
+    executor.execute(&mut graph).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(graph.context.get("price"), Some(&json!({"price": 101})));
+
+    let recording = executor.last_recording().unwrap();
+    let replay_calls = Arc::new(AtomicUsize::new(0));
+    let (mut replayed, mut replayer) = quote_executor(yaml, replay_calls.clone());
+    replayer.set_replay(recording);
+    replayer.set_recording(true);
+    replayer.execute(&mut replayed).await.unwrap();
+
+    assert_eq!(replay_calls.load(Ordering::SeqCst), 0);
+    assert_eq!(replayed.context.get("price"), Some(&json!({"price": 101})));
+    assert_eq!(replayed.context.get("approve_result"), Some(&json!(true)));
+    let pricing = replayer.last_recording().unwrap();
+    assert!(pricing.node_records("pricing").next().unwrap().replayed);
+}
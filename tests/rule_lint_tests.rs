@@ -0,0 +1,20 @@
+/// Integration tests for the GRL rule linter
+use rust_logic_graph::rule::lint::{LintKind, RuleLinter};
+
+#[test]
+fn test_purchasing_rules_lint_clean() {
+    let grl = std::fs::read_to_string("examples/purchasing_rules.grl").unwrap();
+
+    // Exclusive rules at equal salience, such as the reorder/sufficient
+    // inventory pair, are not reported as conflicts
+    assert_eq!(RuleLinter::new().lint(&grl).unwrap(), vec![]);
+
+    let lints = RuleLinter::new()
+        .provided_facts(["avg_daily_demand", "lead_time_days", "demand_lead_time"])
+        .provided_facts(["available_qty", "trend", "moq", "is_active"])
+        .lint(&grl)
+        .unwrap();
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].kind, LintKind::UnknownFact);
+    assert_eq!(lints[0].fact.as_deref(), Some("unit_price"));
+}
@@ -0,0 +1,205 @@
+/// Integration tests for hot-reloadable, versioned rule sets
+use async_trait::async_trait;
+use rust_logic_graph::rule::RuleSetManager;
+use rust_logic_graph::{
+    CacheConfig, CacheManager, Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry,
+    NodeType, RuleResult,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn reorder_rules(threshold: u32) -> String {
+    format!(
+        r#"
+        rule "Reorder" {{
+            when
+                stock < {}
+            then
+                reorder = true;
+        }}
+        "#,
+        threshold
+    )
+}
+
+const REORDER_YAML: &str = r#"
+nodes:
+  check:
+    node_type: RuleNode
+    rule_set: purchasing
+edges: []
+"#;
+
+async fn run_reorder(executor: &Executor, stock: u32) -> Context {
+    let mut graph = Graph::new(GraphIO::from_yaml(REORDER_YAML).unwrap());
+    graph.context.set("stock", json!(stock));
+    executor.execute(&mut graph).await.unwrap();
+    graph.context
+}
+
+#[tokio::test]
+async fn test_new_versions_used_by_running_executor() {
+    let rule_sets = Arc::new(RuleSetManager::new());
+    rule_sets.load("purchasing", reorder_rules(10)).unwrap();
+
+    let def = GraphIO::from_yaml(REORDER_YAML).unwrap();
+    let mut executor = Executor::from_graph_def(&def).unwrap();
+    executor.set_rule_sets(rule_sets.clone());
+    let executor = Arc::new(executor);
+
+    let ctx = run_reorder(&executor, 20).await;
+    assert_eq!(ctx.get("reorder"), None);
+    assert_eq!(
+        executor.metrics().rule_set_versions,
+        HashMap::from([("purchasing".to_string(), 1)])
+    );
+
+    rule_sets.load("purchasing", reorder_rules(50)).unwrap();
+    let ctx = run_reorder(&executor, 20).await;
+    assert_eq!(ctx.get("reorder"), Some(&json!(true)));
+    assert_eq!(executor.metrics().rule_set_versions["purchasing"], 2);
+
+    rule_sets.rollback("purchasing").unwrap();
+    let ctx = run_reorder(&executor, 20).await;
+    assert_eq!(ctx.get("reorder"), None);
+    assert_eq!(executor.metrics().rule_set_versions["purchasing"], 1);
+}
+
+#[tokio::test]
+async fn test_cached_results_keyed_by_rule_set_version() {
+    let rule_sets = Arc::new(RuleSetManager::new());
+    rule_sets.load("purchasing", reorder_rules(10)).unwrap();
+
+    let def = GraphIO::from_yaml(REORDER_YAML).unwrap();
+    let mut executor = Executor::from_graph_def(&def).unwrap();
+    executor.set_rule_sets(rule_sets.clone());
+    executor.set_cache(CacheManager::new(CacheConfig::default()).await.unwrap());
+
+    assert_eq!(run_reorder(&executor, 20).await.get("reorder"), None);
+    assert_eq!(run_reorder(&executor, 20).await.get("reorder"), None);
+    assert_eq!(executor.metrics().cache_hits, 1);
+
+    // The new version runs instead of answering from the old one's entry
+    rule_sets.load("purchasing", reorder_rules(50)).unwrap();
+    let ctx = run_reorder(&executor, 20).await;
+    assert_eq!(ctx.get("reorder"), Some(&json!(true)));
+    assert_eq!(executor.metrics().cache_hits, 0);
+    assert_eq!(executor.metrics().rule_set_versions["purchasing"], 2);
+}
+
+/// Loads a new version of the `purchasing` rule set when run
+struct ReloadNode {
+    id: String,
+    rule_sets: Arc<RuleSetManager>,
+}
+
+#[async_trait]
+impl Node for ReloadNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("ReloadNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let version = self
+            .rule_sets
+            .load("purchasing", reorder_rules(1000))
+            .unwrap();
+        ctx.set(format!("{}_result", self.id), json!(version));
+        Ok(json!(version))
+    }
+}
+
+#[tokio::test]
+async fn test_version_pinned_for_whole_run() {
+    let rule_sets = Arc::new(RuleSetManager::new());
+    rule_sets.load("purchasing", reorder_rules(10)).unwrap();
+
+    let mut registry = NodeRegistry::default();
+    let manager = rule_sets.clone();
+    registry.register("ReloadNode", move |id: &str, _: &NodeConfig| {
+        Ok(Box::new(ReloadNode {
+            id: id.to_string(),
+            rule_sets: manager.clone(),
+        }) as Box<dyn Node>)
+    });
+
+    let yaml = r#"
+nodes:
+  first:
+    node_type: RuleNode
+    rule_set: purchasing
+  reload:
+    node_type: ReloadNode
+  second:
+    node_type: RuleNode
+    rule_set: purchasing
+edges:
+  - { from: first, to: reload }
+  - { from: reload, to: second }
+"#;
+    let mut graph = Graph::new(GraphIO::from_yaml(yaml).unwrap());
+    graph.context.set("stock", json!(20));
+
+    let mut executor =
+        Executor::from_graph_def_with_registry(&graph.def, Arc::new(registry)).unwrap();
+    executor.set_rule_sets(rule_sets.clone());
+    executor.execute(&mut graph).await.unwrap();
+
+    // Version 2 was loaded mid-run, but the run kept using version 1
+    assert_eq!(graph.context.get("reload_result"), Some(&json!(2)));
+    assert_eq!(graph.context.get("reorder"), None);
+    assert_eq!(executor.metrics().rule_set_versions["purchasing"], 1);
+    assert_eq!(rule_sets.active("purchasing").unwrap().version(), 2);
+}
+
+#[tokio::test]
+async fn test_rule_set_node_without_manager_fails() {
+    let def = GraphIO::from_yaml(REORDER_YAML).unwrap();
+    let executor = Executor::from_graph_def(&def).unwrap();
+
+    run_reorder(&executor, 20).await;
+    assert_eq!(executor.metrics().nodes_failed, 1);
+}
+
+#[tokio::test]
+async fn test_watch_reloads_changed_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("purchasing.grl");
+    std::fs::write(&path, reorder_rules(10)).unwrap();
+
+    let rule_sets = Arc::new(RuleSetManager::new());
+    rule_sets.load_file("purchasing", &path).unwrap();
+    let watcher = rule_sets.watch("purchasing", &path, Duration::from_millis(10));
+
+    let wait_for_version = |version: u64| {
+        let rule_sets = rule_sets.clone();
+        async move {
+            for _ in 0..200 {
+                if rule_sets.active("purchasing").unwrap().version() == version {
+                    return true;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            false
+        }
+    };
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    std::fs::write(&path, reorder_rules(50)).unwrap();
+    assert!(wait_for_version(2).await);
+
+    // A broken edit is ignored and version 2 stays active
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    std::fs::write(&path, "rule {").unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(rule_sets.active("purchasing").unwrap().version(), 2);
+    assert_eq!(rule_sets.versions("purchasing").len(), 2);
+
+    watcher.abort();
+}
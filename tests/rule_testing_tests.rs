@@ -0,0 +1,27 @@
+/// Integration tests for GRL rule test fixtures
+use rust_logic_graph::rule::testing::RuleTestSuite;
+
+#[test]
+fn test_purchasing_rules_fixture_passes() {
+    RuleTestSuite::from_file("examples/purchasing_rules.test.yaml")
+        .unwrap()
+        .run()
+        .unwrap()
+        .assert_passed();
+}
+
+#[test]
+fn test_missing_rules_file_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = dir.path().join("rules.test.json");
+    std::fs::write(
+        &fixture,
+        r#"{"rules": "missing.grl", "tests": [{"name": "any"}]}"#,
+    )
+    .unwrap();
+
+    let suite = RuleTestSuite::from_file(&fixture).unwrap();
+    assert_eq!(suite.rules, Some(dir.path().join("missing.grl")));
+    let err = suite.run().unwrap_err();
+    assert!(err.to_string().contains("Failed to read GRL file"));
+}
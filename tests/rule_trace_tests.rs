@@ -0,0 +1,83 @@
+/// Integration tests for rule execution traces
+use rust_logic_graph::{Executor, Graph, GraphIO};
+use serde_json::json;
+
+const PURCHASE_YAML: &str = r#"
+nodes:
+  purchasing:
+    node_type: RuleNode
+    grl: |
+      rule "Reorder" salience 20 no-loop {
+        when
+          stock < 10
+        then
+          create_po = true;
+      }
+      rule "Expedite" salience 10 no-loop {
+        when
+          create_po == true && priority == "high"
+        then
+          expedite = true;
+      }
+edges: []
+"#;
+
+async fn run_purchasing(tracing: bool, stock: i64) -> (Graph, Executor) {
+    let mut graph = Graph::new(GraphIO::from_yaml(PURCHASE_YAML).unwrap());
+    graph.context.set("stock", json!(stock));
+    graph.context.set("priority", json!("low"));
+
+    let mut executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.set_rule_tracing(tracing);
+    executor.execute(&mut graph).await.unwrap();
+    (graph, executor)
+}
+
+#[tokio::test]
+async fn test_trace_explains_fired_and_unfired_rules() {
+    let (graph, executor) = run_purchasing(true, 4).await;
+    assert_eq!(graph.context.get("create_po"), Some(&json!(true)));
+
+    let metrics = executor.metrics();
+    let traces: Vec<_> = metrics.rule_traces().collect();
+    assert_eq!(traces.len(), 1);
+    let (node, trace) = traces[0];
+    assert_eq!(node, "purchasing");
+
+    assert!(trace.fired("Reorder"));
+    let reorder = &trace.fired[0];
+    assert_eq!(reorder.conditions[0].field, "stock");
+    assert_eq!(reorder.conditions[0].actual, json!(4));
+    assert_eq!(reorder.changes[0].fact, "create_po");
+    assert_eq!(reorder.changes[0].before, None);
+    assert_eq!(reorder.changes[0].after, Some(json!(true)));
+
+    // Expedite did not fire, and the trace shows why
+    assert!(!trace.fired("Expedite"));
+    let expedite = &trace.not_fired[0];
+    assert_eq!(expedite.rule, "Expedite");
+    let priority = expedite
+        .conditions
+        .iter()
+        .find(|c| c.field == "priority")
+        .unwrap();
+    assert_eq!(priority.actual, json!("low"));
+    assert!(trace.to_string().contains("not fired \"Expedite\""));
+
+    // The same trace is attached to the node's result
+    let result = graph.context.get("purchasing_result").unwrap();
+    assert_eq!(
+        serde_json::from_value::<rust_logic_graph::rule::RuleTrace>(result["_trace"].clone())
+            .unwrap(),
+        *trace
+    );
+}
+
+#[tokio::test]
+async fn test_no_trace_unless_enabled() {
+    let (graph, executor) = run_purchasing(false, 4).await;
+
+    assert_eq!(executor.metrics().rule_traces().count(), 0);
+    let result = graph.context.get("purchasing_result").unwrap();
+    assert!(result.get("_trace").is_none());
+}
@@ -0,0 +1,172 @@
+/// Integration tests for nested SubgraphNode execution
+use parking_lot::Mutex;
+use rust_logic_graph::{
+    Context, ExecutionListener, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, RuleNode,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+const PARENT_YAML: &str = r#"
+nodes:
+  prepare:
+    node_type: RuleNode
+    condition: "true"
+  pricing:
+    node_type: SubgraphNode
+    subgraph:
+      nodes:
+        copy_price:
+          node_type: RuleNode
+          condition: base_price
+        done:
+          node_type: RuleNode
+          condition: "true"
+      edges:
+        - from: copy_price
+          to: done
+    input_mapping:
+      product_price: base_price
+    output_mapping:
+      copy_price_result: final_price
+edges:
+  - from: prepare
+    to: pricing
+"#;
+
+#[tokio::test]
+async fn test_subgraph_runs_child_nodes() {
+    let def = GraphIO::from_yaml(PARENT_YAML).unwrap();
+    let mut graph = Graph::new(def);
+    graph.context.set("product_price", json!(42));
+
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(graph.context.get("final_price"), Some(&json!(42)));
+    assert_eq!(
+        graph.context.get("pricing_result").unwrap()["nodes_executed"],
+        json!(2)
+    );
+
+    // Child metrics are folded into the parent's with qualified node ids
+    let metrics = executor.metrics();
+    assert_eq!(metrics.nodes_executed, 4);
+    assert!(metrics
+        .node_stats
+        .iter()
+        .any(|s| s.node_id == "pricing/copy_price" && s.success));
+}
+
+#[tokio::test]
+async fn test_subgraph_child_failure_fails_parent_node() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  pricing:
+    node_type: SubgraphNode
+    subgraph:
+      nodes:
+        explode:
+          node_type: FailingNode
+      edges: []
+edges: []
+"#,
+    )
+    .unwrap();
+    let mut graph = Graph::new(def);
+
+    let (executor, _) = common::executor(&graph);
+    executor.execute(&mut graph).await.unwrap();
+
+    let metrics = executor.metrics();
+    assert_eq!(metrics.nodes_failed, 2);
+    let pricing = metrics
+        .node_stats
+        .iter()
+        .find(|s| s.node_id == "pricing")
+        .unwrap();
+    assert!(!pricing.success);
+    assert!(metrics
+        .node_stats
+        .iter()
+        .any(|s| s.node_id == "pricing/explode" && !s.success));
+}
+
+#[tokio::test]
+async fn test_subgraph_node_runs_standalone() {
+    let def = GraphIO::from_yaml(PARENT_YAML).unwrap();
+    let registry = NodeRegistry::default();
+    let node = registry.create("pricing", &def.nodes["pricing"]).unwrap();
+
+    let mut ctx = Context::new();
+    ctx.set("product_price", json!(7));
+    node.run(&mut ctx).await.unwrap();
+
+    assert_eq!(ctx.get("final_price"), Some(&json!(7)));
+}
+
+#[derive(Default)]
+struct StartedNodes(Mutex<Vec<String>>);
+
+impl ExecutionListener for StartedNodes {
+    fn on_node_started(&self, node_id: &str) {
+        self.0.lock().push(node_id.to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_subgraph_follows_current_executor_configuration() {
+    // Count how often the child's nodes are created
+    let built = Arc::new(AtomicUsize::new(0));
+    let mut registry = NodeRegistry::default();
+    let counter = built.clone();
+    registry.register("CountedNode", move |id: &str, _config: &NodeConfig| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(RuleNode::new(id, "true")) as Box<dyn Node>)
+    });
+
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  pricing:
+    node_type: SubgraphNode
+    subgraph:
+      nodes:
+        quote:
+          node_type: CountedNode
+      edges: []
+edges: []
+"#,
+    )
+    .unwrap();
+    let mut executor = Executor::from_graph_def_with_registry(&def, Arc::new(registry)).unwrap();
+    let first = Arc::new(StartedNodes::default());
+    executor.add_listener(first.clone());
+
+    let plan = executor.compile(&def).unwrap();
+    executor
+        .execute_plan(&plan, &mut Context::new())
+        .await
+        .unwrap();
+
+    // A listener added after the first run also sees the child's nodes
+    let second = Arc::new(StartedNodes::default());
+    executor.add_listener(second.clone());
+    for _ in 0..2 {
+        executor
+            .execute_plan(&plan, &mut Context::new())
+            .await
+            .unwrap();
+    }
+
+    // The child executor, and so its nodes, is built again on every run
+    assert_eq!(built.load(Ordering::SeqCst), 3);
+    let first = first.0.lock();
+    assert_eq!(first.iter().filter(|id| *id == "quote").count(), 3);
+    let second = second.0.lock();
+    assert_eq!(second.iter().filter(|id| *id == "quote").count(), 2);
+    assert_eq!(second.iter().filter(|id| *id == "pricing").count(), 2);
+}
@@ -0,0 +1,169 @@
+/// Integration tests for per-node timeouts and the execution deadline
+use rust_logic_graph::{Context, Graph, GraphIO};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+mod common;
+use common::executor;
+
+fn fallback(node_id: &str, ctx: &mut Context) -> Option<Value> {
+    let value = json!({"fallback": true});
+    ctx.set(format!("{}_result", node_id), value.clone());
+    Some(value)
+}
+
+#[tokio::test]
+async fn test_node_timeout_cancels_node_and_feeds_fallback() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  query:
+    node_type: SlowNode
+    timeout_ms: 20
+    settings:
+      sleep_ms: 5000
+  report:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: query
+    to: report
+"#,
+        )
+        .unwrap(),
+    );
+    let (mut executor, _) = executor(&graph);
+    executor.set_fallback_handler(fallback);
+
+    let start = Instant::now();
+    executor.execute(&mut graph).await.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    // The cancelled node's own writes are undone; the fallback's are kept
+    assert!(graph.context.get("query_started").is_none());
+    assert_eq!(
+        graph.context.get("query_result"),
+        Some(&json!({"fallback": true}))
+    );
+    assert!(graph.context.get("report_result").is_some());
+
+    let metrics = executor.metrics();
+    assert_eq!(metrics.nodes_failed, 1);
+    assert_eq!(metrics.nodes_timed_out, 1);
+    let query = metrics
+        .node_stats
+        .iter()
+        .find(|s| s.node_id == "query")
+        .unwrap();
+    assert!(query.timed_out && !query.success);
+}
+
+#[tokio::test]
+async fn test_retry_recovers_from_timed_out_attempt() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  query:
+    node_type: SlowNode
+    timeout_ms: 20
+    settings:
+      sleep_ms: 5000
+  retry:
+    node_type: RetryNode
+    target_node: query
+    max_retries: 2
+    initial_delay_ms: 1
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, _) = executor(&graph);
+    executor.execute(&mut graph).await.unwrap();
+
+    let result = graph.context.get("retry_result").unwrap();
+    assert_eq!(result["status"], json!("success"));
+    assert_eq!(result["attempts"], json!(2));
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_applies_target_timeout() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  query:
+    node_type: SlowNode
+    timeout_ms: 20
+    settings:
+      sleep_ms: 5000
+  breaker:
+    node_type: CircuitBreakerNode
+    target_node: query
+    failure_threshold: 1
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let (executor, _) = executor(&graph);
+
+    let start = Instant::now();
+    executor.execute(&mut graph).await.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    assert!(graph.context.get("query_started").is_none());
+    assert_eq!(executor.metrics().nodes_failed, 1);
+}
+
+#[tokio::test]
+async fn test_execution_deadline_fails_run_with_timeout_error() {
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  query:
+    node_type: SlowNode
+    settings:
+      sleep_ms: 5000
+  report:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: query
+    to: report
+"#,
+        )
+        .unwrap(),
+    );
+    let (mut executor, _) = executor(&graph);
+    executor.set_execution_timeout(Duration::from_millis(30));
+
+    let start = Instant::now();
+    let error = executor.execute(&mut graph).await.unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    assert_eq!(error.code, "E005");
+    assert!(error.is_retryable());
+    assert!(graph.context.get("report_result").is_none());
+    assert_eq!(error.metrics().unwrap().nodes_timed_out, 1);
+}
+
+#[test]
+fn test_zero_timeout_is_rejected() {
+    let def = GraphIO::from_yaml(
+        r#"
+nodes:
+  query:
+    node_type: DBNode
+    query: "SELECT 1"
+    timeout_ms: 0
+edges: []
+"#,
+    )
+    .unwrap();
+    let error = def.validate().unwrap_err();
+    assert!(error.to_string().contains("timeout_ms of 0"));
+}
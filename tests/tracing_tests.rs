@@ -0,0 +1,233 @@
+/// Integration tests for the tracing spans of graph executions
+use parking_lot::Mutex;
+use rust_logic_graph::distributed::DistributedContext;
+use rust_logic_graph::{CacheConfig, CacheManager, Executor, Graph, GraphIO, TraceContext};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, Registry};
+
+const GRAPH_YAML: &str = r#"
+name: scoring
+nodes:
+  score:
+    node_type: RuleNode
+    condition: "true"
+  approve:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: score
+    to: approve
+"#;
+
+/// A span as the collector saw it
+#[derive(Debug, Clone)]
+struct CollectedSpan {
+    id: u64,
+    name: String,
+    parent: Option<u64>,
+    fields: HashMap<String, String>,
+}
+
+impl CollectedSpan {
+    fn field(&self, name: &str) -> &str {
+        self.fields.get(name).map(String::as_str).unwrap_or("")
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// In-process collector keeping every graph and node span
+#[derive(Clone, Default)]
+struct Collector {
+    spans: Arc<Mutex<Vec<CollectedSpan>>>,
+}
+
+impl Collector {
+    fn spans(&self, name: &str) -> Vec<CollectedSpan> {
+        self.spans
+            .lock()
+            .iter()
+            .filter(|span| span.name == name)
+            .cloned()
+            .collect()
+    }
+
+    fn node(&self, node_id: &str) -> CollectedSpan {
+        self.spans("node_execution")
+            .into_iter()
+            .find(|span| span.field("node.id") == node_id)
+            .unwrap()
+    }
+}
+
+impl<S> Layer<S> for Collector
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        if name != "graph_execution" && name != "node_execution" {
+            return;
+        }
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        let parent = ctx
+            .span(id)
+            .and_then(|span| span.parent())
+            .map(|parent| parent.id().into_u64());
+        self.spans.lock().push(CollectedSpan {
+            id: id.into_u64(),
+            name: name.to_string(),
+            parent,
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.spans.lock();
+        if let Some(span) = spans.iter_mut().find(|span| span.id == id.into_u64()) {
+            values.record(&mut FieldVisitor(&mut span.fields));
+        }
+    }
+}
+
+fn graph() -> (Graph, Executor) {
+    let mut graph = Graph::new(GraphIO::from_yaml(GRAPH_YAML).unwrap());
+    graph.context.set("amount", json!(50));
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    (graph, executor)
+}
+
+#[tokio::test]
+async fn test_nodes_run_in_child_spans_of_the_graph() {
+    let collector = Collector::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+    let (mut graph, executor) = graph();
+
+    let result = executor.execute(&mut graph).await.unwrap();
+
+    let graphs = collector.spans("graph_execution");
+    assert_eq!(graphs.len(), 1);
+    let root = &graphs[0];
+    assert_eq!(root.field("graph.name"), "scoring");
+    assert_eq!(root.field("graph.status"), "completed");
+    assert_eq!(root.field("otel.status_code"), "OK");
+    assert_eq!(root.field("trace_id"), result.trace_id);
+    assert!(!root.fields.contains_key("parent_span_id"));
+
+    let score = collector.node("score");
+    assert_eq!(score.parent, Some(root.id));
+    assert_eq!(score.field("node.type"), "RuleNode");
+    assert_eq!(score.field("node.outcome"), "succeeded");
+    assert_eq!(score.field("node.cache_hit"), "false");
+    assert_eq!(score.field("trace_id"), result.trace_id);
+    assert_eq!(score.field("parent_span_id"), root.field("span_id"));
+    assert_eq!(collector.node("approve").parent, Some(root.id));
+}
+
+#[tokio::test]
+async fn test_cache_hits_are_recorded_on_node_spans() {
+    let collector = Collector::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+    let (mut graph, mut executor) = graph();
+    executor.set_cache(CacheManager::new(CacheConfig::default()).await.unwrap());
+
+    executor.execute(&mut graph).await.unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    let scores: Vec<_> = collector
+        .spans("node_execution")
+        .into_iter()
+        .filter(|span| span.field("node.id") == "score")
+        .collect();
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores[1].field("node.cache_hit"), "true");
+    assert_eq!(scores[1].field("node.outcome"), "cached");
+    // Each run starts its own trace
+    assert_ne!(scores[0].field("trace_id"), scores[1].field("trace_id"));
+}
+
+#[tokio::test]
+async fn test_trace_continues_across_distributed_context() {
+    let collector = Collector::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+
+    // The calling service sends its trace context along with the request
+    let caller = TraceContext::new_root();
+    let mut request = DistributedContext::new("session-1");
+    request.set_trace_context(&caller);
+    let received = DistributedContext::deserialize(&request.serialize().unwrap()).unwrap();
+
+    let (mut graph, executor) = graph();
+    let parent = received.trace_context().unwrap();
+    let result = parent.scope(executor.execute(&mut graph)).await.unwrap();
+
+    assert_eq!(result.trace_id, caller.trace_id());
+    let root = &collector.spans("graph_execution")[0];
+    assert_eq!(root.field("parent_span_id"), caller.span_id());
+    assert_eq!(
+        collector.node("approve").field("trace_id"),
+        caller.trace_id()
+    );
+}
+
+#[tokio::test]
+async fn test_subgraphs_join_the_parent_trace() {
+    let collector = Collector::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+name: outer
+nodes:
+  nested:
+    node_type: SubgraphNode
+    subgraph:
+      name: inner
+      nodes:
+        score:
+          node_type: RuleNode
+          condition: "true"
+      edges: []
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    let executor = Executor::from_graph_def(&graph.def).unwrap();
+    let result = executor.execute(&mut graph).await.unwrap();
+
+    let graphs = collector.spans("graph_execution");
+    assert_eq!(graphs.len(), 2);
+    assert!(graphs
+        .iter()
+        .all(|span| span.field("trace_id") == result.trace_id));
+    let nested = collector.node("nested");
+    let inner = graphs
+        .iter()
+        .find(|span| span.field("graph.name") == "inner")
+        .unwrap();
+    assert_eq!(inner.parent, Some(nested.id));
+    assert_eq!(inner.field("parent_span_id"), nested.field("span_id"));
+    assert_eq!(collector.node("score").parent, Some(inner.id));
+}
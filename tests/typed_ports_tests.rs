@@ -0,0 +1,179 @@
+/// Integration tests for node input ports and declared outputs
+use async_trait::async_trait;
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleResult,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Stores the object given in `settings.result` as its result
+struct ConstNode {
+    id: String,
+    result: Value,
+}
+
+#[async_trait]
+impl Node for ConstNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("ConstNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        ctx.set(format!("{}_result", self.id), self.result.clone());
+        Ok(self.result.clone())
+    }
+}
+
+/// Copies the context keys listed in `settings.keys` into its result
+struct EchoNode {
+    id: String,
+    keys: Vec<String>,
+}
+
+#[async_trait]
+impl Node for EchoNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("EchoNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let result: serde_json::Map<String, Value> = self
+            .keys
+            .iter()
+            .map(|key| (key.clone(), ctx.get(key).cloned().unwrap_or(Value::Null)))
+            .collect();
+        let result = Value::Object(result);
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
+
+fn registry() -> Arc<NodeRegistry> {
+    let mut registry = NodeRegistry::default();
+    registry.register("ConstNode", |id: &str, config: &NodeConfig| {
+        let result = config
+            .settings
+            .as_ref()
+            .and_then(|s| s.get("result"))
+            .cloned()
+            .unwrap_or(Value::Null);
+        Ok(Box::new(ConstNode {
+            id: id.to_string(),
+            result,
+        }) as Box<dyn Node>)
+    });
+    registry.register("EchoNode", |id: &str, config: &NodeConfig| {
+        let keys = config
+            .settings
+            .as_ref()
+            .and_then(|s| s.get("keys"))
+            .and_then(|k| serde_json::from_value(k.clone()).ok())
+            .unwrap_or_default();
+        Ok(Box::new(EchoNode {
+            id: id.to_string(),
+            keys,
+        }) as Box<dyn Node>)
+    });
+    Arc::new(registry)
+}
+
+const QUOTE_YAML: &str = r#"
+nodes:
+  pricing:
+    node_type: ConstNode
+    settings:
+      result: { total: 42, currency: USD }
+    outputs: [total, currency]
+  invoice:
+    node_type: EchoNode
+    settings:
+      keys: [amount, product, quote]
+    inputs:
+      amount: { node: pricing, output: total }
+      product: { context: product_id }
+      quote: { node: pricing }
+edges:
+  - from: pricing
+    to: invoice
+"#;
+
+#[tokio::test]
+async fn test_inputs_resolved_from_outputs_and_context() {
+    let def = GraphIO::from_yaml(QUOTE_YAML).unwrap();
+    let mut graph = Graph::new(def);
+    graph.context.set("product_id", json!("SKU-1"));
+
+    let executor = Executor::from_graph_def_with_registry(&graph.def, registry()).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(executor.metrics().nodes_failed, 0);
+    assert_eq!(
+        graph.context.get("invoice_result"),
+        Some(&json!({
+            "amount": 42,
+            "product": "SKU-1",
+            "quote": { "total": 42, "currency": "USD" }
+        }))
+    );
+    // Ports are only bound while the node runs
+    assert!(!graph.context.contains_key("amount"));
+    assert!(!graph.context.contains_key("quote"));
+}
+
+#[test]
+fn test_unknown_output_rejected_at_compile_time() {
+    let yaml = QUOTE_YAML.replace("output: total", "output: totl");
+    let def = GraphIO::from_yaml(&yaml).unwrap();
+    let executor = Executor::from_graph_def_with_registry(&def, registry()).unwrap();
+
+    let err = executor.compile(&def).unwrap_err();
+    assert!(err.to_string().contains("'totl'"));
+}
+
+#[test]
+fn test_input_from_non_upstream_node_rejected() {
+    let yaml = QUOTE_YAML.replace("  - from: pricing\n    to: invoice\n", "");
+    let yaml = yaml.replace("edges:\n", "edges: []\n");
+    let def = GraphIO::from_yaml(&yaml).unwrap();
+
+    let err = def.validate().unwrap_err();
+    assert!(err.to_string().contains("not upstream"));
+}
+
+#[tokio::test]
+async fn test_missing_declared_output_fails_node() {
+    let yaml = QUOTE_YAML.replace("outputs: [total, currency]", "outputs: [total, tax]");
+    let yaml = yaml.replace("output: total", "output: tax");
+    let def = GraphIO::from_yaml(&yaml).unwrap();
+    let mut graph = Graph::new(def);
+    graph.context.set("product_id", json!("SKU-1"));
+
+    let executor = Executor::from_graph_def_with_registry(&graph.def, registry()).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    // pricing fails its output check, so invoice cannot resolve its input
+    let metrics = executor.metrics();
+    assert_eq!(metrics.nodes_failed, 2);
+    assert!(metrics.node_stats.iter().all(|s| !s.success));
+}
+
+#[tokio::test]
+async fn test_missing_context_input_fails_node() {
+    let def = GraphIO::from_yaml(QUOTE_YAML).unwrap();
+    let mut graph = Graph::new(def);
+
+    let executor = Executor::from_graph_def_with_registry(&graph.def, registry()).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    let metrics = executor.metrics();
+    assert_eq!(metrics.nodes_executed, 1);
+    assert_eq!(metrics.nodes_failed, 1);
+}
@@ -0,0 +1,404 @@
+/// Integration tests for wrapper nodes (TryCatch, Retry, CircuitBreaker, Loop)
+/// running their target nodes through the executor
+use async_trait::async_trait;
+use rust_logic_graph::distributed::{ContextStore, InMemoryStore};
+use rust_logic_graph::{
+    Context, Executor, Graph, GraphIO, Node, NodeConfig, NodeRegistry, NodeType, RuleError,
+    RuleResult,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Fails its first `settings.failures` calls, then succeeds. Every call is
+/// counted in the shared `calls` counter. A failing call leaves a
+/// `{id}_partial` entry behind; with `settings.invalid` it fails with a
+/// non-retryable error.
+struct FlakyNode {
+    id: String,
+    failures: usize,
+    invalid: bool,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Node for FlakyNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("FlakyNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if call <= self.failures {
+            ctx.set(format!("{}_partial", self.id), json!(call));
+            let message = format!("call {} failed", call);
+            return Err(match self.invalid {
+                true => RuleError::InvalidExpression(message),
+                false => RuleError::Eval(message),
+            });
+        }
+        let result = json!(call);
+        ctx.set(format!("{}_result", self.id), result.clone());
+        Ok(result)
+    }
+}
+
+/// Records in `{id}_saw` whether `settings.key` was in the context when it
+/// ran
+struct ProbeNode {
+    id: String,
+    key: String,
+}
+
+#[async_trait]
+impl Node for ProbeNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Custom("ProbeNode".to_string())
+    }
+
+    async fn run(&self, ctx: &mut Context) -> RuleResult {
+        let saw = json!(ctx.get(&self.key).is_some());
+        ctx.set(format!("{}_saw", self.id), saw.clone());
+        Ok(saw)
+    }
+}
+
+fn registry_with_flaky(calls: Arc<AtomicUsize>) -> Arc<NodeRegistry> {
+    let mut registry = NodeRegistry::default();
+    registry.register("FlakyNode", move |id: &str, config: &NodeConfig| {
+        let setting = |name: &str| config.settings.as_ref().and_then(|s| s.get(name));
+        let failures = setting("failures").and_then(|f| f.as_u64()).unwrap_or(0) as usize;
+        let invalid = setting("invalid")
+            .and_then(|i| i.as_bool())
+            .unwrap_or(false);
+        Ok(Box::new(FlakyNode {
+            id: id.to_string(),
+            failures,
+            invalid,
+            calls: calls.clone(),
+        }) as Box<dyn Node>)
+    });
+    registry.register("ProbeNode", |id: &str, config: &NodeConfig| {
+        let key = config
+            .settings
+            .as_ref()
+            .and_then(|s| s.get("key"))
+            .and_then(|k| k.as_str())
+            .unwrap_or_default();
+        Ok(Box::new(ProbeNode {
+            id: id.to_string(),
+            key: key.to_string(),
+        }) as Box<dyn Node>)
+    });
+    Arc::new(registry)
+}
+
+async fn run_yaml(yaml: &str, calls: Arc<AtomicUsize>) -> (Graph, Executor) {
+    let mut graph = Graph::new(GraphIO::from_yaml(yaml).unwrap());
+    let executor =
+        Executor::from_graph_def_with_registry(&graph.def, registry_with_flaky(calls)).unwrap();
+    executor.execute(&mut graph).await.unwrap();
+    (graph, executor)
+}
+
+#[tokio::test]
+async fn test_retry_reruns_target_until_success() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (graph, executor) = run_yaml(
+        r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 2
+  retry:
+    node_type: RetryNode
+    target_node: flaky
+    max_retries: 3
+    initial_delay_ms: 1
+edges: []
+"#,
+        calls.clone(),
+    )
+    .await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    let result = graph.context.get("retry_result").unwrap();
+    assert_eq!(result["status"], json!("success"));
+    assert_eq!(result["attempts"], json!(3));
+    assert_eq!(result["result"], json!(3));
+    // Writes of the failed attempts are rolled back
+    assert_eq!(graph.context.get("flaky_partial"), None);
+
+    // The wrapped node is not scheduled on its own
+    assert_eq!(executor.metrics().nodes_executed, 1);
+    assert_eq!(executor.metrics().nodes_failed, 0);
+}
+
+#[tokio::test]
+async fn test_retry_fails_after_exhausting_retries() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (_graph, executor) = run_yaml(
+        r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 10
+  retry:
+    node_type: RetryNode
+    target_node: flaky
+    max_retries: 2
+    initial_delay_ms: 1
+edges: []
+"#,
+        calls.clone(),
+    )
+    .await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(executor.metrics().nodes_failed, 1);
+}
+
+#[tokio::test]
+async fn test_retry_does_not_retry_non_retryable_errors() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (graph, executor) = run_yaml(
+        r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 10
+      invalid: true
+  retry:
+    node_type: RetryNode
+    target_node: flaky
+    max_retries: 3
+    initial_delay_ms: 1
+edges: []
+"#,
+        calls.clone(),
+    )
+    .await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(graph.context.get("flaky_partial"), None);
+    assert_eq!(executor.metrics().nodes_failed, 1);
+}
+
+#[tokio::test]
+async fn test_try_catch_runs_catch_and_finally() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (graph, executor) = run_yaml(
+        r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 1
+  recover:
+    node_type: RuleNode
+    condition: "true"
+  cleanup:
+    node_type: RuleNode
+    condition: "true"
+  guard:
+    node_type: TryCatchNode
+    try_node: flaky
+    catch_node: recover
+    finally_node: cleanup
+edges: []
+"#,
+        calls,
+    )
+    .await;
+
+    let result = graph.context.get("guard_result").unwrap();
+    assert_eq!(result["status"], json!("error_handled"));
+    assert!(graph
+        .context
+        .get("_error")
+        .and_then(|e| e.as_str())
+        .unwrap()
+        .contains("call 1 failed"));
+    assert_eq!(graph.context.get("recover_result"), Some(&json!(true)));
+    assert_eq!(graph.context.get("cleanup_result"), Some(&json!(true)));
+    assert_eq!(executor.metrics().nodes_failed, 0);
+}
+
+#[tokio::test]
+async fn test_try_catch_discards_failed_try_writes_before_catch() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (graph, _executor) = run_yaml(
+        r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 1
+  recover:
+    node_type: ProbeNode
+    settings:
+      key: flaky_partial
+  guard:
+    node_type: TryCatchNode
+    try_node: flaky
+    catch_node: recover
+edges: []
+"#,
+        calls,
+    )
+    .await;
+
+    assert_eq!(graph.context.get("recover_saw"), Some(&json!(false)));
+    assert_eq!(graph.context.get("flaky_partial"), None);
+    assert!(graph.context.get("_error").is_some());
+}
+
+#[tokio::test]
+async fn test_try_without_catch_propagates_error_after_finally() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (graph, executor) = run_yaml(
+        r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 1
+  cleanup:
+    node_type: RuleNode
+    condition: "true"
+  guard:
+    node_type: TryCatchNode
+    try_node: flaky
+    finally_node: cleanup
+edges: []
+"#,
+        calls,
+    )
+    .await;
+
+    assert_eq!(graph.context.get("cleanup_result"), Some(&json!(true)));
+    assert_eq!(executor.metrics().nodes_failed, 1);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_opens_and_state_persists() {
+    let yaml = r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+    settings:
+      failures: 100
+  breaker:
+    node_type: CircuitBreakerNode
+    target_node: flaky
+    failure_threshold: 2
+    recovery_timeout_ms: 60000
+edges: []
+"#;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let store: Arc<dyn ContextStore> = Arc::new(InMemoryStore::new());
+    let def = GraphIO::from_yaml(yaml).unwrap();
+
+    let mut executor =
+        Executor::from_graph_def_with_registry(&def, registry_with_flaky(calls.clone())).unwrap();
+    executor.set_context_store(store.clone());
+    for _ in 0..4 {
+        let mut graph = Graph::new(def.clone());
+        executor.execute(&mut graph).await.unwrap();
+        assert_eq!(executor.metrics().nodes_failed, 1);
+    }
+    // Once open, the target is no longer called
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(store.exists("cb:breaker").await.unwrap());
+
+    // A new executor sharing the store starts with the circuit open
+    let mut executor =
+        Executor::from_graph_def_with_registry(&def, registry_with_flaky(calls.clone())).unwrap();
+    executor.set_context_store(store);
+    let mut graph = Graph::new(def);
+    executor.execute(&mut graph).await.unwrap();
+    assert_eq!(executor.metrics().nodes_failed, 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_loop_runs_body_for_each_item() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  body:
+    node_type: FlakyNode
+  each:
+    node_type: LoopNode
+    collection_key: items
+    body_node: body
+edges: []
+"#,
+        )
+        .unwrap(),
+    );
+    graph.context.set("items", json!(["a", "b", "c"]));
+
+    let executor =
+        Executor::from_graph_def_with_registry(&graph.def, registry_with_flaky(calls.clone()))
+            .unwrap();
+    executor.execute(&mut graph).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    let result = graph.context.get("each_result").unwrap();
+    assert_eq!(result["iterations"], json!(3));
+    assert_eq!(result["results"][2]["result"], json!(3));
+}
+
+#[tokio::test]
+async fn test_wrapped_node_cannot_have_edges() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut graph = Graph::new(
+        GraphIO::from_yaml(
+            r#"
+nodes:
+  flaky:
+    node_type: FlakyNode
+  retry:
+    node_type: RetryNode
+    target_node: flaky
+  after:
+    node_type: RuleNode
+    condition: "true"
+edges:
+  - from: flaky
+    to: after
+"#,
+        )
+        .unwrap(),
+    );
+
+    let executor =
+        Executor::from_graph_def_with_registry(&graph.def, registry_with_flaky(calls)).unwrap();
+    let err = executor.execute(&mut graph).await.unwrap_err();
+    assert!(err.to_string().contains("wrapped"));
+}
+
+#[tokio::test]
+async fn test_wrapper_requires_executor() {
+    let node = NodeRegistry::default()
+        .create("retry", &NodeConfig::retry_node("flaky", 1))
+        .unwrap();
+    let mut ctx = Context::new();
+    let error = node.run(&mut ctx).await.unwrap_err();
+    assert!(matches!(error, RuleError::Configuration(_)));
+    assert!(!error.is_retryable());
+}